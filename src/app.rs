@@ -1,9 +1,23 @@
 use std::fs::File;
 use std::io::Read;
+use std::time::Instant;
 
+pub mod consumer;
 pub mod importer;
+pub mod notifier;
+pub mod options;
+pub mod state;
 
-use super::app::importer::MovementImporter;
+use super::app::consumer::DirectoryLog;
+use super::app::importer::{AdminError, MovementImporter};
+use super::app::notifier::{EventFileSink, WebhookSink};
+use super::app::options::RunOptions;
+use super::model::exchange::ExchangeTable;
+use super::model::fee::{FeeImportError, FeeSchedule};
+use super::model::limit::LimitSchedule;
+use super::model::policy::DisputePolicy;
+use super::model::rule::RuleSet;
+use super::model::statement::{StatementFormat, StatementRange};
 
 //==============================================================================
 // Structure RunClientAccounting Declaration
@@ -12,8 +26,43 @@ use super::app::importer::MovementImporter;
 pub struct RunClientAccounting {
     _importer: MovementImporter,
     _stxfile: String,
+    _slogdir: String,
+    _sstatefile: String,
+    _seventsfile: String,
+    _sfeereportfile: String,
+    _slatereportfile: String,
+    _scasesfile: String,
+    _sledgerfile: String,
+    _sjournalfile: String,
+    _strialbalancefile: String,
+    _sexpected: Option<String>,
+    _sreconcilefile: String,
+    _ftolerance: f64,
+    _ssummaryfile: String,
+    _bsummary: bool,
+    _fees: FeeSchedule,
+    _limits: LimitSchedule,
+    _rules: RuleSet,
+    _srulereportfile: String,
+    _exchange: ExchangeTable,
+    _disputes: DisputePolicy,
+    _swebhookurl: String,
+    _scommand: String,
+    _vcommandargs: Vec<String>,
+    _oclient: Option<u16>,
+    _otx: Option<u32>,
+    _imaxage: u64,
+    _oamount: Option<f64>,
+    _soperator: String,
+    _sreason: String,
+    _ocurrency: Option<String>,
+    _range: StatementRange,
+    _format: StatementFormat,
     _vinput: Vec<u8>,
+    _vheader: Vec<u8>,
     _bimport: bool,
+    _bcurrencies: bool,
+    _breorderflush: bool,
     _bquiet: bool,
     _bdebug: bool,
     _ierr: i32,
@@ -42,8 +91,43 @@ impl RunClientAccounting {
         let accounting = RunClientAccounting {
             _importer: MovementImporter::new(),
             _stxfile: String::new(),
+            _slogdir: String::new(),
+            _sstatefile: String::new(),
+            _seventsfile: String::new(),
+            _sfeereportfile: String::new(),
+            _slatereportfile: String::new(),
+            _scasesfile: String::new(),
+            _sledgerfile: String::new(),
+            _sjournalfile: String::new(),
+            _strialbalancefile: String::new(),
+            _sexpected: None,
+            _sreconcilefile: String::new(),
+            _ftolerance: 0.0,
+            _ssummaryfile: String::new(),
+            _bsummary: false,
+            _fees: FeeSchedule::new(),
+            _limits: LimitSchedule::new(),
+            _rules: RuleSet::new(),
+            _srulereportfile: String::new(),
+            _exchange: ExchangeTable::new(),
+            _disputes: DisputePolicy::default(),
+            _swebhookurl: String::new(),
+            _scommand: String::new(),
+            _vcommandargs: Vec::new(),
+            _oclient: None,
+            _otx: None,
+            _imaxage: 0,
+            _oamount: None,
+            _soperator: String::new(),
+            _sreason: String::new(),
+            _ocurrency: None,
+            _range: StatementRange::All,
+            _format: StatementFormat::Csv,
             _vinput: Vec::new(),
+            _vheader: Vec::new(),
             _bimport: false,
+            _bcurrencies: false,
+            _breorderflush: false,
             _bquiet: false,
             _bdebug: false,
             _ierr: 0,
@@ -55,11 +139,254 @@ impl RunClientAccounting {
         accounting
     }
 
+    /// Creates the Application Object from the parsed Script Parameters.
+    /// The Fee, Limit, Rule, Rate and expected Account Files are read here
+    pub fn from_options(options: RunOptions) -> RunClientAccounting {
+        let mut accounting = RunClientAccounting::new();
+
+        accounting.apply_options(options);
+
+        accounting
+    }
+
     /*
     #----------------------------------------------------------------------------
     #Administration Methods
     */
 
+    fn apply_options(&mut self, options: RunOptions) {
+        self.set_quiet(options.quiet);
+        self.set_debug(options.debug);
+
+        if !options.state.is_empty() {
+            self._importer.set_state_filename(&options.state);
+        }
+
+        if let Some(clearing) = options.clearing {
+            self._importer.set_clearing_policy(clearing);
+        }
+
+        if let Some(negative_balance) = options.negative_balance {
+            self._importer.set_negative_balance_policy(negative_balance);
+        }
+
+        if let Some(iwindow) = options.reorder_window {
+            self._importer.set_reorder_window(iwindow);
+        }
+
+        if !options.fees.is_empty() {
+            self.load_fees(&options.fees);
+        }
+
+        if !options.tiers.is_empty() {
+            self.load_tiers(&options.tiers);
+        }
+
+        if !options.limits.is_empty() {
+            self.load_limits(&options.limits);
+        }
+
+        if let Some(iseconds) = options.limit_window {
+            self._limits.set_window(iseconds);
+        }
+
+        if let Some(breview) = options.limit_review {
+            self._limits.set_review(breview);
+        }
+
+        if !options.limits.is_empty()
+            || options.limit_window.is_some()
+            || options.limit_review.is_some()
+        {
+            self._importer.set_limit_schedule(self._limits.clone());
+        }
+
+        if !options.rules.is_empty() {
+            self.load_rules(&options.rules);
+        }
+
+        if !options.rates.is_empty() {
+            self.load_rates(&options.rates);
+        }
+
+        if let Some(rounding) = options.rounding {
+            self._exchange.set_rounding(rounding);
+        }
+
+        if !options.rates.is_empty() || options.rounding.is_some() {
+            self._importer.set_exchange_table(self._exchange.clone());
+        }
+
+        if let Some(iseconds) = options.dispute_window {
+            self._disputes.window = Some(iseconds);
+        }
+
+        if let Some(deadline) = options.dispute_deadline {
+            self._disputes.deadline = deadline;
+        }
+
+        if let Some(expiry) = options.dispute_expiry {
+            self._disputes.expiry = expiry;
+        }
+
+        if options.dispute_window.is_some()
+            || options.dispute_deadline.is_some()
+            || options.dispute_expiry.is_some()
+        {
+            self._importer.set_dispute_policy(self._disputes.clone());
+        }
+
+        if !options.reconcile.is_empty() {
+            self.load_expected_accounts(&options.reconcile);
+        }
+
+        self.set_transaction_filename(&options.transactions);
+        self._slogdir = options.log;
+        self._sstatefile = options.state;
+        self._seventsfile = options.events;
+        self._swebhookurl = options.webhook;
+        self._scommand = options.command;
+        self._vcommandargs = options.command_args;
+        self._oclient = options.client;
+        self._otx = options.tx;
+        self._imaxage = options.max_age.unwrap_or(0);
+        self._oamount = options.amount;
+        self._soperator = options.operator;
+        self._sreason = options.reason;
+        self._ocurrency = options.currency;
+        self._range = options.range;
+        self._format = options.format;
+        self._sfeereportfile = options.fee_report;
+        self._srulereportfile = options.rule_report;
+        self._slatereportfile = options.late_report;
+        self._scasesfile = options.cases;
+        self._sledgerfile = options.ledger;
+        self._sjournalfile = options.journal;
+        self._strialbalancefile = options.trial_balance;
+        self._sreconcilefile = options.reconcile_report;
+        self._ftolerance = options.tolerance;
+        self._bsummary = options.summary;
+        self._ssummaryfile = options.summary_file;
+        self.set_import(options.import);
+        self._bcurrencies = options.currencies;
+        self._breorderflush = options.reorder_flush;
+    }
+
+    /// Imports the Fee Rules with the Columns `type,tier,flat,percent,min,max`
+    fn load_fees(&mut self, sfilename: &str) {
+        let rsimport = match std::fs::read_to_string(sfilename) {
+            Ok(srules) => self._fees.import_rules_str(&srules),
+            Err(e) => Err(FeeImportError {
+                ok_count: 0,
+                lines: Vec::new(),
+                message: format!("File '{}': {:?}", sfilename, e),
+                code: 3,
+            }),
+        };
+
+        self.apply_fee_import("Rules", rsimport);
+    }
+
+    /// Imports the Tiers of the Clients with the Columns `client,tier`
+    fn load_tiers(&mut self, sfilename: &str) {
+        let rsimport = match std::fs::read_to_string(sfilename) {
+            Ok(stiers) => self._fees.import_tiers_str(&stiers),
+            Err(e) => Err(FeeImportError {
+                ok_count: 0,
+                lines: Vec::new(),
+                message: format!("File '{}': {:?}", sfilename, e),
+                code: 3,
+            }),
+        };
+
+        self.apply_fee_import("Tiers", rsimport);
+    }
+
+    fn apply_fee_import(&mut self, sname: &str, rsimport: Result<u32, FeeImportError>) {
+        match rsimport {
+            Ok(_) => self._importer.set_fee_schedule(self._fees.clone()),
+            Err(e) => {
+                eprintln!(
+                    "Parameter Error: Fee {} invalid in Lines {:?}: {}",
+                    sname, e.lines, e.message
+                );
+
+                self._ierr = 3;
+            }
+        }
+    }
+
+    /// Imports the Limits of the Clients with the Columns
+    /// `client,overdraft,withdrawal_limit,max_transaction,max_balance`
+    fn load_limits(&mut self, sfilename: &str) {
+        let rsimport = match std::fs::read_to_string(sfilename) {
+            Ok(slimits) => self
+                ._limits
+                .import_str(&slimits)
+                .map_err(|e| format!("Lines {:?}: {}", e.lines, e.message)),
+            Err(e) => Err(format!("File '{}': {:?}", sfilename, e)),
+        };
+
+        if let Err(serr) = rsimport {
+            eprintln!("Parameter Error: Limits invalid in {}", serr);
+
+            self._ierr = 3;
+        }
+    }
+
+    /// Imports the Fraud Rules with the Columns `rule,kind,threshold,window,action`
+    fn load_rules(&mut self, sfilename: &str) {
+        let rsimport = match std::fs::read_to_string(sfilename) {
+            Ok(srules) => self
+                ._rules
+                .import_str(&srules)
+                .map_err(|e| format!("Lines {:?}: {}", e.lines, e.message)),
+            Err(e) => Err(format!("File '{}': {:?}", sfilename, e)),
+        };
+
+        match rsimport {
+            Ok(_) => self._importer.set_rule_set(self._rules.clone()),
+            Err(serr) => {
+                eprintln!("Parameter Error: Rules invalid in {}", serr);
+
+                self._ierr = 3;
+            }
+        }
+    }
+
+    /// Imports the Exchange Rates with the Columns `date,from,to,rate,spread`
+    fn load_rates(&mut self, sfilename: &str) {
+        let rsimport = match std::fs::read_to_string(sfilename) {
+            Ok(srates) => self
+                ._exchange
+                .import_rates_str(&srates)
+                .map_err(|e| format!("Lines {:?}: {}", e.lines, e.message)),
+            Err(e) => Err(format!("File '{}': {:?}", sfilename, e)),
+        };
+
+        if let Err(serr) = rsimport {
+            eprintln!("Parameter Error: Exchange Rates invalid in {}", serr);
+
+            self._ierr = 3;
+        }
+    }
+
+    /// Reads the expected Accounts with the Columns `client,available,held,total,locked`
+    /// to reconcile them with the computed Accounts after the Run
+    fn load_expected_accounts(&mut self, sfilename: &str) {
+        match std::fs::read_to_string(sfilename) {
+            Ok(sexpected) => self._sexpected = Some(sexpected),
+            Err(e) => {
+                eprintln!(
+                    "Parameter Error: Expected Accounts File '{}': {:?}",
+                    sfilename, e
+                );
+
+                self._ierr = 3;
+            }
+        }
+    }
+
     pub fn set_transaction_filename(&mut self, sfilename: &str) {
         self._stxfile = String::from(sfilename);
     }
@@ -127,24 +454,17 @@ impl RunClientAccounting {
                                     );
                                 }
 
-                                if let Some(_) = find_last(&self._vinput, &10) {
+                                if find_last(&self._vinput, &10).is_some() {
                                     icnkcnt += 1;
 
-                                    let iimprs = match icnkcnt {
-                                        1 => self
-                                            ._importer
-                                            .import_movements_bytes(&self._vinput, true),
-                                        _ => self
-                                            ._importer
-                                            .import_movements_bytes(&self._vinput, false),
-                                    };
+                                    let iimprs = self.import_input(icnkcnt == 1);
 
                                     if iimprs != 0 {
                                         self._ierr = iimprs;
                                     }
 
                                     self._vinput.clear();
-                                } //if let Some(_) = find_last(&self._vinput, &10)
+                                } //if find_last(&self._vinput, &10).is_some()
 
                                 if ilnend < icnt {
                                     self._vinput.append(&mut vchunk[ilnend..icnt].to_owned());
@@ -160,7 +480,7 @@ impl RunClientAccounting {
                         } //match fl.read(&mut vchunk)
                     } //while irdcnt > 0 && irdcnt == ichunksize
 
-                    if self._vinput.len() > 0 {
+                    if !self._vinput.is_empty() {
                         if self._bdebug && !self._bquiet {
                             eprintln!(
                                 "input lst (sz: '{}'):\n'{:?}'\n",
@@ -169,17 +489,14 @@ impl RunClientAccounting {
                             );
                         }
 
-                        let iimprs = match icnkcnt {
-                            0 => self._importer.import_movements_bytes(&self._vinput, true),
-                            _ => self._importer.import_movements_bytes(&self._vinput, false),
-                        };
+                        let iimprs = self.import_input(icnkcnt == 0);
 
                         if iimprs != 0 {
                             self._ierr = iimprs;
                         }
 
                         self._vinput.clear();
-                    } //if self._vinput.len() > 0
+                    } //if !self._vinput.is_empty()
                 }
                 Err(e) => {
                     if !self._bquiet {
@@ -200,18 +517,600 @@ impl RunClientAccounting {
         } //if ! self._stxfile.is_empty()
     }
 
+    /// Imports the buffered Input.
+    /// The Header of the first Chunk is kept and reused for the following Chunks
+    /// so that their Columns are matched by Name
+    fn import_input(&mut self, bfirst: bool) -> i32 {
+        if bfirst {
+            self._vheader = match self._vinput.iter().position(|b| *b == 10) {
+                Some(ipos) => self._vinput[..=ipos].to_vec(),
+                None => self._vinput.clone(),
+            };
+
+            self._importer.import_movements_bytes(&self._vinput, true)
+        } else {
+            let mut vchunk = self._vheader.clone();
+
+            vchunk.extend_from_slice(&self._vinput);
+
+            self._importer.import_movements_bytes(&vchunk, true)
+        }
+    }
+
+    fn consume_from_log(&mut self) {
+        match DirectoryLog::open(self._slogdir.as_str()) {
+            Ok(mut log) => {
+                log.set_quiet(self._bquiet);
+                log.set_debug(self._bdebug);
+
+                let iconsrs = self._importer.consume_log(&mut log, 64);
+
+                if iconsrs != 0 {
+                    self._ierr = iconsrs;
+                }
+            }
+            Err(e) => {
+                if !self._bquiet {
+                    eprintln!("Movements Log Open Error: '{:?}'", e);
+                }
+
+                self._ierr = e.code as i32;
+            }
+        } //match DirectoryLog::open(self._slogdir.as_str())
+    }
+
     fn export_accounts(&self) -> i32 {
-        let data = self._importer.export_accounts_str();
+        let data = if self._bcurrencies {
+            self._importer.export_currencies_str()
+        } else {
+            self._importer.export_accounts_str()
+        };
 
         print!("{}", &data);
 
         self._ierr
     }
 
-    pub fn do_run(&mut self) -> i32 {
-        self.import_from_file();
+    fn export_fee_report(&mut self) {
+        if let Err(e) = std::fs::write(
+            self._sfeereportfile.as_str(),
+            self._importer.export_fee_report_str(),
+        ) {
+            if !self._bquiet {
+                eprintln!("Fee Report Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn export_late_report(&mut self) {
+        if let Err(e) = std::fs::write(
+            self._slatereportfile.as_str(),
+            self._importer.export_late_arrivals_str(),
+        ) {
+            if !self._bquiet {
+                eprintln!("Late Arrivals Report Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn export_rule_report(&mut self) {
+        if let Err(e) = std::fs::write(
+            self._srulereportfile.as_str(),
+            self._importer.export_rule_hits_str(),
+        ) {
+            if !self._bquiet {
+                eprintln!("Rule Hits Report Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn export_cases(&mut self) {
+        if let Err(e) = std::fs::write(self._scasesfile.as_str(), self._importer.export_cases_str())
+        {
+            if !self._bquiet {
+                eprintln!("Dispute Cases Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn export_ledger(&mut self) {
+        if let Err(e) = std::fs::write(
+            self._sledgerfile.as_str(),
+            self._importer.export_ledger_str(self._oclient),
+        ) {
+            if !self._bquiet {
+                eprintln!("Ledger Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn export_journal(&mut self) {
+        if let Err(e) = std::fs::write(
+            self._sjournalfile.as_str(),
+            self._importer.export_journal_str(),
+        ) {
+            if !self._bquiet {
+                eprintln!("Journal Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn export_trial_balance(&mut self) {
+        if !self._importer.get_journal().is_balanced() {
+            if !self._bquiet {
+                eprintln!("Trial Balance Error: The Journal is not balanced.");
+            }
+
+            self._ierr = 2;
+        }
+
+        if let Err(e) = std::fs::write(
+            self._strialbalancefile.as_str(),
+            self._importer.export_trial_balance_str(),
+        ) {
+            if !self._bquiet {
+                eprintln!("Trial Balance Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+    }
+
+    fn reconcile_accounts(&mut self, sexpected: &str) {
+        let reconciliation = self._importer.reconcile(sexpected, self._ftolerance);
+        let sdiff = reconciliation.export_csv(self._bdebug, self._bquiet);
+
+        if self._sreconcilefile.is_empty() {
+            if !reconciliation.is_clean() {
+                eprint!("{}", &sdiff);
+            }
+        } else if let Err(e) = std::fs::write(self._sreconcilefile.as_str(), &sdiff) {
+            if !self._bquiet {
+                eprintln!("Reconciliation Write Error: '{:?}'", e);
+            }
+
+            self._ierr = 3;
+        }
+
+        if !reconciliation.is_clean() {
+            if !self._bquiet {
+                eprintln!(
+                    "Reconciliation Error: '{}' Differences found.",
+                    reconciliation.vdiffs.len()
+                );
+            }
+
+            self._ierr = 2;
+        }
+    }
+
+    fn report_summary(&mut self) {
+        let summary = self._importer.get_summary();
+
+        if self._bsummary {
+            eprint!("{}", summary.export_text());
+        }
+
+        if !self._ssummaryfile.is_empty() {
+            if let Err(e) = std::fs::write(
+                self._ssummaryfile.as_str(),
+                summary.export_json(self._bquiet),
+            ) {
+                if !self._bquiet {
+                    eprintln!("Summary Write Error: '{:?}'", e);
+                }
+
+                self._ierr = 3;
+            }
+        }
+    }
+
+    fn add_event_sinks(&mut self) {
+        if !self._seventsfile.is_empty() {
+            let sink = EventFileSink::from_filename(self._seventsfile.as_str(), self._bquiet);
+
+            if sink.get_error_code() != 0 {
+                self._ierr = sink.get_error_code();
+            }
+
+            self._importer.add_observer(Box::new(sink));
+        }
+
+        if !self._swebhookurl.is_empty() {
+            match WebhookSink::new(self._swebhookurl.as_str()) {
+                Some(mut sink) => {
+                    sink.set_quiet(self._bquiet);
+
+                    self._importer.add_observer(Box::new(sink));
+                }
+                None => {
+                    if !self._bquiet {
+                        eprintln!(
+                            "Events Webhook Error: URL '{}' is invalid.",
+                            self._swebhookurl
+                        );
+                    }
+
+                    self._ierr = 3;
+                }
+            } //match WebhookSink::new(self._swebhookurl.as_str())
+        } //if !self._swebhookurl.is_empty()
+    }
+
+    /// Delivers the collected Events. Failed Deliveries fail the Run
+    fn flush_events(&mut self) {
+        let ierr = self._importer.flush_events();
+
+        if ierr != 0 {
+            if !self._bquiet {
+                eprintln!("Events Error: Events could not be delivered.");
+            }
+
+            self._ierr = ierr;
+        }
+    }
+
+    fn load_state(&mut self) {
+        if let Err(e) = self._importer.load_state() {
+            if !self._bquiet {
+                eprintln!("State Load Error: '{:?}'", e);
+            }
+
+            self._ierr = e.code as i32;
+        }
+    }
+
+    fn save_state(&mut self) {
+        if let Err(e) = self._importer.save_state() {
+            if !self._bquiet {
+                eprintln!("State Save Error: '{:?}'", e);
+            }
+
+            self._ierr = e.code as i32;
+        }
+    }
+
+    fn run_admin(&mut self) {
+        if self._sstatefile.is_empty() {
+            if !self._bquiet {
+                eprintln!("Admin Error: State File is missing.");
+            }
+
+            self._ierr = 3;
+
+            return;
+        }
+
+        let iclient = match self._oclient {
+            Some(ic) => ic,
+            None => {
+                if !self._bquiet {
+                    eprintln!("Admin Error: Client is missing.");
+                }
+
+                self._ierr = 3;
+
+                return;
+            }
+        };
+
+        self.load_state();
+
+        if self._ierr != 0 {
+            return;
+        }
+
+        let soperation = match self._vcommandargs.first() {
+            Some(sop) => sop.to_lowercase(),
+            None => String::new(),
+        };
+        let soperator = self._soperator.clone();
+        let sreason = self._sreason.clone();
+        let rsadmin = match (soperation.as_str(), self._oamount) {
+            ("unlock", _) => self._importer.unlock_account(iclient, &soperator, &sreason),
+            ("freeze", _) => self._importer.freeze_account(iclient, &soperator, &sreason),
+            ("credit", Some(famount)) => {
+                self._importer
+                    .adjust_account(iclient, famount.abs(), &soperator, &sreason)
+            }
+            ("debit", Some(famount)) => {
+                self._importer
+                    .adjust_account(iclient, -famount.abs(), &soperator, &sreason)
+            }
+            ("credit", None) | ("debit", None) => Err(AdminError::new(
+                String::from("Adjustment Amount is missing."),
+                3,
+            )),
+            _ => Err(AdminError::new(
+                format!("Admin Operation '{}': unknown", soperation),
+                3,
+            )),
+        };
+
+        match rsadmin {
+            Ok(itxid) => {
+                if !self._bquiet {
+                    eprintln!(
+                        "Admin Operation '{}' recorded as Transaction (id: '{}').",
+                        soperation, itxid
+                    );
+                }
+            }
+            Err(e) => {
+                if !self._bquiet {
+                    eprintln!("Admin Error: '{}'", e.message);
+                }
+
+                self._ierr = e.code as i32;
+            }
+        } //match rsadmin
+
+        self.flush_events();
+
+        //Failed Operations are recorded in the History too
+        self.save_state();
+        self.export_accounts();
+    }
+
+    fn run_statement(&mut self) {
+        if self._sstatefile.is_empty() {
+            if !self._bquiet {
+                eprintln!("Statement Error: State File is missing.");
+            }
+
+            self._ierr = 3;
+
+            return;
+        }
+
+        let iclient = match self._oclient {
+            Some(ic) => ic,
+            None => {
+                if !self._bquiet {
+                    eprintln!("Statement Error: Client is missing.");
+                }
+
+                self._ierr = 3;
+
+                return;
+            }
+        };
+
+        self.load_state();
+
+        if self._ierr != 0 {
+            return;
+        }
+
+        print!(
+            "{}",
+            self._importer.export_statement_str(
+                iclient,
+                self._ocurrency.as_deref(),
+                &self._range,
+                self._format
+            )
+        );
+    }
+
+    fn run_review(&mut self) {
+        if self._sstatefile.is_empty() {
+            if !self._bquiet {
+                eprintln!("Review Error: State File is missing.");
+            }
+
+            self._ierr = 3;
 
+            return;
+        }
+
+        self.load_state();
+
+        if self._ierr != 0 {
+            return;
+        }
+
+        let soperation = match self._vcommandargs.first() {
+            Some(sop) => sop.to_lowercase(),
+            None => String::from("list"),
+        };
+
+        if soperation == "list" {
+            print!("{}", self._importer.export_review_str());
+
+            return;
+        }
+
+        if soperation == "aged" {
+            print!("{}", self._importer.export_aged_review_str(self._imaxage));
+
+            return;
+        }
+
+        let soperator = self._soperator.clone();
+        let sreason = self._sreason.clone();
+        let rsreview = match (soperation.as_str(), self._otx) {
+            ("approve", Some(itx)) => self._importer.approve_movement(itx, &soperator),
+            ("reject", Some(itx)) => self
+                ._importer
+                .reject_held_movement(itx, &soperator, &sreason),
+            ("approve", None) | ("reject", None) => {
+                Err(AdminError::new(String::from("Transaction is missing."), 3))
+            }
+            _ => Err(AdminError::new(
+                format!("Review Operation '{}': unknown", soperation),
+                3,
+            )),
+        };
+
+        match rsreview {
+            Ok(itxid) => {
+                if !self._bquiet {
+                    eprintln!(
+                        "Review Operation '{}' on Movement (id: '{}') done.",
+                        soperation, itxid
+                    );
+                }
+
+                self.save_state();
+            }
+            Err(e) => {
+                if !self._bquiet {
+                    eprintln!("Review Error: '{}'", e.message);
+                }
+
+                self._ierr = e.code as i32;
+            }
+        } //match rsreview
+
+        self.flush_events();
         self.export_accounts();
+    }
+
+    fn run_audit(&mut self) {
+        if self._sstatefile.is_empty() {
+            if !self._bquiet {
+                eprintln!("Audit Error: State File is missing.");
+            }
+
+            self._ierr = 3;
+
+            return;
+        }
+
+        self.load_state();
+
+        if self._ierr != 0 {
+            return;
+        }
+
+        let report = self._importer.audit();
+
+        print!("{}", report.export_csv(self._bdebug, self._bquiet));
+
+        if !report.is_clean() {
+            if !self._bquiet {
+                eprintln!(
+                    "Audit Error: '{}' Violations found.",
+                    report.vviolations.len()
+                );
+            }
+
+            self._ierr = 2;
+        }
+    }
+
+    fn run_import(&mut self, istart: Instant) {
+        if !self._sstatefile.is_empty() {
+            self.load_state();
+
+            if self._ierr != 0 {
+                //Do not process Movements on an unknown State
+                return;
+            }
+        }
+
+        if !self._slogdir.is_empty() {
+            self.consume_from_log();
+        } else {
+            self.import_from_file();
+        }
+
+        //Apply the Movements left in the Reorder Buffer.
+        //With a State File they wait for the next Run unless this is the final Run
+        if self._sstatefile.is_empty() || self._breorderflush {
+            self._importer.flush_movements();
+        }
+
+        //Release the Deposits whose Clearing Window has passed
+        self._importer.process_clearing();
+
+        //Close the Disputes whose Deadline has passed
+        self._importer.process_expiry();
+
+        self.flush_events();
+
+        self._importer
+            .add_elapsed(istart.elapsed().as_millis() as u64);
+
+        if !self._sstatefile.is_empty() {
+            self.save_state();
+        }
+
+        if !self._sfeereportfile.is_empty() {
+            self.export_fee_report();
+        }
+
+        if !self._slatereportfile.is_empty() {
+            self.export_late_report();
+        }
+
+        if !self._srulereportfile.is_empty() {
+            self.export_rule_report();
+        }
+
+        if !self._scasesfile.is_empty() {
+            self.export_cases();
+        }
+
+        if !self._sledgerfile.is_empty() {
+            self.export_ledger();
+        }
+
+        if !self._sjournalfile.is_empty() {
+            self.export_journal();
+        }
+
+        if !self._strialbalancefile.is_empty() {
+            self.export_trial_balance();
+        }
+
+        self.export_accounts();
+
+        if let Some(sexpected) = self._sexpected.take() {
+            self.reconcile_accounts(&sexpected);
+        }
+    }
+
+    pub fn do_run(&mut self) -> i32 {
+        let istart = Instant::now();
+
+        if self._ierr != 0 {
+            //Invalid Parameters
+            return self._ierr;
+        }
+
+        self.add_event_sinks();
+
+        if self._ierr != 0 {
+            return self._ierr;
+        }
+
+        match self._scommand.as_str() {
+            "admin" => self.run_admin(),
+            "statement" => self.run_statement(),
+            "audit" => self.run_audit(),
+            "review" => self.run_review(),
+            _ => {
+                self.run_import(istart);
+
+                if self._bsummary || !self._ssummaryfile.is_empty() {
+                    self.report_summary();
+                }
+            }
+        }
 
         self._ierr
     }
@@ -220,6 +1119,10 @@ impl RunClientAccounting {
      * Consultation Methods
      */
 
+    pub fn get_command(&self) -> &str {
+        self._scommand.as_str()
+    }
+
     pub fn is_import(&self) -> bool {
         self._bimport
     }
@@ -250,8 +1153,8 @@ fn find_last<T: PartialEq>(vvector: &[T], needle: &T) -> Option<usize> {
         if let Some(item) = oitem {
             if item == needle {
                 oipos = Some(iitempos);
-            } else if iitempos > 0 {
-                iitempos -= 1;
+            } else {
+                iitempos = iitempos.saturating_sub(1);
             }
 
             oitem = iter.next();
@@ -0,0 +1,603 @@
+use csv::{ReaderBuilder, Trim, WriterBuilder};
+use serde::{Deserialize, Serialize};
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use super::super::model::transaction::Movement;
+
+//==============================================================================
+// Structure LogRecord Declaration
+
+/// One Record read from a Partition of the Log.
+/// The `offset` is the Position of the Record within its Partition
+/// and the `payload` is one Movement CSV Line without Line Break
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub partition: u16,
+    pub offset: u64,
+    pub payload: String,
+}
+
+//==============================================================================
+// Structure PartitionOffset Declaration
+
+/// The committed Offset of a Partition is the Offset of the next Record
+/// that has not been processed yet
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PartitionOffset {
+    pub partition: u16,
+    pub offset: u64,
+}
+
+//==============================================================================
+// Structure OffsetRow Declaration
+
+/// A Row of the File `offsets.csv`.
+/// The `position` is the Byte Position of the committed Offset within the Partition File
+#[derive(Debug, Deserialize, Serialize)]
+struct OffsetRow {
+    partition: u16,
+    offset: u64,
+    #[serde(default)]
+    position: Option<u64>,
+}
+
+//==============================================================================
+// Structure LogError Declaration
+
+#[derive(Debug)]
+pub struct LogError {
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure LogError Implementation
+
+impl LogError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(error_message: String, error_code: i8) -> LogError {
+        LogError {
+            message: error_message,
+            code: error_code,
+        }
+    }
+}
+
+//==============================================================================
+// Trait LogConsumer Declaration
+
+/// Consumer of a partitioned append-only Log like a _Kafka_ Topic.
+/// Records are delivered in order per Partition and the Consumer resumes
+/// from the committed Offset of each Partition
+pub trait LogConsumer {
+    fn get_partition_count(&self) -> u16;
+
+    /// Reads up to `imaxrecords` Records from the current Positions
+    /// and advances the Positions behind the returned Records
+    fn poll(&mut self, imaxrecords: usize) -> Result<Vec<LogRecord>, LogError>;
+
+    /// Moves the reading Position of the Partition to the Offset
+    fn seek(&mut self, ipartition: u16, ioffset: u64);
+
+    /// Records `ioffset` as the next Offset to process in the Partition
+    fn commit(&mut self, ipartition: u16, ioffset: u64) -> Result<(), LogError>;
+
+    fn get_position(&self, ipartition: u16) -> u64;
+
+    fn get_committed_offset(&self, ipartition: u16) -> u64;
+}
+
+//==============================================================================
+// Structure DirectoryLog Declaration
+
+/// Local Stand-in for a _Kafka_ Topic.
+/// Each Partition is a File `partition-<n>.log` with one Record per Line
+/// and the committed Offsets are kept in the File `offsets.csv`.
+/// The Byte Positions of the Reading Positions and of the committed Offsets are kept
+/// so that a Poll continues reading where it stopped instead of rereading the Partition.
+/// The End Offsets are counted once when the Log is opened and then advanced by each Append
+#[derive(Debug)]
+pub struct DirectoryLog {
+    _pdirectory: PathBuf,
+    _vpositions: Vec<u64>,
+    _vbytes: Vec<Option<u64>>,
+    _vcommitted: Vec<u64>,
+    _vcommittedbytes: Vec<Option<u64>>,
+    _vends: Vec<u64>,
+    _bquiet: bool,
+    _bdebug: bool,
+}
+
+//==============================================================================
+// Structure DirectoryLog Implementation
+
+#[allow(dead_code)]
+impl DirectoryLog {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Creates the Log Directory with `ipartitions` empty Partitions
+    /// if they do not exist yet
+    pub fn create(sdirectory: &str, ipartitions: u16) -> Result<DirectoryLog, LogError> {
+        if ipartitions == 0 {
+            return Err(LogError::new(
+                String::from("Partition Count must not be '0'"),
+                3,
+            ));
+        }
+
+        let pdirectory = PathBuf::from(sdirectory);
+
+        if let Err(e) = fs::create_dir_all(&pdirectory) {
+            return Err(LogError::new(
+                format!("Log Directory '{}': Create failed: '{:?}'", sdirectory, e),
+                1,
+            ));
+        }
+
+        for ipartition in 0..ipartitions {
+            let ppartition = partition_path(&pdirectory, ipartition);
+
+            if let Err(e) = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&ppartition)
+            {
+                return Err(LogError::new(
+                    format!("Log Partition '{}': Create failed: '{:?}'", ipartition, e),
+                    1,
+                ));
+            }
+        } //for ipartition in 0..ipartitions
+
+        DirectoryLog::open(sdirectory)
+    }
+
+    /// Opens an existing Log Directory and loads the committed Offsets.
+    /// The Reading Positions start at the committed Offsets
+    pub fn open(sdirectory: &str) -> Result<DirectoryLog, LogError> {
+        let pdirectory = PathBuf::from(sdirectory);
+        let mut ipartitions = 0;
+
+        while partition_path(&pdirectory, ipartitions).is_file() {
+            ipartitions += 1;
+        }
+
+        if ipartitions == 0 {
+            return Err(LogError::new(
+                format!("Log Directory '{}': No Partitions found", sdirectory),
+                3,
+            ));
+        }
+
+        let mut log = DirectoryLog {
+            _pdirectory: pdirectory,
+            _vpositions: vec![0; ipartitions as usize],
+            _vbytes: vec![Some(0); ipartitions as usize],
+            _vcommitted: vec![0; ipartitions as usize],
+            _vcommittedbytes: vec![Some(0); ipartitions as usize],
+            _vends: Vec::with_capacity(ipartitions as usize),
+            _bquiet: false,
+            _bdebug: false,
+        };
+
+        for ipartition in 0..ipartitions {
+            let iend = log.count_records(ipartition)?;
+
+            log._vends.push(iend);
+        }
+
+        log.load_offsets()?;
+
+        log._vpositions = log._vcommitted.clone();
+        log._vbytes = log._vcommittedbytes.clone();
+
+        Ok(log)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    pub fn set_quiet(&mut self, bquiet: bool) {
+        self._bquiet = bquiet;
+    }
+
+    pub fn set_debug(&mut self, bdebug: bool) {
+        self._bdebug = bdebug;
+    }
+
+    /// Appends one Record to the end of the Partition
+    pub fn append(&mut self, ipartition: u16, spayload: &str) -> Result<u64, LogError> {
+        if ipartition >= self.get_partition_count() {
+            return Err(LogError::new(
+                format!("Log Partition '{}': does not exist", ipartition),
+                3,
+            ));
+        }
+
+        let ioffset = self._vends[ipartition as usize];
+
+        match OpenOptions::new()
+            .append(true)
+            .open(partition_path(&self._pdirectory, ipartition))
+        {
+            Ok(mut fl) => match writeln!(fl, "{}", spayload.trim_end()) {
+                Ok(_) => {
+                    self._vends[ipartition as usize] += 1;
+
+                    Ok(ioffset)
+                }
+                Err(e) => Err(LogError::new(
+                    format!("Log Partition '{}': Write failed: '{:?}'", ipartition, e),
+                    1,
+                )),
+            },
+            Err(e) => Err(LogError::new(
+                format!("Log Partition '{}': Open failed: '{:?}'", ipartition, e),
+                1,
+            )),
+        } //match OpenOptions::new().append(true).open(...)
+    }
+
+    /// Appends Movement CSV Text to the Log partitioned by the `client` Column.
+    /// So all Movements of one Client keep their Order in the same Partition.
+    /// With `bheaders` the Columns are matched by their Names, so reordered or partial Headers
+    /// are accepted. Each Record is written to the Log in the standard Column Order
+    pub fn append_movements_str(
+        &mut self,
+        smovements_csv: &str,
+        bheaders: bool,
+    ) -> Result<u32, LogError> {
+        let mut rdr = ReaderBuilder::new()
+            .has_headers(bheaders)
+            .trim(Trim::All)
+            .flexible(true)
+            .from_reader(smovements_csv.as_bytes());
+        let mut icount = 0;
+
+        for result in rdr.deserialize() {
+            match result {
+                Ok(r) => {
+                    let mvrecord: Movement = r;
+                    let mut wtr = WriterBuilder::new().has_headers(false).from_writer(vec![]);
+
+                    if let Err(e) = wtr.serialize(&mvrecord) {
+                        return Err(LogError::new(
+                            format!("Movement CSV Write Error: '{:?}'", e),
+                            1,
+                        ));
+                    }
+
+                    let mut sline = match wtr.into_inner() {
+                        Ok(vline) => String::from_utf8_lossy(&vline).trim_end().to_string(),
+                        Err(e) => {
+                            return Err(LogError::new(
+                                format!("Movement CSV Write Error: '{:?}'", e),
+                                1,
+                            ));
+                        }
+                    };
+
+                    //Empty optional Columns after the `amount` Column are left out
+                    while sline.ends_with(',') && sline.matches(',').count() > 3 {
+                        sline.pop();
+                    }
+
+                    self.append(mvrecord.client % self.get_partition_count(), &sline)?;
+
+                    icount += 1;
+                }
+                Err(e) => {
+                    return Err(LogError::new(
+                        format!("Movement CSV Parse Error: '{:?}'", e),
+                        1,
+                    ));
+                }
+            } //match result
+        } //for result in rdr.deserialize()
+
+        Ok(icount)
+    }
+
+    fn load_offsets(&mut self) -> Result<(), LogError> {
+        let poffsets = self._pdirectory.join("offsets.csv");
+
+        if !poffsets.is_file() {
+            //Nothing was committed yet
+            return Ok(());
+        }
+
+        let mut rdr = match ReaderBuilder::new().trim(Trim::All).from_path(&poffsets) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(LogError::new(
+                    format!("Log Offsets: Open failed: '{:?}'", e),
+                    1,
+                ));
+            }
+        };
+
+        for result in rdr.deserialize() {
+            match result {
+                Ok(r) => {
+                    let row: OffsetRow = r;
+                    let ipartition = row.partition as usize;
+
+                    if ipartition < self._vcommitted.len() {
+                        //Offsets from before the Byte Positions are found by reading the Partition
+                        self._vcommitted[ipartition] = row.offset;
+                        self._vcommittedbytes[ipartition] = row.position;
+                    }
+                }
+                Err(e) => {
+                    return Err(LogError::new(
+                        format!("Log Offsets: Parse failed: '{:?}'", e),
+                        1,
+                    ));
+                }
+            } //match result
+        } //for result in rdr.deserialize()
+
+        Ok(())
+    }
+
+    fn save_offsets(&self) -> Result<(), LogError> {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        for (ipartition, ioffset) in self._vcommitted.iter().enumerate() {
+            if let Err(e) = wtr.serialize(OffsetRow {
+                partition: ipartition as u16,
+                offset: *ioffset,
+                position: self._vcommittedbytes[ipartition],
+            }) {
+                return Err(LogError::new(
+                    format!("Log Offsets: Write failed: '{:?}'", e),
+                    1,
+                ));
+            }
+        }
+
+        match wtr.into_inner() {
+            Ok(vdata) => write_file_atomic(&self._pdirectory.join("offsets.csv"), &vdata),
+            Err(e) => Err(LogError::new(
+                format!("Log Offsets: Write failed: '{:?}'", e),
+                1,
+            )),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// The Offset that the next appended Record will get
+    pub fn get_end_offset(&self, ipartition: u16) -> Result<u64, LogError> {
+        match self._vends.get(ipartition as usize) {
+            Some(iend) => Ok(*iend),
+            None => Err(LogError::new(
+                format!("Log Partition '{}': does not exist", ipartition),
+                3,
+            )),
+        }
+    }
+
+    /// Counts the Records in the Partition File
+    fn count_records(&self, ipartition: u16) -> Result<u64, LogError> {
+        match File::open(partition_path(&self._pdirectory, ipartition)) {
+            Ok(fl) => Ok(BufReader::new(fl).lines().count() as u64),
+            Err(e) => Err(LogError::new(
+                format!("Log Partition '{}': Open failed: '{:?}'", ipartition, e),
+                1,
+            )),
+        }
+    }
+
+    pub fn get_committed_offsets(&self) -> Vec<PartitionOffset> {
+        self._vcommitted
+            .iter()
+            .enumerate()
+            .map(|(ipartition, ioffset)| PartitionOffset {
+                partition: ipartition as u16,
+                offset: *ioffset,
+            })
+            .collect()
+    }
+
+    pub fn get_directory(&self) -> &Path {
+        self._pdirectory.as_path()
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        self._bquiet
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self._bdebug
+    }
+}
+
+impl LogConsumer for DirectoryLog {
+    fn get_partition_count(&self) -> u16 {
+        self._vpositions.len() as u16
+    }
+
+    fn poll(&mut self, imaxrecords: usize) -> Result<Vec<LogRecord>, LogError> {
+        let mut vrecords = Vec::new();
+
+        for ipartition in 0..self.get_partition_count() {
+            if vrecords.len() >= imaxrecords {
+                break;
+            }
+
+            let ipidx = ipartition as usize;
+            let ipos = self._vpositions[ipidx];
+            let mut fl = match File::open(partition_path(&self._pdirectory, ipartition)) {
+                Ok(f) => f,
+                Err(e) => {
+                    return Err(LogError::new(
+                        format!("Log Partition '{}': Open failed: '{:?}'", ipartition, e),
+                        1,
+                    ));
+                }
+            };
+            let iend = fl.metadata().map(|m| m.len()).unwrap_or(0);
+            //Without a known Byte Position the Partition is read from its Start
+            let (mut ioffset, mut ibyte) = match self._vbytes[ipidx].filter(|ib| *ib <= iend) {
+                Some(ib) => (ipos, ib),
+                None => (0, 0),
+            };
+
+            if let Err(e) = fl.seek(SeekFrom::Start(ibyte)) {
+                return Err(LogError::new(
+                    format!("Log Partition '{}': Seek failed: '{:?}'", ipartition, e),
+                    1,
+                ));
+            }
+
+            let mut rdr = BufReader::new(fl);
+            let mut sline = String::new();
+
+            while vrecords.len() < imaxrecords {
+                sline.clear();
+
+                let iread = match rdr.read_line(&mut sline) {
+                    Ok(iread) => iread,
+                    Err(e) => {
+                        return Err(LogError::new(
+                            format!("Log Partition '{}': Read failed: '{:?}'", ipartition, e),
+                            1,
+                        ));
+                    }
+                };
+
+                if iread == 0 {
+                    break;
+                }
+
+                ibyte += iread as u64;
+
+                if ioffset >= ipos {
+                    vrecords.push(LogRecord {
+                        partition: ipartition,
+                        offset: ioffset,
+                        payload: String::from(sline.trim_end_matches(['\n', '\r'])),
+                    });
+                }
+
+                ioffset += 1;
+            } //while vrecords.len() < imaxrecords
+
+            if ioffset >= ipos {
+                self._vpositions[ipidx] = ioffset;
+                self._vbytes[ipidx] = Some(ibyte);
+            }
+        } //for ipartition in 0..self.get_partition_count()
+
+        if self._bdebug && !self._bquiet {
+            eprintln!("Log Poll Count: '{}'", vrecords.len());
+        }
+
+        Ok(vrecords)
+    }
+
+    fn seek(&mut self, ipartition: u16, ioffset: u64) {
+        let ipidx = ipartition as usize;
+
+        if ipidx < self._vpositions.len() && self._vpositions[ipidx] != ioffset {
+            //The Byte Position is only known for the committed Offset
+            self._vbytes[ipidx] = if self._vcommitted[ipidx] == ioffset {
+                self._vcommittedbytes[ipidx]
+            } else {
+                None
+            };
+            self._vpositions[ipidx] = ioffset;
+        }
+    }
+
+    fn commit(&mut self, ipartition: u16, ioffset: u64) -> Result<(), LogError> {
+        let ipidx = ipartition as usize;
+
+        match self._vcommitted.get_mut(ipidx) {
+            Some(icommitted) => {
+                *icommitted = ioffset;
+                self._vcommittedbytes[ipidx] = if self._vpositions[ipidx] == ioffset {
+                    self._vbytes[ipidx]
+                } else {
+                    None
+                };
+            }
+            None => {
+                return Err(LogError::new(
+                    format!("Log Partition '{}': does not exist", ipartition),
+                    3,
+                ));
+            }
+        }
+
+        self.save_offsets()
+    }
+
+    fn get_position(&self, ipartition: u16) -> u64 {
+        match self._vpositions.get(ipartition as usize) {
+            Some(ipos) => *ipos,
+            None => 0,
+        }
+    }
+
+    fn get_committed_offset(&self, ipartition: u16) -> u64 {
+        match self._vcommitted.get(ipartition as usize) {
+            Some(ioffset) => *ioffset,
+            None => 0,
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+fn partition_path(pdirectory: &Path, ipartition: u16) -> PathBuf {
+    pdirectory.join(format!("partition-{}.log", ipartition))
+}
+
+/// Replaces the File in one Step by writing a temporary File first
+/// and renaming it over the original File
+pub fn write_file_atomic(pfile: &Path, vdata: &[u8]) -> Result<(), LogError> {
+    let mut stmpname = pfile.as_os_str().to_owned();
+
+    stmpname.push(".tmp");
+
+    let ptmp = PathBuf::from(stmpname);
+
+    match File::create(&ptmp) {
+        Ok(mut fl) => {
+            if let Err(e) = fl.write_all(vdata).and_then(|_| fl.sync_all()) {
+                return Err(LogError::new(
+                    format!("File '{}': Write failed: '{:?}'", ptmp.display(), e),
+                    1,
+                ));
+            }
+        }
+        Err(e) => {
+            return Err(LogError::new(
+                format!("File '{}': Create failed: '{:?}'", ptmp.display(), e),
+                1,
+            ));
+        }
+    } //match File::create(&ptmp)
+
+    match fs::rename(&ptmp, pfile) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(LogError::new(
+            format!("File '{}': Rename failed: '{:?}'", pfile.display(), e),
+            1,
+        )),
+    }
+}
@@ -1,5 +1,71 @@
-use super::super::model::account::AccountFactory;
-use super::super::model::transaction::TransactionFactory;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::super::model::account::{Account, AccountFactory};
+use super::super::model::audit::AuditReport;
+use super::super::model::dispute::DisputeCase;
+use super::super::model::event::AccountEvent;
+use super::super::model::event::RejectReason;
+use super::super::model::exchange::ExchangeTable;
+use super::super::model::fee::FeeSchedule;
+use super::super::model::journal::Journal;
+use super::super::model::ledger::{Ledger, LedgerEntry};
+use super::super::model::limit::{LimitSchedule, WithdrawalWindow};
+use super::super::model::policy::{
+    ClearingPolicy, DisputeDeadline, DisputePolicy, NegativeBalancePolicy, ProcessingPolicy,
+};
+use super::super::model::reconcile::Reconciliation;
+use super::super::model::review::ReviewQueue;
+use super::super::model::round_amount;
+use super::super::model::rule::{export_hits_csv, RuleAction, RuleHit, RuleSet};
+use super::super::model::statement::{Statement, StatementFormat, StatementRange};
+use super::super::model::summary::RunSummary;
+use super::super::model::transaction::{
+    Movement, Transaction, TransactionFactory, INTERNAL_ID_START,
+};
+use super::consumer::{LogConsumer, LogError, PartitionOffset};
+use super::notifier::EventObserver;
+use super::state::{StateError, StateSnapshot};
+
+//==============================================================================
+// Structure AdminError Declaration
+
+#[derive(Debug)]
+pub struct AdminError {
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure AdminError Implementation
+
+impl AdminError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(error_message: String, error_code: i8) -> AdminError {
+        AdminError {
+            message: error_message,
+            code: error_code,
+        }
+    }
+}
+
+//==============================================================================
+// Structure LateArrival Declaration
+
+/// A Movement which arrived after Movements that are newer than the Reorder Window
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LateArrival {
+    pub tx_type: String,
+    pub client: u16,
+    pub tx: u32,
+    pub timestamp: u64,
+    /// The newest Timestamp seen before the Movement arrived
+    pub newest: u64,
+}
 
 //==============================================================================
 // Structure MovementImporter Declaration
@@ -8,6 +74,23 @@ use super::super::model::transaction::TransactionFactory;
 pub struct MovementImporter {
     _accfact: AccountFactory,
     _txfact: TransactionFactory,
+    _ledger: Ledger,
+    _voffsets: Vec<PartitionOffset>,
+    _sstatefile: String,
+    _vobservers: Vec<Box<dyn EventObserver>>,
+    _policy: ProcessingPolicy,
+    _vclearing: Vec<u32>,
+    _vdisputes: Vec<u32>,
+    _withdrawals: WithdrawalWindow,
+    _oclock: Option<u64>,
+    _oreorder: Option<u64>,
+    _vbuffer: Vec<Movement>,
+    _onewest: Option<u64>,
+    _vlate: Vec<LateArrival>,
+    _vhits: Vec<RuleHit>,
+    _review: ReviewQueue,
+    _orelease: Option<(u32, bool)>,
+    _summary: RunSummary,
     _bquiet: bool,
     _bdebug: bool,
     _ierr: i32,
@@ -36,6 +119,23 @@ impl MovementImporter {
         MovementImporter {
             _accfact: AccountFactory::new(),
             _txfact: TransactionFactory::new(),
+            _ledger: Ledger::new(),
+            _voffsets: Vec::new(),
+            _sstatefile: String::new(),
+            _vobservers: Vec::new(),
+            _policy: ProcessingPolicy::new(),
+            _vclearing: Vec::new(),
+            _vdisputes: Vec::new(),
+            _withdrawals: WithdrawalWindow::new(),
+            _oclock: None,
+            _oreorder: None,
+            _vbuffer: Vec::new(),
+            _onewest: None,
+            _vlate: Vec::new(),
+            _vhits: Vec::new(),
+            _review: ReviewQueue::new(),
+            _orelease: None,
+            _summary: RunSummary::new(),
             _bquiet: false,
             _bdebug: false,
             _ierr: 0,
@@ -54,18 +154,533 @@ impl MovementImporter {
         self._bdebug = bdebug;
     }
 
+    pub fn set_state_filename(&mut self, sfilename: &str) {
+        self._sstatefile = String::from(sfilename);
+    }
+
+    /// Registers an Observer that receives all Account Events in their Order.
+    /// The Observers deliver the Events on `flush_events()`
+    pub fn add_observer(&mut self, observer: Box<dyn EventObserver>) {
+        self._vobservers.push(observer);
+    }
+
+    pub fn set_processing_policy(&mut self, policy: ProcessingPolicy) {
+        self._policy = policy;
+    }
+
+    pub fn set_clearing_policy(&mut self, clearing: ClearingPolicy) {
+        self._policy.clearing = clearing;
+    }
+
+    pub fn set_negative_balance_policy(&mut self, negative_balance: NegativeBalancePolicy) {
+        self._policy.negative_balance = negative_balance;
+    }
+
+    pub fn set_fee_schedule(&mut self, fees: FeeSchedule) {
+        self._policy.fees = fees;
+    }
+
+    pub fn set_limit_schedule(&mut self, limits: LimitSchedule) {
+        self._policy.limits = limits;
+    }
+
+    pub fn set_rule_set(&mut self, rules: RuleSet) {
+        self._policy.rules = rules;
+    }
+
+    pub fn set_exchange_table(&mut self, exchange: ExchangeTable) {
+        self._policy.exchange = exchange;
+    }
+
+    pub fn set_dispute_policy(&mut self, disputes: DisputePolicy) {
+        self._policy.disputes = disputes;
+    }
+
+    /// Fixes the Processing Time in Seconds since the Unix Epoch.
+    /// Without it the System Time is used
+    pub fn set_clock(&mut self, inow: u64) {
+        self._oclock = Some(inow);
+    }
+
+    /// Closes the open Disputes whose Deadline has passed at the current Time.
+    /// Returns the Number of expired Disputes
+    pub fn process_expiry(&mut self) -> usize {
+        let inow = self.get_time();
+
+        self._ledger.set_time(inow);
+
+        let vevents = expire_disputes(
+            &mut self._accfact,
+            &mut self._txfact.lsttransactions,
+            &mut self._vdisputes,
+            &mut self._ledger,
+            &self._policy,
+            None,
+            inow,
+            self._bdebug,
+            self._bquiet,
+        );
+        track_dispute_cases(&mut self._txfact.lsttransactions, None, &vevents, inow);
+        self._summary
+            .add_events(&vevents, &self._txfact.lsttransactions);
+
+        let iexpired = vevents
+            .iter()
+            .filter(|event| matches!(event, AccountEvent::DisputeExpired { .. }))
+            .count();
+
+        notify_observers(&mut self._vobservers, &vevents);
+
+        iexpired
+    }
+
+    /// Buffers Movements with a Timestamp and applies them in the Order of their Timestamps
+    /// as soon as a Movement newer than `iwindow` has arrived
+    pub fn set_reorder_window(&mut self, iwindow: u64) {
+        self._oreorder = Some(iwindow);
+    }
+
+    /// Applies all buffered Movements in the Order of their Timestamps
+    pub fn flush_movements(&mut self) {
+        if !self._vbuffer.is_empty() {
+            let mut vbuffer: Vec<Movement> = self._vbuffer.drain(..).collect();
+
+            vbuffer.sort_by_key(|mvrec| mvrec.timestamp);
+
+            self._txfact.vmovements.append(&mut vbuffer);
+
+            self.process_movements();
+        }
+    }
+
+    /// Delivers the Events collected by the Observers since the last Flush.
+    /// A failed Delivery sets the Error Code of the Importer which is returned
+    pub fn flush_events(&mut self) -> i32 {
+        let mut ierr = 0;
+
+        for observer in self._vobservers.iter_mut() {
+            let iflusherr = observer.flush();
+
+            if iflusherr != 0 {
+                ierr = iflusherr;
+            }
+        }
+
+        if ierr != 0 {
+            self._ierr = ierr;
+        }
+
+        ierr
+    }
+
+    /// Adds the Duration of the current Run to the Run Summary
+    pub fn add_elapsed(&mut self, ielapsed_ms: u64) {
+        self._summary.add_elapsed(ielapsed_ms);
+    }
+
+    /// Releases the Funds of the clearing Deposits which are due at the current Time.
+    /// Returns the Number of released Deposits
+    pub fn process_clearing(&mut self) -> usize {
+        let inow = self.get_time();
+
+        self._ledger.set_time(inow);
+
+        let vevents = release_cleared_deposits(
+            &mut self._accfact,
+            &mut self._txfact,
+            &mut self._vclearing,
+            &mut self._ledger,
+            &self._policy,
+            None,
+            inow,
+            self._bdebug,
+            self._bquiet,
+        );
+
+        notify_observers(&mut self._vobservers, &vevents);
+
+        vevents
+            .iter()
+            .filter(|event| matches!(event, AccountEvent::DepositCleared { .. }))
+            .count()
+    }
+
+    /// Moves the new Movements with Timestamp into the Reorder Buffer and releases
+    /// the buffered Movements which are older than the Reorder Window.
+    /// Movements without Timestamp are applied at once.
+    /// Movements which are older than the Window on Arrival are reported as late
+    fn reorder_movements(&mut self) {
+        let iwindow = match self._oreorder {
+            Some(iwindow) => iwindow,
+            None => return,
+        };
+        let vincoming: Vec<Movement> = self._txfact.vmovements.drain(..).collect();
+        let mut vready: Vec<Movement> = Vec::new();
+
+        for mvrec in vincoming.into_iter() {
+            match mvrec.timestamp {
+                Some(itime) => {
+                    if let Some(inewest) = self._onewest {
+                        if itime.saturating_add(iwindow) < inewest {
+                            if !self._bquiet {
+                                eprintln!(
+                                    "Movement Reorder Warning: Transaction (id: '{}') at '{}' arrived late after '{}'.",
+                                    mvrec.tx, itime, inewest
+                                );
+                            }
+
+                            self._vlate.push(LateArrival {
+                                tx_type: mvrec.tx_type.clone(),
+                                client: mvrec.client,
+                                tx: mvrec.tx,
+                                timestamp: itime,
+                                newest: inewest,
+                            });
+                        }
+                    } //if let Some(inewest) = self._onewest
+
+                    self._onewest = Some(self._onewest.map_or(itime, |inewest| inewest.max(itime)));
+                    self._vbuffer.push(mvrec);
+                }
+                None => vready.push(mvrec),
+            } //match mvrec.timestamp
+        } //for mvrec in vincoming.into_iter()
+
+        if let Some(inewest) = self._onewest {
+            let ithreshold = inewest.saturating_sub(iwindow);
+            let (mut vreleased, vkept): (Vec<Movement>, Vec<Movement>) = self
+                ._vbuffer
+                .drain(..)
+                .partition(|mvrec| mvrec.timestamp.is_some_and(|itime| itime <= ithreshold));
+
+            vreleased.sort_by_key(|mvrec| mvrec.timestamp);
+            vready.append(&mut vreleased);
+
+            self._vbuffer = vkept;
+        } //if let Some(inewest) = self._onewest
+
+        self._txfact.vmovements = vready;
+    }
+
     fn process_movements(&mut self) {
-        for mut mvrec in &mut self._txfact.vmovements {
+        let inow = self.get_time();
+
+        self._ledger.set_time(inow);
+
+        //The Movements are taken out so that the Transactions can be changed while processing
+        let mut vmovements = std::mem::take(&mut self._txfact.vmovements);
+
+        for mvrec in vmovements.iter_mut() {
+            //Movements released from the Review Queue are applied in their Order
+            let (breleased, bapproved) = match self._orelease {
+                Some((itx, bapproved)) if itx == mvrec.tx => (true, bapproved),
+                _ => (false, false),
+            };
+
+            if !breleased && self._review.has_client(mvrec.client) {
+                //The Movement waits behind the parked Movements of the Client
+                self._review.push(mvrec.clone(), None, inow);
+
+                continue;
+            }
+
+            //Approved Movements are not checked again
+            let vhits = if bapproved {
+                Vec::new()
+            } else {
+                self._policy.rules.evaluate(mvrec, &self._txfact, inow)
+            };
+            let oaction = vhits.iter().map(|hit| hit.action).max();
+            let olimitreason = match self._accfact.lstaccounts.get(&mvrec.client) {
+                Some(acc) if self._policy.limits.review && !bapproved => review_limits(
+                    acc,
+                    &self._txfact.lsttransactions,
+                    &self._policy.limits,
+                    &mut self._withdrawals,
+                    mvrec,
+                    inow,
+                ),
+                _ => None,
+            };
+
+            for hit in vhits.iter() {
+                if !self._bquiet {
+                    eprintln!(
+                        "Movement Rule Warning: Transaction (id: '{}') matches Rule '{}' ({}): {}.",
+                        hit.tx,
+                        hit.rule,
+                        hit.action.as_str(),
+                        hit.message
+                    );
+                }
+            }
+
+            self._vhits.extend(vhits);
+
+            let oreason = match (oaction, olimitreason) {
+                (Some(RuleAction::Reject), _) => None,
+                (Some(RuleAction::Hold), _) => held_by_rule(&self._vhits, mvrec.tx),
+                (_, Some(reason)) => Some(String::from(reason.as_str())),
+                _ => None,
+            };
+
+            if let Some(sreason) = oreason {
+                //The Movement waits for a Review and is not applied
+                if !self._bquiet {
+                    eprintln!(
+                        "Movement Review Notice: Transaction (id: '{}') is held for Review by '{}'.",
+                        mvrec.tx, sreason
+                    );
+                }
+
+                if breleased {
+                    self._review.push_front(mvrec.clone(), Some(sreason), inow);
+                } else {
+                    self._review.push(mvrec.clone(), Some(sreason), inow);
+                }
+
+                continue;
+            }
+
+            //The Destination Account of a Transfer is taken out of the List while it is changed
+            let mut oaccdest = match transfer_destination(&self._txfact.lsttransactions, mvrec) {
+                Some(idestination) => {
+                    if !self._accfact.lstaccounts.contains_key(&idestination) {
+                        self._accfact.create_account(&idestination);
+                        self._summary.add_account();
+                    }
+
+                    self._accfact.lstaccounts.remove(&idestination)
+                }
+                None => None,
+            };
             let mut oacc = self._accfact.lstaccounts.get_mut(&mvrec.client);
-            let otxrec = self._txfact.lsttransactions.get_mut(&mvrec.tx);
 
             if oacc.is_none() {
                 oacc = self._accfact.create_account(&mvrec.client);
+                self._summary.add_account();
             }
 
+            self._summary.add_movement(&mvrec.tx_type);
+
+            //Approved Movements are not checked against the Limits again
+            let blimit = match &oacc {
+                Some(acc) if !bapproved => exceeds_withdrawal_limit(
+                    acc,
+                    &self._txfact.lsttransactions,
+                    &self._policy.limits,
+                    &mut self._withdrawals,
+                    mvrec,
+                    inow,
+                ),
+                _ => false,
+            };
+            let ocurrency = movement_currency(&self._txfact.lsttransactions, mvrec);
+            let otxrec = self._txfact.lsttransactions.get_mut(&mvrec.tx);
+            let approvedpolicy;
+            let policy = if bapproved {
+                approvedpolicy = self._policy.without_transaction_limit(mvrec.client);
+                &approvedpolicy
+            } else {
+                &self._policy
+            };
+
             match &mut oacc {
                 Some(acc) => {
-                    acc.process_movement(&mut mvrec, otxrec, self._bdebug, self._bquiet);
+                    let mut vevents = {
+                        //The Balances in the Currency of the Transaction are processed
+                        //and the Guards restore the primary Balances at the End of the Block
+                        let mut acc = acc.switch_to(ocurrency.as_deref());
+                        let mut odest = oaccdest
+                            .as_mut()
+                            .map(|accdest| accdest.switch_to(ocurrency.as_deref()));
+
+                        let vbefore = acc.get_balances(ocurrency.as_deref());
+                        let odestbefore = odest
+                            .as_ref()
+                            .map(|accdest| accdest.get_balances(ocurrency.as_deref()));
+
+                        let mut vevents = if oaction == Some(RuleAction::Reject) {
+                            reject_by_rule(&acc, otxrec, mvrec, self._bquiet)
+                        } else if blimit {
+                            match otxrec {
+                                Some(txrec) => acc.reject_movement(
+                                    txrec,
+                                    RejectReason::WithdrawalLimitExceeded,
+                                    self._bquiet,
+                                ),
+                                None => Vec::new(),
+                            }
+                        } else if let Some(accdest) = &mut odest {
+                            acc.process_transfer(
+                                accdest,
+                                mvrec,
+                                otxrec,
+                                policy,
+                                inow,
+                                self._bdebug,
+                                self._bquiet,
+                            )
+                        } else if mvrec.tx_type == "refund" {
+                            process_refund(
+                                &mut acc,
+                                &mut self._txfact.lsttransactions,
+                                mvrec.tx,
+                                self._bdebug,
+                                self._bquiet,
+                            )
+                        } else {
+                            acc.process_movement(
+                                mvrec,
+                                otxrec,
+                                policy,
+                                inow,
+                                self._bdebug,
+                                self._bquiet,
+                            )
+                        };
+
+                        self._ledger.record(
+                            &acc,
+                            ocurrency.as_deref(),
+                            &vbefore,
+                            mvrec.tx,
+                            &mvrec.tx_type,
+                        );
+
+                        if let (Some(accdest), Some(vdestbefore)) = (&odest, &odestbefore) {
+                            self._ledger.record(
+                                accdest,
+                                ocurrency.as_deref(),
+                                vdestbefore,
+                                mvrec.tx,
+                                &mvrec.tx_type,
+                            );
+                        }
+
+                        if !self._policy.fees.is_empty() {
+                            vevents.append(&mut charge_fees(
+                                &mut acc,
+                                &mut self._txfact,
+                                &mut self._ledger,
+                                &self._policy,
+                                mvrec,
+                                &vevents,
+                                self._bdebug,
+                                self._bquiet,
+                            ));
+
+                            //The Chargeback Fee of a Transfer is charged to its Destination
+                            if let Some(accdest) = &mut odest {
+                                vevents.append(&mut charge_fees(
+                                    accdest,
+                                    &mut self._txfact,
+                                    &mut self._ledger,
+                                    &self._policy,
+                                    mvrec,
+                                    &vevents,
+                                    self._bdebug,
+                                    self._bquiet,
+                                ));
+                            }
+                        }
+
+                        vevents
+                    };
+
+                    //The Destination Account returns to the List before the Deadlines are checked
+                    if let Some(accdest) = oaccdest.take() {
+                        self._accfact.add_account(accdest);
+                    }
+
+                    //The Movement counts for the Deposits that were clearing before
+                    vevents.append(&mut release_cleared_deposits(
+                        &mut self._accfact,
+                        &mut self._txfact,
+                        &mut self._vclearing,
+                        &mut self._ledger,
+                        &self._policy,
+                        Some(mvrec.client),
+                        inow,
+                        self._bdebug,
+                        self._bquiet,
+                    ));
+
+                    //The Movement counts for the Disputes that were open before
+                    vevents.append(&mut expire_disputes(
+                        &mut self._accfact,
+                        &mut self._txfact.lsttransactions,
+                        &mut self._vdisputes,
+                        &mut self._ledger,
+                        &self._policy,
+                        Some(mvrec.client),
+                        inow,
+                        self._bdebug,
+                        self._bquiet,
+                    ));
+
+                    if let Some(txrec) = self._txfact.lsttransactions.get_mut(&mvrec.tx) {
+                        //A Withdrawal has no Event when it is accepted
+                        if mvrec.tx_type == "withdrawal"
+                            && txrec.tx_type == "withdrawal"
+                            && txrec.status == 1
+                            && !vevents.iter().any(|event| {
+                                matches!(event, AccountEvent::WithdrawalRejected { .. })
+                            })
+                        {
+                            self._summary
+                                .add_withdrawal(txrec.currency.as_deref(), txrec.amount);
+                        }
+
+                        //Processed Withdrawals and Transfers count for the Withdrawal Limit
+                        if txrec.client == mvrec.client
+                            && txrec.tx_type == mvrec.tx_type
+                            && (txrec.tx_type == "withdrawal" || txrec.tx_type == "transfer")
+                            && txrec.status != -1
+                        {
+                            if let Some(iprocessed) = txrec.processed_at {
+                                self._withdrawals.record(txrec.client, txrec.tx, iprocessed);
+                            }
+                        }
+
+                        if txrec.clearing && !self._vclearing.contains(&txrec.tx) {
+                            self._vclearing.push(txrec.tx);
+                        }
+
+                        //A new Dispute gets its Deadline
+                        if mvrec.tx_type == "dispute"
+                            && txrec.status == -2
+                            && txrec.client == mvrec.client
+                            && (txrec.tx_type == "deposit"
+                                || txrec.tx_type == "withdrawal"
+                                || txrec.tx_type == "transfer")
+                            && !self._vdisputes.contains(&txrec.tx)
+                        {
+                            match self._policy.disputes.deadline {
+                                DisputeDeadline::Movements(icount) => {
+                                    txrec.expires_after = Some(icount);
+                                    self._vdisputes.push(txrec.tx);
+                                }
+                                DisputeDeadline::Window(iseconds) => {
+                                    txrec.expires_at = Some(inow + iseconds);
+                                    self._vdisputes.push(txrec.tx);
+                                }
+                                DisputeDeadline::Unlimited => {}
+                            }
+                        }
+                    } //if let Some(txrec) = self._txfact.lsttransactions.get_mut(&mvrec.tx)
+
+                    track_dispute_cases(
+                        &mut self._txfact.lsttransactions,
+                        Some(mvrec),
+                        &vevents,
+                        inow,
+                    );
+
+                    self._summary
+                        .add_events(&vevents, &self._txfact.lsttransactions);
+
+                    notify_observers(&mut self._vobservers, &vevents);
                 }
                 None => {
                     if !self._bquiet {
@@ -73,7 +688,11 @@ impl MovementImporter {
                     }
                 }
             } //match &mut oacc
-        } //for mvrec in &self._txfact.vmovements
+
+            if let Some(accdest) = oaccdest {
+                self._accfact.add_account(accdest);
+            }
+        } //for mvrec in vmovements.iter_mut()
 
         //Clear processed Movements
         self._txfact.vmovements.clear();
@@ -86,8 +705,17 @@ impl MovementImporter {
             self._bdebug,
             self._bquiet,
         ) {
-            Ok(icnt) => icnt,
+            Ok(icnt) => {
+                self._summary.add_input(icnt as u64, icnt as u64);
+
+                icnt
+            }
             Err(e) => {
+                self._summary.add_input(
+                    (e.ok_count as usize + e.lines.len()) as u64,
+                    e.ok_count as u64,
+                );
+
                 if !self._bquiet {
                     eprintln!(
                         "Movement CSV Import Error: Import Movements failed with [{}]",
@@ -140,7 +768,11 @@ impl MovementImporter {
         }
 
         if itxcount > 0 {
-            self.process_movements();
+            self.reorder_movements();
+
+            if !self._txfact.vmovements.is_empty() {
+                self.process_movements();
+            }
         }
 
         if self._bdebug && !self._bquiet {
@@ -155,6 +787,501 @@ impl MovementImporter {
         self.import_movements_bytes(smovements_str.as_bytes(), bheaders)
     }
 
+    /// Consumes all available Records from the Log in Batches of `imaxrecords`.
+    /// Each Record is one Movement CSV Line without Header.
+    /// After each Batch the processed Offsets are committed
+    pub fn consume_log(&mut self, consumer: &mut dyn LogConsumer, imaxrecords: usize) -> i32 {
+        let mut bconsume = true;
+
+        while bconsume {
+            match self.consume_log_batch(consumer, imaxrecords) {
+                Ok(icount) => {
+                    if icount == 0 {
+                        bconsume = false;
+                    }
+                }
+                Err(e) => {
+                    if !self._bquiet {
+                        eprintln!("Movements Log Consume Error: '{:?}'", e);
+                    }
+
+                    self._ierr = e.code as i32;
+                    bconsume = false;
+                }
+            } //match self.consume_log_batch(consumer, imaxrecords)
+        } //while bconsume
+
+        self._ierr
+    }
+
+    /// Consumes one Batch of up to `imaxrecords` Records from the Log.
+    /// The Consumer is moved to the Input Offsets of the Engine first, so Records that
+    /// were already applied are skipped.
+    /// If a State File is set the new Input Offsets are saved together with the State
+    /// in one atomic Step before they are committed to the Log.
+    /// If the State cannot be saved the Batch is not committed and the Engine State
+    /// must be discarded and reloaded from the State File.
+    /// Movements in the Reorder Buffer are not applied at the End of the Batch.
+    /// A Record that cannot be imported is reported with its Partition and Offset and
+    /// the Error Code is kept, but its Offset is committed so that it does not block the Partition
+    pub fn consume_log_batch(
+        &mut self,
+        consumer: &mut dyn LogConsumer,
+        imaxrecords: usize,
+    ) -> Result<usize, LogError> {
+        for offset in self._voffsets.iter() {
+            consumer.seek(offset.partition, offset.offset);
+        }
+
+        let vrecords = consumer.poll(imaxrecords)?;
+
+        for record in vrecords.iter() {
+            let mut sline = String::from(record.payload.as_str());
+
+            sline.push('\n');
+
+            let ierrprev = self._ierr;
+
+            self._ierr = 0;
+
+            if self.import_movements_str(&sline, false) != 0 {
+                //The Record is reported and skipped so that it does not block the Partition
+                if !self._bquiet {
+                    eprintln!(
+                        "Movements Log Record Error: Record (partition: '{}', offset: '{}') could not be imported: '{}'",
+                        record.partition, record.offset, record.payload
+                    );
+                }
+            } else {
+                self._ierr = ierrprev;
+            }
+
+            match self
+                ._voffsets
+                .iter_mut()
+                .find(|o| o.partition == record.partition)
+            {
+                Some(offset) => offset.offset = record.offset + 1,
+                None => self._voffsets.push(PartitionOffset {
+                    partition: record.partition,
+                    offset: record.offset + 1,
+                }),
+            }
+        } //for record in vrecords.iter()
+
+        if !vrecords.is_empty() {
+            //The Reorder Buffer is saved with the State and waits for the next Batches
+            if !self._sstatefile.is_empty() {
+                if let Err(e) = self.save_state() {
+                    return Err(LogError::new(e.message, e.code));
+                }
+            }
+
+            for offset in self._voffsets.iter() {
+                consumer.commit(offset.partition, offset.offset)?;
+            }
+
+            self.flush_events();
+        } //if !vrecords.is_empty()
+
+        Ok(vrecords.len())
+    }
+
+    /// Unlocks the Account so that it accepts Movements again.
+    /// Returns the ID of the Transaction which records the Operation
+    pub fn unlock_account(
+        &mut self,
+        client_id: u16,
+        soperator: &str,
+        sreason: &str,
+    ) -> Result<u32, AdminError> {
+        match self._accfact.lstaccounts.get(&client_id) {
+            Some(acc) if !acc.locked => Err(AdminError::new(
+                format!("Account (id: '{}') is not locked.", client_id),
+                2,
+            )),
+            _ => self.apply_admin_operation("unlock", client_id, 0.0, soperator, sreason),
+        }
+    }
+
+    /// Locks the Account without a Chargeback so that it refuses all Movements.
+    /// Returns the ID of the Transaction which records the Operation
+    pub fn freeze_account(
+        &mut self,
+        client_id: u16,
+        soperator: &str,
+        sreason: &str,
+    ) -> Result<u32, AdminError> {
+        match self._accfact.lstaccounts.get(&client_id) {
+            Some(acc) if acc.locked => Err(AdminError::new(
+                format!("Account (id: '{}') is already locked.", client_id),
+                2,
+            )),
+            _ => self.apply_admin_operation("freeze", client_id, 0.0, soperator, sreason),
+        }
+    }
+
+    /// Credits a positive and debits a negative Amount on the `available` Funds.
+    /// A Debit must be covered by the `available` Funds.
+    /// Returns the ID of the Transaction which records the Operation
+    pub fn adjust_account(
+        &mut self,
+        client_id: u16,
+        famount: f64,
+        soperator: &str,
+        sreason: &str,
+    ) -> Result<u32, AdminError> {
+        if !famount.is_finite() || famount == 0.0 {
+            return Err(AdminError::new(
+                format!("Adjustment Amount '{}' is invalid.", famount),
+                3,
+            ));
+        }
+
+        if sreason.is_empty() {
+            return Err(AdminError::new(
+                String::from("Adjustment Reason is missing."),
+                3,
+            ));
+        }
+
+        if famount.is_sign_positive() {
+            self.apply_admin_operation("credit", client_id, famount, soperator, sreason)
+        } else {
+            self.apply_admin_operation("debit", client_id, -famount, soperator, sreason)
+        }
+    }
+
+    /// Applies the held Movement with the Transaction ID after a Review.
+    /// Only the first held Movement of a Client can be decided.
+    /// The Movements waiting behind it are applied afterwards in their Order
+    pub fn approve_movement(
+        &mut self,
+        transaction_id: u32,
+        soperator: &str,
+    ) -> Result<u32, AdminError> {
+        self.decide_movement(transaction_id, true, soperator, "")
+    }
+
+    /// Drops the held Movement with the Transaction ID after a Review.
+    /// Its Transaction is marked as invalid with the Reason `review_rejected`.
+    /// The Movements waiting behind it are applied afterwards in their Order
+    pub fn reject_held_movement(
+        &mut self,
+        transaction_id: u32,
+        soperator: &str,
+        sreason: &str,
+    ) -> Result<u32, AdminError> {
+        self.decide_movement(transaction_id, false, soperator, sreason)
+    }
+
+    fn decide_movement(
+        &mut self,
+        transaction_id: u32,
+        bapprove: bool,
+        soperator: &str,
+        sreason: &str,
+    ) -> Result<u32, AdminError> {
+        if soperator.is_empty() {
+            return Err(AdminError::new(String::from("Operator is missing."), 3));
+        }
+
+        let client_id = match self._review.get_item(transaction_id) {
+            Some(item) if item.is_flagged() => item.movement.client,
+            _ => {
+                return Err(AdminError::new(
+                    format!(
+                        "Movement (id: '{}') is not held for Review.",
+                        transaction_id
+                    ),
+                    3,
+                ));
+            }
+        };
+
+        if let Some(itemfirst) = self
+            ._review
+            .get_first(client_id)
+            .filter(|item| item.movement.tx != transaction_id)
+        {
+            return Err(AdminError::new(
+                format!(
+                    "Movement (id: '{}') waits behind Movement (id: '{}') of the Client.",
+                    transaction_id, itemfirst.movement.tx
+                ),
+                3,
+            ));
+        }
+
+        //All Checks are done before the Queue or the Transaction are changed
+        let stype = match self._review.get_first(client_id) {
+            Some(item) => item.movement.tx_type.clone(),
+            None => {
+                return Err(AdminError::new(
+                    format!(
+                        "Movement (id: '{}') is not held for Review.",
+                        transaction_id
+                    ),
+                    3,
+                ))
+            }
+        };
+        //Only a Transaction created by the held Movement itself is rejected
+        let bown = self
+            ._txfact
+            .lsttransactions
+            .get(&transaction_id)
+            .is_some_and(|txrec| {
+                txrec.client == client_id && txrec.status == 0 && txrec.tx_type == stype
+            });
+        let item = match self._review.pop_client(client_id) {
+            Some(item) => item,
+            None => return Err(AdminError::new(String::from("Review Queue is empty."), 3)),
+        };
+
+        if bapprove {
+            self.release_movement(item.movement, true);
+        } else {
+            let mut vevents = Vec::new();
+
+            if let Some(txrec) = self
+                ._txfact
+                .lsttransactions
+                .get_mut(&transaction_id)
+                .filter(|_| bown)
+            {
+                vevents = match self._accfact.lstaccounts.get(&client_id) {
+                    Some(acc) => {
+                        acc.reject_movement(txrec, RejectReason::ReviewRejected, self._bquiet)
+                    }
+                    None => {
+                        txrec.status = -1;
+
+                        Vec::new()
+                    }
+                };
+
+                txrec.reason = Some(String::from(sreason)).filter(|sreason| !sreason.is_empty());
+            }
+
+            self._summary
+                .add_events(&vevents, &self._txfact.lsttransactions);
+
+            notify_observers(&mut self._vobservers, &vevents);
+        } //if bapprove
+
+        if let Some(txrec) = self
+            ._txfact
+            .lsttransactions
+            .get_mut(&transaction_id)
+            .filter(|txrec| txrec.client == client_id && txrec.tx_type == stype)
+        {
+            txrec.operator = Some(String::from(soperator));
+        }
+
+        //The Movements waiting behind the decided Movement follow now
+        while let Some(item) = self
+            ._review
+            .get_first(client_id)
+            .filter(|item| !item.is_flagged())
+            .cloned()
+        {
+            self._review.pop_client(client_id);
+            self.release_movement(item.movement, false);
+        }
+
+        Ok(transaction_id)
+    }
+
+    fn release_movement(&mut self, mvrecord: Movement, bapproved: bool) {
+        self._orelease = Some((mvrecord.tx, bapproved));
+        self._txfact.vmovements.push(mvrecord);
+
+        self.process_movements();
+
+        self._orelease = None;
+    }
+
+    fn apply_admin_operation(
+        &mut self,
+        tx_type: &str,
+        client_id: u16,
+        famount: f64,
+        soperator: &str,
+        sreason: &str,
+    ) -> Result<u32, AdminError> {
+        if soperator.is_empty() {
+            return Err(AdminError::new(String::from("Operator is missing."), 3));
+        }
+
+        let inow = self.get_time();
+        let acc = match self._accfact.lstaccounts.get_mut(&client_id) {
+            Some(a) => a,
+            None => {
+                return Err(AdminError::new(
+                    format!("Account (id: '{}') does not exist.", client_id),
+                    3,
+                ));
+            }
+        };
+        let mut txrec = Transaction::new_admin(
+            tx_type,
+            client_id,
+            self._txfact.next_internal_id(),
+            famount,
+            soperator,
+            sreason,
+        );
+        let vbefore = acc.get_balances(None);
+
+        self._ledger.set_time(inow);
+
+        txrec.booked_at = Some(inow);
+
+        let vevents = acc.process_admin(&mut txrec, self._bdebug, self._bquiet);
+
+        self._ledger.record(acc, None, &vbefore, txrec.tx, tx_type);
+
+        let itxid = txrec.tx;
+        let bapplied = txrec.status == 1;
+
+        //Failed Operations are recorded as well
+        self._txfact.add_transaction(txrec);
+
+        notify_observers(&mut self._vobservers, &vevents);
+
+        if bapplied {
+            Ok(itxid)
+        } else {
+            Err(AdminError::new(
+                format!(
+                    "Admin Operation '{}' on Account (id: '{}') failed.",
+                    tx_type, client_id
+                ),
+                2,
+            ))
+        }
+    }
+
+    /// Restores Accounts, Transactions and Input Offsets from the State File
+    /// together with the Reorder Buffer, the Rule Hits and the Run Summary.
+    /// The Balances of a State File from before the Journal are booked as Opening Entries.
+    /// Balances which differ from a saved Journal are reported and found by `audit()`.
+    /// A missing State File leaves the Engine empty
+    pub fn load_state(&mut self) -> Result<(), StateError> {
+        let snapshot = StateSnapshot::from_file(self._sstatefile.as_str())?;
+        let bopening = snapshot.journal.is_empty();
+
+        self._accfact = AccountFactory::new();
+        self._txfact = TransactionFactory::new();
+        self._txfact.inextinternal = snapshot.next_internal.unwrap_or(INTERNAL_ID_START);
+        self._ledger = Ledger::from_entries(snapshot.ledger);
+        self._ledger.journal = Journal::from_postings(snapshot.journal);
+
+        for acc in snapshot.accounts.into_iter() {
+            for (scurrency, balance) in acc.get_balances(None).iter() {
+                let ocurrency = Some(scurrency.as_str()).filter(|scurrency| !scurrency.is_empty());
+
+                if bopening {
+                    //Balances from before the Journal are booked as Opening Entries
+                    self._ledger
+                        .journal
+                        .post_opening(acc.client, ocurrency, balance);
+                } else if !self._bquiet {
+                    let derived = self._ledger.journal.derive_balance(acc.client, ocurrency);
+
+                    if derived.available != round_amount(balance.available)
+                        || derived.held != round_amount(balance.held)
+                    {
+                        eprintln!(
+                            "State Load Error: Account (id: '{}' / '{}'): Balances '{:?}' differ from the Journal '{:?}'",
+                            acc.client, scurrency, balance, derived
+                        );
+                    }
+                }
+            } //for (scurrency, balance) in acc.get_balances(None).iter()
+
+            self._accfact.add_account(acc);
+        } //for acc in snapshot.accounts.into_iter()
+
+        self._vclearing = snapshot
+            .transactions
+            .iter()
+            .filter(|txrec| txrec.clearing)
+            .map(|txrec| txrec.tx)
+            .collect();
+        self._vdisputes = snapshot
+            .transactions
+            .iter()
+            .filter(|txrec| txrec.expires_after.is_some() || txrec.expires_at.is_some())
+            .map(|txrec| txrec.tx)
+            .collect();
+
+        let mut voutgoing: Vec<(u64, u16, u32)> = snapshot
+            .transactions
+            .iter()
+            .filter(|txrec| {
+                (txrec.tx_type == "withdrawal" || txrec.tx_type == "transfer") && txrec.status != -1
+            })
+            .filter_map(|txrec| txrec.processed_at.map(|iat| (iat, txrec.client, txrec.tx)))
+            .collect();
+
+        //The Withdrawal Window is rebuilt in the Order of Processing
+        voutgoing.sort_unstable();
+        self._withdrawals = WithdrawalWindow::new();
+
+        for (iprocessed, client_id, transaction_id) in voutgoing.into_iter() {
+            self._withdrawals
+                .record(client_id, transaction_id, iprocessed);
+        }
+
+        for txrec in snapshot.transactions.into_iter() {
+            self._txfact.add_transaction(txrec);
+        }
+
+        self._voffsets = snapshot.offsets;
+        self._review = ReviewQueue::from_items(snapshot.review);
+        self._vbuffer = snapshot.buffer;
+        self._onewest = snapshot.newest;
+        self._vlate = snapshot.late;
+        self._vhits = snapshot.hits;
+        self._summary = snapshot.summary;
+
+        if self._bdebug && !self._bquiet {
+            eprintln!("State Load Offsets: '{:?}'", self._voffsets);
+        }
+
+        Ok(())
+    }
+
+    /// Saves Accounts, Transactions and Input Offsets atomically to the State File
+    pub fn save_state(&self) -> Result<(), StateError> {
+        let mut snapshot = StateSnapshot::new();
+
+        snapshot.offsets = self._voffsets.clone();
+        snapshot.accounts = self._accfact.lstaccounts.values().cloned().collect();
+        snapshot.transactions = self._txfact.lsttransactions.values().cloned().collect();
+        snapshot.next_internal = Some(self._txfact.inextinternal);
+        snapshot.ledger = self
+            ._ledger
+            .get_all_entries()
+            .into_iter()
+            .cloned()
+            .collect();
+        snapshot.journal = self._ledger.journal.vpostings.clone();
+        snapshot.review = self._review.vitems.clone();
+        snapshot.buffer = self._vbuffer.clone();
+        snapshot.newest = self._onewest;
+        snapshot.late = self._vlate.clone();
+        snapshot.hits = self._vhits.clone();
+        snapshot.summary = self._summary.clone();
+
+        snapshot.accounts.sort_by_key(|acc| acc.client);
+        snapshot.transactions.sort_by_key(|txrec| txrec.tx);
+
+        snapshot.to_file(self._sstatefile.as_str())
+    }
+
     /*----------------------------------------------------------------------------
      * Consultation Methods
      */
@@ -163,10 +1290,213 @@ impl MovementImporter {
         self._accfact.export_csv(self._bdebug, self._bquiet)
     }
 
+    /// Exports the Accounts with one Row per Currency
+    pub fn export_currencies_str(&self) -> String {
+        self._accfact
+            .export_currency_csv(self._bdebug, self._bquiet)
+    }
+
+    pub fn export_history_str(&self) -> String {
+        self._txfact.export_history_csv(self._bdebug, self._bquiet)
+    }
+
     pub fn export_transactions_str(&self) -> String {
         self._txfact
             .export_transactions_csv(self._bdebug, self._bquiet)
     }
+    /// Exports the Fee Income per Movement Type with the Columns `type,count,income`
+    pub fn export_fee_report_str(&self) -> String {
+        self._txfact
+            .export_fee_report_csv(self._bdebug, self._bquiet)
+    }
+
+    /// Exports the open and closed Dispute Cases
+    pub fn export_cases_str(&self) -> String {
+        self._txfact.export_cases_csv(self._bdebug, self._bquiet)
+    }
+
+    pub fn get_transaction(&self, transaction_id: u32) -> Option<&Transaction> {
+        self._txfact.lsttransactions.get(&transaction_id)
+    }
+
+    /// Returns the Ledger Entries of the Account in the Order they were recorded
+    pub fn get_ledger_entries(&self, client_id: u16) -> &[LedgerEntry] {
+        self._ledger.get_entries(client_id)
+    }
+
+    /// Exports the Ledger Entries of the Client `oclient` or of all Clients
+    pub fn export_ledger_str(&self, oclient: Option<u16>) -> String {
+        self._ledger.export_csv(oclient, self._bdebug, self._bquiet)
+    }
+
+    /// Verifies the Invariants of all Accounts and Transactions
+    /// and compares the Account Balances with the Journal
+    pub fn audit(&self) -> AuditReport {
+        AuditReport::new(&self._accfact, &self._txfact, &self._ledger.journal)
+    }
+
+    /// Compares the Accounts with the expected Accounts in the Format of the Accounts Export.
+    /// Balances which differ by not more than `ftolerance` are equal
+    pub fn reconcile(&self, sexpected_csv: &str, ftolerance: f64) -> Reconciliation {
+        let expected = AccountFactory::from_str(sexpected_csv, true, self._bdebug, self._bquiet);
+
+        Reconciliation::new(&self._accfact, &expected, ftolerance)
+    }
+
+    /// Returns the Movements which matched a Fraud Rule in the Order of their Evaluation
+    pub fn get_rule_hits(&self) -> &Vec<RuleHit> {
+        &self._vhits
+    }
+
+    /// Exports the Rule Hits with the Columns `rule,action,client,tx,type,amount,at,message`
+    pub fn export_rule_hits_str(&self) -> String {
+        export_hits_csv(&self._vhits, self._bdebug, self._bquiet)
+    }
+
+    /// Returns the Movements parked in the Review Queue in their Order
+    pub fn get_held_movements(&self) -> Vec<&Movement> {
+        self._review
+            .vitems
+            .iter()
+            .map(|item| &item.movement)
+            .collect()
+    }
+
+    pub fn get_review_queue(&self) -> &ReviewQueue {
+        &self._review
+    }
+
+    /// Exports the Review Queue with the Columns `client,tx,type,amount,reason,held_at`
+    pub fn export_review_str(&self) -> String {
+        self._review.export_csv(self._bdebug, self._bquiet)
+    }
+
+    /// Exports the flagged Movements held for at least `imaxage` Seconds
+    /// with the Columns `client,tx,type,reason,held_at,age,waiting`
+    pub fn export_aged_review_str(&self, imaxage: u64) -> String {
+        self._review
+            .export_aged_csv(self.get_time(), imaxage, self._bdebug, self._bquiet)
+    }
+
+    /// Returns the Statistics of the Movements processed since the Importer was created
+    /// including the Runs restored from the State File.
+    /// The held Funds are taken from the Accounts at the Time of the Call
+    pub fn get_summary(&self) -> RunSummary {
+        let mut summary = self._summary.clone();
+
+        summary.held = BTreeMap::new();
+
+        for (scurrency, balance) in self
+            ._accfact
+            .lstaccounts
+            .values()
+            .flat_map(|acc| acc.get_balances(None).into_iter())
+            .filter(|(_, balance)| balance.held != 0.0)
+        {
+            let fheld = summary.held.entry(scurrency).or_default();
+
+            *fheld = round_amount(*fheld + balance.held);
+        }
+
+        summary
+    }
+
+    /// Builds the Statement of the Client in the Currency for the Range
+    pub fn build_statement(
+        &self,
+        client_id: u16,
+        ocurrency: Option<&str>,
+        range: &StatementRange,
+    ) -> Statement {
+        Statement::new(client_id, ocurrency, range, &self._txfact, &self._ledger)
+    }
+
+    /// Exports the Statement of the Client in the Currency for the Range in the Format
+    pub fn export_statement_str(
+        &self,
+        client_id: u16,
+        ocurrency: Option<&str>,
+        range: &StatementRange,
+        format: StatementFormat,
+    ) -> String {
+        self.build_statement(client_id, ocurrency, range)
+            .export(format, self._bdebug, self._bquiet)
+    }
+
+    pub fn get_journal(&self) -> &Journal {
+        &self._ledger.journal
+    }
+
+    /// Returns the Account with the Balances derived from the Journal Postings
+    pub fn derive_account(&self, client_id: u16) -> Option<Account> {
+        self._accfact.lstaccounts.get(&client_id).map(|acc| {
+            let mut derived = acc.clone();
+            let primary = self._ledger.journal.derive_balance(client_id, None);
+
+            derived.available = primary.available;
+            derived.held = primary.held;
+            derived.total = primary.total;
+
+            for (scurrency, balance) in derived.currencies.iter_mut() {
+                *balance = self
+                    ._ledger
+                    .journal
+                    .derive_balance(client_id, Some(scurrency.as_str()));
+            }
+
+            derived
+        })
+    }
+
+    /// Exports the Journal Postings
+    pub fn export_journal_str(&self) -> String {
+        self._ledger.journal.export_csv(self._bdebug, self._bquiet)
+    }
+
+    /// Exports the Trial Balance of the Journal
+    pub fn export_trial_balance_str(&self) -> String {
+        self._ledger
+            .journal
+            .export_trial_balance_csv(self._bdebug, self._bquiet)
+    }
+
+    pub fn get_late_arrivals(&self) -> &Vec<LateArrival> {
+        &self._vlate
+    }
+
+    /// Exports the late Movements with the Columns `type,client,tx,timestamp,newest`
+    pub fn export_late_arrivals_str(&self) -> String {
+        let mut slate = String::from("type,client,tx,timestamp,newest\n");
+
+        for late in self._vlate.iter() {
+            slate.push_str(&format!(
+                "{},{},{},{},{}\n",
+                late.tx_type, late.client, late.tx, late.timestamp, late.newest
+            ));
+        }
+
+        slate
+    }
+
+    pub fn get_input_offsets(&self) -> &Vec<PartitionOffset> {
+        &self._voffsets
+    }
+
+    pub fn get_processing_policy(&self) -> &ProcessingPolicy {
+        &self._policy
+    }
+
+    /// Returns the Processing Time in Seconds since the Unix Epoch
+    pub fn get_time(&self) -> u64 {
+        match self._oclock {
+            Some(inow) => inow,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
     pub fn is_quiet(&self) -> bool {
         self._bquiet
     }
@@ -179,3 +1509,558 @@ impl MovementImporter {
         self._ierr
     }
 }
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Releases the clearing Deposits which are due.
+/// A Movement on the Account of `oclient` counts down the Deposits which clear after
+/// a Number of Movements. Deposits with a Clearing Time are released when it is reached.
+/// The Deposit Fee is charged when the Funds are released
+#[allow(clippy::too_many_arguments)]
+fn release_cleared_deposits(
+    accfact: &mut AccountFactory,
+    txfactory: &mut TransactionFactory,
+    vclearing: &mut Vec<u32>,
+    ledger: &mut Ledger,
+    policy: &ProcessingPolicy,
+    oclient: Option<u16>,
+    inow: u64,
+    bdebug: bool,
+    bquiet: bool,
+) -> Vec<AccountEvent> {
+    let mut vevents = Vec::new();
+    let mut vreleased: Vec<(u16, u32, f64)> = Vec::new();
+
+    vclearing.retain(|itxid| {
+        let txrec = match txfactory.lsttransactions.get_mut(itxid) {
+            Some(txrec) if txrec.clearing => txrec,
+            _ => return false,
+        };
+        let mut bdue = false;
+
+        if let Some(icount) = txrec.clears_after {
+            if oclient == Some(txrec.client) {
+                txrec.clears_after = Some(icount.saturating_sub(1));
+                bdue = icount <= 1;
+            }
+        }
+
+        if let Some(iclears) = txrec.clears_at {
+            bdue = bdue || iclears <= inow;
+        }
+
+        if bdue {
+            if let Some(acc) = accfact.lstaccounts.get_mut(&txrec.client) {
+                let ocurrency = txrec.currency.clone();
+                let vbefore = acc.get_balances(None);
+
+                vevents.append(
+                    &mut acc
+                        .switch_to(ocurrency.as_deref())
+                        .clear_deposit(txrec, bdebug, bquiet),
+                );
+
+                ledger.record(acc, None, &vbefore, txrec.tx, "clearing");
+
+                if !txrec.clearing && txrec.status != -1 {
+                    vreleased.push((txrec.client, txrec.tx, txrec.amount));
+                }
+            }
+        }
+
+        txrec.clearing
+    });
+
+    //The Deposit Fee is charged when the Funds are released
+    for (iclient, itxid, famount) in vreleased.into_iter() {
+        let ffee = policy.fees.calculate("deposit", iclient, famount);
+
+        if ffee > 0.0 {
+            let ocurrency = txfactory
+                .lsttransactions
+                .get(&itxid)
+                .and_then(|txrec| txrec.currency.clone());
+
+            if let Some(acc) = accfact.lstaccounts.get_mut(&iclient) {
+                vevents.append(&mut apply_fees(
+                    &mut acc.switch_to(ocurrency.as_deref()),
+                    txfactory,
+                    ledger,
+                    policy,
+                    vec![(String::from("deposit"), itxid, ffee)],
+                    bdebug,
+                    bquiet,
+                ));
+            }
+        }
+    } //for (iclient, itxid, famount) in vreleased.into_iter()
+
+    vevents
+}
+
+/// Closes the open Disputes which are due.
+/// A Movement on the Account of `oclient` counts down the Disputes held on it which expire after
+/// a Number of Movements. Disputes with an Expiry Time are closed when it is reached.
+/// The Dispute of a "_transfer_" expires on its Destination Account as in `Account::process_transfer()`
+#[allow(clippy::too_many_arguments)]
+fn expire_disputes(
+    accfact: &mut AccountFactory,
+    lsttransactions: &mut HashMap<u32, Transaction>,
+    vdisputes: &mut Vec<u32>,
+    ledger: &mut Ledger,
+    policy: &ProcessingPolicy,
+    oclient: Option<u16>,
+    inow: u64,
+    bdebug: bool,
+    bquiet: bool,
+) -> Vec<AccountEvent> {
+    let mut vevents = Vec::new();
+
+    vdisputes.retain(|itxid| {
+        let txrec = match lsttransactions.get_mut(itxid) {
+            Some(txrec) if txrec.status == -2 => txrec,
+            Some(txrec) => {
+                //The Dispute was closed before its Deadline
+                txrec.expires_after = None;
+                txrec.expires_at = None;
+
+                return false;
+            }
+            None => return false,
+        };
+        let mut bdue = false;
+        let mut bexpired = false;
+
+        if let Some(icount) = txrec.expires_after {
+            if oclient == Some(txrec.get_holder()) {
+                txrec.expires_after = Some(icount.saturating_sub(1));
+                bdue = icount <= 1;
+            }
+        }
+
+        if let Some(iexpires) = txrec.expires_at {
+            bdue = bdue || iexpires <= inow;
+        }
+
+        if bdue && txrec.tx_type == "transfer" {
+            let ocurrency = txrec.currency.clone();
+            let iholder = txrec.get_holder();
+            let mut oaccdest = accfact.lstaccounts.remove(&iholder);
+
+            if let (Some(acc), Some(accdest)) = (
+                accfact.lstaccounts.get_mut(&txrec.client),
+                oaccdest.as_mut(),
+            ) {
+                let vbefore = acc.get_balances(None);
+                let vdestbefore = accdest.get_balances(None);
+
+                {
+                    let mut acc = acc.switch_to(ocurrency.as_deref());
+                    let mut accdest = accdest.switch_to(ocurrency.as_deref());
+
+                    vevents.append(&mut acc.expire_transfer_dispute(
+                        &mut accdest,
+                        txrec,
+                        policy,
+                        bdebug,
+                        bquiet,
+                    ));
+                }
+
+                ledger.record(acc, None, &vbefore, txrec.tx, "expiry");
+                ledger.record(accdest, None, &vdestbefore, txrec.tx, "expiry");
+
+                bexpired = true;
+            }
+
+            if let Some(accdest) = oaccdest {
+                accfact.add_account(accdest);
+            }
+        } else if bdue {
+            if let Some(acc) = accfact.lstaccounts.get_mut(&txrec.client) {
+                let ocurrency = txrec.currency.clone();
+                let vbefore = acc.get_balances(None);
+
+                vevents.append(
+                    &mut acc
+                        .switch_to(ocurrency.as_deref())
+                        .expire_dispute(txrec, policy, bdebug, bquiet),
+                );
+
+                ledger.record(acc, None, &vbefore, txrec.tx, "expiry");
+
+                bexpired = true;
+            }
+        }
+
+        //The Dispute waits while its Accounts are missing
+        !bexpired
+    });
+
+    vevents
+}
+
+/// Records the Dispute Events in the Dispute Cases of their Transactions.
+/// A Dispute opens a new Case unless a Case is still open.
+/// The Case is closed when the Transaction is not disputed anymore.
+/// The Reason Code and the Case Reference are taken from the Movement `omvrecord`
+fn track_dispute_cases(
+    lsttransactions: &mut HashMap<u32, Transaction>,
+    omvrecord: Option<&Movement>,
+    vevents: &[AccountEvent],
+    inow: u64,
+) {
+    for event in vevents.iter() {
+        let famount = match event {
+            AccountEvent::DisputeOpened { amount, .. }
+            | AccountEvent::DisputeRejected { amount, .. }
+            | AccountEvent::DisputeExpired { amount, .. }
+            | AccountEvent::DisputeResolved { amount, .. }
+            | AccountEvent::ChargebackApplied { amount, .. } => *amount,
+            _ => continue,
+        };
+        let txrec = match lsttransactions.get_mut(&event.get_transaction()) {
+            Some(txrec) => txrec,
+            None => continue,
+        };
+        let omovement = omvrecord.filter(|mvrec| mvrec.tx == txrec.tx);
+        let ocase = omovement.and_then(|mvrec| mvrec.case.clone());
+        let oreason_code = omovement.and_then(|mvrec| mvrec.reason_code.clone());
+
+        if matches!(event, AccountEvent::DisputeOpened { .. })
+            && txrec.cases.last().is_none_or(|dcase| !dcase.is_open())
+        {
+            txrec
+                .cases
+                .push(DisputeCase::new(inow, ocase.clone(), oreason_code.clone()));
+        }
+
+        if let Some(dcase) = txrec.cases.last_mut().filter(|dcase| dcase.is_open()) {
+            if dcase.case.is_none() {
+                dcase.case = ocase;
+            }
+
+            dcase.add_entry(event.get_event_type(), Some(famount), inow, oreason_code);
+
+            //The Dispute is over when the Transaction is not disputed anymore
+            if txrec.status != -2 {
+                match event {
+                    AccountEvent::DisputeResolved { .. } => dcase.close(inow, "resolved"),
+                    AccountEvent::ChargebackApplied { .. } => dcase.close(inow, "charged_back"),
+                    _ => {}
+                }
+            }
+        } //if let Some(dcase) = txrec.cases.last_mut().filter(|dcase| dcase.is_open())
+    } //for event in vevents.iter()
+}
+
+/// Returns the Destination Client when the Movement is a "_transfer_"
+/// or references a "_transfer_" Transaction
+fn transfer_destination(
+    lsttransactions: &HashMap<u32, Transaction>,
+    mvrecord: &Movement,
+) -> Option<u16> {
+    match lsttransactions.get(&mvrecord.tx) {
+        Some(txrec) if txrec.tx_type == "transfer" && txrec.client == mvrecord.client => {
+            match mvrecord.tx_type.as_str() {
+                "transfer" | "dispute" | "resolve" | "chargeback" => txrec.destination,
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the Currency of the Balances changed by the Movement.
+/// A "_refund_" changes the Balances in the Currency of the original Transaction
+fn movement_currency(
+    lsttransactions: &HashMap<u32, Transaction>,
+    mvrecord: &Movement,
+) -> Option<String> {
+    match lsttransactions.get(&mvrecord.tx) {
+        //An "_exchange_" changes the Balances of both its Currencies itself
+        Some(txrec) if txrec.tx_type == "exchange" => None,
+        Some(txrec) if txrec.tx_type == "refund" && mvrecord.tx_type == "refund" => txrec
+            .original
+            .and_then(|ioriginal| lsttransactions.get(&ioriginal))
+            .and_then(|txoriginal| txoriginal.currency.clone()),
+        Some(txrec) => txrec.currency.clone(),
+        None => None,
+    }
+}
+
+/// Applies the Refund with the Transaction ID `itxid` on its original Transaction.
+/// The original Transaction is taken out of the List while both are modified
+fn process_refund(
+    acc: &mut Account,
+    lsttransactions: &mut HashMap<u32, Transaction>,
+    itxid: u32,
+    bdebug: bool,
+    bquiet: bool,
+) -> Vec<AccountEvent> {
+    let mut otxoriginal = match lsttransactions.get(&itxid).and_then(|txrec| txrec.original) {
+        Some(ioriginal) if ioriginal != itxid => lsttransactions.remove(&ioriginal),
+        _ => None,
+    };
+    let vevents = match lsttransactions.get_mut(&itxid) {
+        Some(txrefund) => acc.process_refund(txrefund, otxoriginal.as_mut(), bdebug, bquiet),
+        None => {
+            if !bquiet {
+                eprintln!("Movement Processing Error: Transaction (id: '{}') does not exist. Movement omitted.", itxid);
+            }
+
+            Vec::new()
+        }
+    };
+
+    if let Some(txoriginal) = otxoriginal {
+        lsttransactions.insert(txoriginal.tx, txoriginal);
+    }
+
+    vevents
+}
+
+/// Finds the Rule which held the Movement with the Transaction ID most recently
+fn held_by_rule(vhits: &[RuleHit], itxid: u32) -> Option<String> {
+    vhits
+        .iter()
+        .rev()
+        .find(|hit| hit.tx == itxid && hit.action == RuleAction::Hold)
+        .map(|hit| hit.rule.clone())
+}
+
+/// Checks whether a Movement exceeds the Withdrawal or the Transaction Limit
+/// when such Movements are held for a Review
+fn review_limits(
+    acc: &Account,
+    lsttransactions: &HashMap<u32, Transaction>,
+    limits: &LimitSchedule,
+    withdrawals: &mut WithdrawalWindow,
+    mvrecord: &Movement,
+    inow: u64,
+) -> Option<RejectReason> {
+    if exceeds_withdrawal_limit(acc, lsttransactions, limits, withdrawals, mvrecord, inow) {
+        return Some(RejectReason::WithdrawalLimitExceeded);
+    }
+
+    let olimits = limits.get_limits(acc.client)?;
+
+    match lsttransactions.get(&mvrecord.tx) {
+        Some(txrec)
+            if txrec.client == acc.client
+                && txrec.status == 0
+                && txrec.tx_type == mvrecord.tx_type
+                && (txrec.tx_type == "deposit"
+                    || txrec.tx_type == "withdrawal"
+                    || txrec.tx_type == "transfer")
+                && olimits.exceeds_transaction(txrec.amount) =>
+        {
+            Some(RejectReason::TransactionLimitExceeded)
+        }
+        _ => None,
+    }
+}
+
+/// Refuses a Movement which matched a rejecting Fraud Rule.
+/// Only the Transaction created by the Movement itself is marked as invalid
+fn reject_by_rule(
+    acc: &Account,
+    otxrec: Option<&mut Transaction>,
+    mvrecord: &Movement,
+    bquiet: bool,
+) -> Vec<AccountEvent> {
+    match otxrec {
+        Some(txrec)
+            if txrec.client == mvrecord.client
+                && txrec.status == 0
+                && txrec.tx_type == mvrecord.tx_type =>
+        {
+            acc.reject_movement(txrec, RejectReason::RuleViolation, bquiet)
+        }
+        _ => {
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Movement '{}' on Transaction (id: '{}'): refused '{}'.",
+                    &mvrecord.tx_type,
+                    mvrecord.tx,
+                    RejectReason::RuleViolation.as_str()
+                );
+            }
+
+            if mvrecord.tx_type == "dispute" {
+                vec![AccountEvent::DisputeRejected {
+                    client: mvrecord.client,
+                    tx: mvrecord.tx,
+                    amount: mvrecord.amount.unwrap_or(0.0),
+                    reason: RejectReason::RuleViolation,
+                }]
+            } else {
+                Vec::new()
+            }
+        }
+    } //match otxrec
+}
+
+/// Checks whether a new "_withdrawal_" or "_transfer_" exceeds the Withdrawal Limit
+/// together with the Withdrawals and Transfers of the Account within the rolling Window
+fn exceeds_withdrawal_limit(
+    acc: &Account,
+    lsttransactions: &HashMap<u32, Transaction>,
+    limits: &LimitSchedule,
+    withdrawals: &mut WithdrawalWindow,
+    mvrecord: &Movement,
+    inow: u64,
+) -> bool {
+    let olimits = match limits.get_limits(acc.client) {
+        Some(l) if l.withdrawal_limit.is_some() && !acc.locked => l,
+        _ => return false,
+    };
+    let (famount, ocurrency) = match lsttransactions.get(&mvrecord.tx) {
+        Some(txrec)
+            if txrec.client == acc.client
+                && txrec.status == 0
+                && txrec.tx_type == mvrecord.tx_type
+                && (txrec.tx_type == "withdrawal" || txrec.tx_type == "transfer") =>
+        {
+            (txrec.amount, &txrec.currency)
+        }
+        _ => return false,
+    };
+    let istart = inow.saturating_sub(limits.window);
+    let fwithdrawn: f64 = withdrawals
+        .get_recent(acc.client, istart)
+        .iter()
+        .filter_map(|itxid| lsttransactions.get(itxid))
+        .filter(|txrec| {
+            (txrec.tx_type == "withdrawal" || txrec.tx_type == "transfer")
+                && txrec.status != -1
+                && &txrec.currency == ocurrency
+        })
+        .map(|txrec| txrec.amount)
+        .sum();
+
+    olimits.exceeds_withdrawals(fwithdrawn + famount)
+}
+
+/// Charges the Fees for the processed Movement, the Deposits it settled and the Chargebacks it caused.
+/// Each Fee is recorded as a "_fee_" Transaction linked to the charged Transaction
+#[allow(clippy::too_many_arguments)]
+fn charge_fees(
+    acc: &mut Account,
+    txfactory: &mut TransactionFactory,
+    ledger: &mut Ledger,
+    policy: &ProcessingPolicy,
+    mvrecord: &Movement,
+    vmvevents: &[AccountEvent],
+    bdebug: bool,
+    bquiet: bool,
+) -> Vec<AccountEvent> {
+    let mut vcharges: Vec<(&str, u32, f64)> = Vec::new();
+    let mut vevents = Vec::new();
+
+    //Clearing Deposits are charged when they are released
+    match txfactory.lsttransactions.get(&mvrecord.tx) {
+        Some(txrec)
+            if txrec.client == acc.client
+                && txrec.tx_type == mvrecord.tx_type
+                && txrec.status == 1 =>
+        {
+            match txrec.tx_type.as_str() {
+                "deposit" | "withdrawal" | "transfer" | "refund" => {
+                    vcharges.push((txrec.tx_type.as_str(), txrec.tx, txrec.amount))
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    } //match txfactory.lsttransactions.get(&mvrecord.tx)
+
+    for event in vmvevents.iter() {
+        match event {
+            AccountEvent::ChargebackApplied { client, tx, amount } if *client == acc.client => {
+                vcharges.push(("chargeback", *tx, *amount))
+            }
+            //A settled Deposit is charged with the whole Amount
+            AccountEvent::DepositCleared { client, tx, .. } if *client == acc.client => {
+                if let Some(txrec) = txfactory.lsttransactions.get(tx) {
+                    vcharges.push(("deposit", *tx, txrec.amount));
+                }
+            }
+            _ => {}
+        }
+    } //for event in vmvevents.iter()
+
+    let vfees: Vec<(String, u32, f64)> = vcharges
+        .into_iter()
+        .map(|(stype, itxid, famount)| {
+            (
+                String::from(stype),
+                itxid,
+                policy.fees.calculate(stype, acc.client, famount),
+            )
+        })
+        .filter(|(_, _, ffee)| *ffee > 0.0)
+        .collect();
+
+    vevents.append(&mut apply_fees(
+        acc, txfactory, ledger, policy, vfees, bdebug, bquiet,
+    ));
+
+    vevents
+}
+
+/// Charges the calculated Fees `vfees` as "_fee_" Transactions
+/// linked to the charged Transactions
+fn apply_fees(
+    acc: &mut Account,
+    txfactory: &mut TransactionFactory,
+    ledger: &mut Ledger,
+    policy: &ProcessingPolicy,
+    vfees: Vec<(String, u32, f64)>,
+    bdebug: bool,
+    bquiet: bool,
+) -> Vec<AccountEvent> {
+    let mut vevents = Vec::new();
+
+    for (stype, itxid, ffee) in vfees.into_iter() {
+        let mut txfee = Transaction::new_fee(
+            acc.client,
+            txfactory.next_internal_id(),
+            ffee,
+            &stype,
+            itxid,
+        );
+
+        //The Fee is charged in the Currency of the charged Transaction
+        txfee.currency = txfactory
+            .lsttransactions
+            .get(&itxid)
+            .and_then(|txrec| txrec.currency.clone());
+
+        //The Caller has switched the Account to the Currency of the Fee through its Guard
+        let vbefore = acc.get_balances(txfee.currency.as_deref());
+
+        vevents.append(&mut acc.apply_fee(
+            &mut txfee,
+            policy.limits.get_overdraft(acc.client),
+            bdebug,
+            bquiet,
+        ));
+
+        ledger.record(acc, txfee.currency.as_deref(), &vbefore, txfee.tx, "fee");
+
+        //Rejected Fees are recorded as well
+        txfactory.add_transaction(txfee);
+    }
+
+    vevents
+}
+
+fn notify_observers(vobservers: &mut [Box<dyn EventObserver>], vevents: &[AccountEvent]) {
+    for event in vevents.iter() {
+        for observer in vobservers.iter_mut() {
+            observer.notify(event);
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use csv::WriterBuilder;
+
+use std::fmt::Debug;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use super::super::model::event::{AccountEvent, EventRecord};
+
+//==============================================================================
+// Trait EventObserver Declaration
+
+/// Receiver of the Account Events emitted by the Engine
+pub trait EventObserver: Debug {
+    fn notify(&mut self, event: &AccountEvent);
+
+    /// Delivers the Events received since the last Flush.
+    /// Returns the Error Code of failed Deliveries
+    fn flush(&mut self) -> i32 {
+        0
+    }
+}
+
+//==============================================================================
+// Webhook Settings
+
+/// Timeout for Connecting, Sending and Receiving of one Webhook Request
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+/// The maximal Number of Events posted in one Webhook Request
+const WEBHOOK_BATCH_SIZE: usize = 500;
+
+//==============================================================================
+// Enum EventFormat Declaration
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventFormat {
+    Csv,
+    Ndjson,
+}
+
+//==============================================================================
+// Structure EventFileSink Declaration
+
+/// Appends each Event as one Line to the Events File
+/// either as CSV Record or as JSON Object (NDJSON)
+#[derive(Debug)]
+pub struct EventFileSink {
+    _sfilename: String,
+    _format: EventFormat,
+    _file: Option<File>,
+    _bheaders: bool,
+    _bquiet: bool,
+    _ierr: i32,
+}
+
+//==============================================================================
+// Structure EventFileSink Implementation
+
+impl EventFileSink {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Opens the Events File for appending.
+    /// An Open Error is reported unless `bquiet` is set and kept as Error Code
+    pub fn new(sfilename: &str, format: EventFormat, bquiet: bool) -> EventFileSink {
+        let mut sink = EventFileSink {
+            _sfilename: String::from(sfilename),
+            _format: format,
+            _file: None,
+            _bheaders: false,
+            _bquiet: bquiet,
+            _ierr: 0,
+        };
+
+        match OpenOptions::new().create(true).append(true).open(sfilename) {
+            Ok(fl) => {
+                //The Header is written only once into an empty File
+                sink._bheaders = match fl.metadata() {
+                    Ok(meta) => meta.len() == 0,
+                    Err(_) => false,
+                };
+                sink._file = Some(fl);
+            }
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Events File '{}': Open failed: '{:?}'", sfilename, e);
+                }
+
+                sink._ierr = 1;
+            }
+        } //match OpenOptions::new().create(true).append(true).open(sfilename)
+
+        sink
+    }
+
+    /// Chooses the Format by the File Extension.
+    /// The Extensions `.ndjson`, `.jsonl` and `.json` produce NDJSON
+    pub fn from_filename(sfilename: &str, bquiet: bool) -> EventFileSink {
+        let slower = sfilename.to_lowercase();
+
+        if slower.ends_with(".ndjson") || slower.ends_with(".jsonl") || slower.ends_with(".json") {
+            EventFileSink::new(sfilename, EventFormat::Ndjson, bquiet)
+        } else {
+            EventFileSink::new(sfilename, EventFormat::Csv, bquiet)
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    pub fn set_quiet(&mut self, bquiet: bool) {
+        self._bquiet = bquiet;
+    }
+
+    fn format_record(&mut self, record: &EventRecord) -> Option<Vec<u8>> {
+        match self._format {
+            EventFormat::Csv => {
+                let mut wtr = WriterBuilder::new()
+                    .has_headers(self._bheaders)
+                    .from_writer(vec![]);
+
+                match wtr.serialize(record) {
+                    Ok(_) => {
+                        self._bheaders = false;
+
+                        wtr.into_inner().ok()
+                    }
+                    Err(e) => {
+                        if !self._bquiet {
+                            eprintln!("Event CSV Export Error: '{:?}'", e);
+                        }
+
+                        None
+                    }
+                } //match wtr.serialize(record)
+            }
+            EventFormat::Ndjson => match serde_json::to_vec(record) {
+                Ok(mut vline) => {
+                    vline.push(b'\n');
+
+                    Some(vline)
+                }
+                Err(e) => {
+                    if !self._bquiet {
+                        eprintln!("Event JSON Export Error: '{:?}'", e);
+                    }
+
+                    None
+                }
+            },
+        } //match self._format
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_filename(&self) -> &str {
+        self._sfilename.as_str()
+    }
+
+    pub fn get_error_code(&self) -> i32 {
+        self._ierr
+    }
+}
+
+impl EventObserver for EventFileSink {
+    fn notify(&mut self, event: &AccountEvent) {
+        if let Some(vline) = self.format_record(&event.to_record()) {
+            if let Some(fl) = &mut self._file {
+                if let Err(e) = fl.write_all(&vline) {
+                    if !self._bquiet {
+                        eprintln!("Events File '{}': Write failed: '{:?}'", self._sfilename, e);
+                    }
+
+                    self._ierr = 1;
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) -> i32 {
+        if let Some(fl) = &mut self._file {
+            if let Err(e) = fl.flush() {
+                if !self._bquiet {
+                    eprintln!("Events File '{}': Write failed: '{:?}'", self._sfilename, e);
+                }
+
+                self._ierr = 1;
+            }
+        }
+
+        self._ierr
+    }
+}
+
+//==============================================================================
+// Structure WebhookSink Declaration
+
+/// Posts the Events as JSON Array to an HTTP Endpoint.
+/// The Events are collected and posted in one Request per Flush
+/// of at most `WEBHOOK_BATCH_SIZE` Events.
+/// Only plain `http://` URLs are supported
+#[derive(Debug)]
+pub struct WebhookSink {
+    _shost: String,
+    _spath: String,
+    _vpending: Vec<Vec<u8>>,
+    _isent: u32,
+    _ifailed: u32,
+    _bquiet: bool,
+    _ierr: i32,
+}
+
+//==============================================================================
+// Structure WebhookSink Implementation
+
+impl WebhookSink {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(surl: &str) -> Option<WebhookSink> {
+        let saddress = surl.strip_prefix("http://")?;
+        let (shost, spath) = match saddress.find('/') {
+            Some(ipos) => saddress.split_at(ipos),
+            None => (saddress, "/"),
+        };
+
+        if shost.is_empty() {
+            return None;
+        }
+
+        let shost = if shost.contains(':') {
+            String::from(shost)
+        } else {
+            format!("{}:80", shost)
+        };
+
+        Some(WebhookSink {
+            _shost: shost,
+            _spath: String::from(spath),
+            _vpending: Vec::new(),
+            _isent: 0,
+            _ifailed: 0,
+            _bquiet: false,
+            _ierr: 0,
+        })
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    pub fn set_quiet(&mut self, bquiet: bool) {
+        self._bquiet = bquiet;
+    }
+
+    fn connect(&self) -> Result<TcpStream, String> {
+        let mut serr = format!("Host '{}' not found", self._shost);
+
+        for address in self
+            ._shost
+            .to_socket_addrs()
+            .map_err(|e| format!("{:?}", e))?
+        {
+            match TcpStream::connect_timeout(&address, WEBHOOK_TIMEOUT) {
+                Ok(stream) => return Ok(stream),
+                Err(e) => serr = format!("{:?}", e),
+            }
+        }
+
+        Err(serr)
+    }
+
+    fn post(&self, vbody: &[u8]) -> Result<u16, String> {
+        let mut stream = self.connect()?;
+
+        stream
+            .set_write_timeout(Some(WEBHOOK_TIMEOUT))
+            .and_then(|_| stream.set_read_timeout(Some(WEBHOOK_TIMEOUT)))
+            .map_err(|e| format!("{:?}", e))?;
+
+        let srequest = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self._spath,
+            self._shost,
+            vbody.len()
+        );
+
+        stream
+            .write_all(srequest.as_bytes())
+            .and_then(|_| stream.write_all(vbody))
+            .map_err(|e| format!("{:?}", e))?;
+
+        let mut sresponse = String::new();
+
+        stream
+            .read_to_string(&mut sresponse)
+            .map_err(|e| format!("{:?}", e))?;
+
+        //Status Line: "HTTP/1.1 200 OK"
+        match sresponse
+            .lines()
+            .next()
+            .and_then(|sline| sline.split_whitespace().nth(1))
+            .and_then(|scode| scode.parse::<u16>().ok())
+        {
+            Some(istatus) => Ok(istatus),
+            None => Err(format!("Response invalid: '{}'", sresponse)),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_sent_count(&self) -> u32 {
+        self._isent
+    }
+
+    pub fn get_failed_count(&self) -> u32 {
+        self._ifailed
+    }
+
+    pub fn get_error_code(&self) -> i32 {
+        self._ierr
+    }
+}
+
+impl EventObserver for WebhookSink {
+    fn notify(&mut self, event: &AccountEvent) {
+        match serde_json::to_vec(&event.to_record()) {
+            Ok(vrecord) => self._vpending.push(vrecord),
+            Err(e) => {
+                if !self._bquiet {
+                    eprintln!("Event JSON Export Error: '{:?}'", e);
+                }
+
+                self._ifailed += 1;
+                self._ierr = 1;
+            }
+        }
+    }
+
+    fn flush(&mut self) -> i32 {
+        let vpending: Vec<Vec<u8>> = self._vpending.drain(..).collect();
+
+        for vbatch in vpending.chunks(WEBHOOK_BATCH_SIZE) {
+            let mut vbody = vec![b'['];
+
+            for (irecord, vrecord) in vbatch.iter().enumerate() {
+                if irecord > 0 {
+                    vbody.push(b',');
+                }
+
+                vbody.extend_from_slice(vrecord);
+            }
+
+            vbody.push(b']');
+
+            match self.post(&vbody) {
+                Ok(istatus) if (200..300).contains(&istatus) => {
+                    self._isent += vbatch.len() as u32;
+                }
+                Ok(istatus) => {
+                    if !self._bquiet {
+                        eprintln!("Event Webhook Error: Status '{}'", istatus);
+                    }
+
+                    self._ifailed += vbatch.len() as u32;
+                    self._ierr = 1;
+                }
+                Err(e) => {
+                    if !self._bquiet {
+                        eprintln!("Event Webhook Error: '{}'", e);
+                    }
+
+                    self._ifailed += vbatch.len() as u32;
+                    self._ierr = 1;
+                }
+            } //match self.post(&vbody)
+        } //for vbatch in vpending.chunks(WEBHOOK_BATCH_SIZE)
+
+        self._ierr
+    }
+}
@@ -0,0 +1,349 @@
+use std::str::FromStr;
+
+use crate::model::exchange::Rounding;
+use crate::model::policy::{ClearingPolicy, DisputeDeadline, ExpiryAction, NegativeBalancePolicy};
+use crate::model::statement::{StatementFormat, StatementRange};
+
+//==============================================================================
+// Structure OptionError Declaration
+
+#[derive(Debug)]
+pub struct OptionError {
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure RunOptions Declaration
+
+/// The Script Parameters of one Run.
+/// Values are parsed into their Types but Files are not read yet.
+/// Empty File Names mean the Option was not given
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub command: String,
+    pub command_args: Vec<String>,
+    pub transactions: String,
+    pub log: String,
+    pub state: String,
+    pub events: String,
+    pub webhook: String,
+    pub client: Option<u16>,
+    pub tx: Option<u32>,
+    pub max_age: Option<u64>,
+    pub amount: Option<f64>,
+    pub operator: String,
+    pub reason: String,
+    pub currency: Option<String>,
+    pub range: StatementRange,
+    pub format: StatementFormat,
+    pub clearing: Option<ClearingPolicy>,
+    pub negative_balance: Option<NegativeBalancePolicy>,
+    pub fees: String,
+    pub tiers: String,
+    pub fee_report: String,
+    pub limits: String,
+    pub limit_window: Option<u64>,
+    pub limit_review: Option<bool>,
+    pub rules: String,
+    pub rule_report: String,
+    pub rates: String,
+    pub rounding: Option<Rounding>,
+    pub reorder_window: Option<u64>,
+    pub reorder_flush: bool,
+    pub late_report: String,
+    pub dispute_window: Option<u64>,
+    pub dispute_deadline: Option<DisputeDeadline>,
+    pub dispute_expiry: Option<ExpiryAction>,
+    pub cases: String,
+    pub ledger: String,
+    pub journal: String,
+    pub trial_balance: String,
+    pub reconcile: String,
+    pub reconcile_report: String,
+    pub tolerance: f64,
+    pub summary: bool,
+    pub summary_file: String,
+    pub import: bool,
+    pub currencies: bool,
+    pub quiet: bool,
+    pub debug: bool,
+}
+
+//==============================================================================
+// Structure OptionError Implementation
+
+impl OptionError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(error_message: String, error_code: i8) -> OptionError {
+        OptionError {
+            message: error_message,
+            code: error_code,
+        }
+    }
+}
+
+//==============================================================================
+// Structure RunOptions Implementation
+
+impl Default for RunOptions {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        RunOptions::new()
+    }
+}
+
+impl RunOptions {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Creates the Options of a Run without Parameters.
+    /// Notices are suppressed by default
+    pub fn new() -> RunOptions {
+        RunOptions {
+            command: String::new(),
+            command_args: Vec::new(),
+            transactions: String::new(),
+            log: String::new(),
+            state: String::new(),
+            events: String::new(),
+            webhook: String::new(),
+            client: None,
+            tx: None,
+            max_age: None,
+            amount: None,
+            operator: String::new(),
+            reason: String::new(),
+            currency: None,
+            range: StatementRange::All,
+            format: StatementFormat::Csv,
+            clearing: None,
+            negative_balance: None,
+            fees: String::new(),
+            tiers: String::new(),
+            fee_report: String::new(),
+            limits: String::new(),
+            limit_window: None,
+            limit_review: None,
+            rules: String::new(),
+            rule_report: String::new(),
+            rates: String::new(),
+            rounding: None,
+            reorder_window: None,
+            reorder_flush: false,
+            late_report: String::new(),
+            dispute_window: None,
+            dispute_deadline: None,
+            dispute_expiry: None,
+            cases: String::new(),
+            ledger: String::new(),
+            journal: String::new(),
+            trial_balance: String::new(),
+            reconcile: String::new(),
+            reconcile_report: String::new(),
+            tolerance: 0.0,
+            summary: false,
+            summary_file: String::new(),
+            import: false,
+            currencies: false,
+            quiet: true,
+            debug: false,
+        }
+    }
+
+    /// Parses the Script Parameters without the Program Name.
+    /// Invalid Values and unknown Options are refused with the Error Code `3`
+    pub fn parse<I, S>(vargs: I) -> Result<RunOptions, OptionError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut options = RunOptions::new();
+
+        for argument in vargs {
+            let argument = argument.as_ref();
+
+            if let Some(sarg) = argument.strip_prefix("--") {
+                //Parameter with Double Dash
+                match sarg.split_once('=') {
+                    Some((sname, svalue)) => options.set_value(sname, svalue)?,
+                    None => options.set_flag(sarg, argument)?,
+                }
+            } else if let Some(sarg) = argument.strip_prefix('-') {
+                //Parameter with Single Dash
+                let sflag = match sarg.to_lowercase().as_str() {
+                    "i" => "import",
+                    "v" => "verbose",
+                    "d" => "debug",
+                    _ => return Err(unknown_option(argument)),
+                };
+
+                options.set_flag(sflag, argument)?;
+            } else if !options.command.is_empty() {
+                options.command_args.push(String::from(argument));
+            } else if is_command(argument) {
+                options.command = argument.to_lowercase();
+            } else {
+                options.transactions = String::from(argument);
+            } //if let Some(sarg) = argument.strip_prefix("--")
+        } //for argument in vargs
+
+        Ok(options)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    fn set_value(&mut self, sname: &str, svalue: &str) -> Result<(), OptionError> {
+        match sname.to_lowercase().as_str() {
+            "log" => self.log = String::from(svalue),
+            "state" => self.state = String::from(svalue),
+            "events" => self.events = String::from(svalue),
+            "webhook" => self.webhook = String::from(svalue),
+            "client" => self.client = Some(parse_value(svalue, "Client")?),
+            "amount" => self.amount = Some(parse_value(svalue, "Amount")?),
+            "tx" => self.tx = Some(parse_value(svalue, "Transaction")?),
+            "max-age" => self.max_age = Some(parse_value(svalue, "Maximum Age")?),
+            "operator" => self.operator = String::from(svalue),
+            "reason" => self.reason = String::from(svalue),
+            "currency" => {
+                let scurrency = svalue.trim().to_uppercase();
+
+                self.currency = Some(scurrency).filter(|scurrency| !scurrency.is_empty());
+            }
+            "range" => {
+                self.range = StatementRange::parse(svalue)
+                    .ok_or_else(|| invalid_value(svalue, "Statement Range"))?
+            }
+            "format" => {
+                self.format = StatementFormat::parse(svalue)
+                    .ok_or_else(|| invalid_value(svalue, "Statement Format"))?
+            }
+            "clearing" => {
+                self.clearing = Some(
+                    ClearingPolicy::parse(svalue)
+                        .ok_or_else(|| invalid_value(svalue, "Clearing Policy"))?,
+                )
+            }
+            "negative-balance" => {
+                self.negative_balance = Some(
+                    NegativeBalancePolicy::parse(svalue)
+                        .ok_or_else(|| invalid_value(svalue, "Negative Balance Policy"))?,
+                )
+            }
+            "fees" => self.fees = String::from(svalue),
+            "tiers" => self.tiers = String::from(svalue),
+            "fee-report" => self.fee_report = String::from(svalue),
+            "limits" => self.limits = String::from(svalue),
+            "limit-window" => {
+                self.limit_window = Some(
+                    svalue
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|iseconds| *iseconds > 0)
+                        .ok_or_else(|| invalid_value(svalue, "Limit Window"))?,
+                )
+            }
+            "limit-action" => {
+                self.limit_review = match svalue.trim().to_lowercase().as_str() {
+                    "reject" => Some(false),
+                    "review" => Some(true),
+                    _ => return Err(invalid_value(svalue, "Limit Action")),
+                }
+            }
+            "rules" => self.rules = String::from(svalue),
+            "rule-report" => self.rule_report = String::from(svalue),
+            "rates" => self.rates = String::from(svalue),
+            "fx-rounding" => {
+                self.rounding = Some(
+                    Rounding::parse(svalue)
+                        .ok_or_else(|| invalid_value(svalue, "Exchange Rounding"))?,
+                )
+            }
+            "reorder-window" => self.reorder_window = Some(parse_value(svalue, "Reorder Window")?),
+            "late-report" => self.late_report = String::from(svalue),
+            "dispute-window" => self.dispute_window = Some(parse_value(svalue, "Dispute Window")?),
+            "dispute-deadline" => {
+                self.dispute_deadline = Some(
+                    DisputeDeadline::parse(svalue)
+                        .ok_or_else(|| invalid_value(svalue, "Dispute Deadline"))?,
+                )
+            }
+            "dispute-expiry" => {
+                self.dispute_expiry = Some(
+                    ExpiryAction::parse(svalue)
+                        .ok_or_else(|| invalid_value(svalue, "Dispute Expiry"))?,
+                )
+            }
+            "cases" => self.cases = String::from(svalue),
+            "ledger" => self.ledger = String::from(svalue),
+            "journal" => self.journal = String::from(svalue),
+            "trial-balance" => self.trial_balance = String::from(svalue),
+            "reconcile" => self.reconcile = String::from(svalue),
+            "reconcile-report" => self.reconcile_report = String::from(svalue),
+            "tolerance" => {
+                self.tolerance = svalue
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|ftolerance| *ftolerance >= 0.0)
+                    .ok_or_else(|| invalid_value(svalue, "Tolerance"))?
+            }
+            "summary" => self.summary_file = String::from(svalue),
+            _ => return Err(unknown_option(&format!("--{}", sname))),
+        } //match sname.to_lowercase().as_str()
+
+        Ok(())
+    }
+
+    fn set_flag(&mut self, sflag: &str, sargument: &str) -> Result<(), OptionError> {
+        match sflag.to_lowercase().as_str() {
+            "import" => self.import = true,
+            "currencies" => self.currencies = true,
+            "summary" => self.summary = true,
+            "reorder-flush" => self.reorder_flush = true,
+            "verbose" => self.quiet = false,
+            "debug" => {
+                //Reenable Notices
+                self.quiet = false;
+                //Enable Debug Output
+                self.debug = true;
+            }
+            _ => return Err(unknown_option(sargument)),
+        } //match sflag.to_lowercase().as_str()
+
+        Ok(())
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Known Commands are: "_admin_", "_statement_", "_audit_" and "_review_"
+pub fn is_command(sargument: &str) -> bool {
+    matches!(
+        sargument.to_lowercase().as_str(),
+        "admin" | "statement" | "audit" | "review"
+    )
+}
+
+fn parse_value<T: FromStr>(svalue: &str, sname: &str) -> Result<T, OptionError> {
+    svalue
+        .parse::<T>()
+        .map_err(|_| invalid_value(svalue, sname))
+}
+
+fn invalid_value(svalue: &str, sname: &str) -> OptionError {
+    OptionError::new(format!("{} '{}' is invalid.", sname, svalue), 3)
+}
+
+fn unknown_option(soption: &str) -> OptionError {
+    OptionError::new(format!("Option '{}' is unknown.", soption), 3)
+}
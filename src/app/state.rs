@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use std::fs;
+use std::path::Path;
+
+use super::super::model::account::Account;
+use super::super::model::journal::Posting;
+use super::super::model::ledger::LedgerEntry;
+use super::super::model::review::ReviewItem;
+use super::super::model::rule::RuleHit;
+use super::super::model::summary::RunSummary;
+use super::super::model::transaction::{Movement, Transaction};
+use super::consumer::{write_file_atomic, PartitionOffset};
+use super::importer::LateArrival;
+
+//==============================================================================
+// Structure StateSnapshot Declaration
+
+/// The persisted State of the Engine.
+/// The Input Offsets are stored together with the Accounts and Transactions
+/// so that they are always committed in one single atomic Step
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct StateSnapshot {
+    pub offsets: Vec<PartitionOffset>,
+    pub accounts: Vec<Account>,
+    pub transactions: Vec<Transaction>,
+    /// The next ID for internally created Transactions
+    #[serde(default)]
+    pub next_internal: Option<u32>,
+    #[serde(default)]
+    pub ledger: Vec<LedgerEntry>,
+    #[serde(default)]
+    pub journal: Vec<Posting>,
+    /// The Movements parked for a Review
+    #[serde(default)]
+    pub review: Vec<ReviewItem>,
+    /// The Movements waiting in the Reorder Buffer
+    #[serde(default)]
+    pub buffer: Vec<Movement>,
+    /// The newest Timestamp seen by the Reorder Buffer
+    #[serde(default)]
+    pub newest: Option<u64>,
+    #[serde(default)]
+    pub late: Vec<LateArrival>,
+    #[serde(default)]
+    pub hits: Vec<RuleHit>,
+    /// The Statistics accumulated over all Runs on the State
+    #[serde(default)]
+    pub summary: RunSummary,
+}
+
+//==============================================================================
+// Structure StateError Declaration
+
+#[derive(Debug)]
+pub struct StateError {
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure StateError Implementation
+
+impl StateError {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(error_message: String, error_code: i8) -> StateError {
+        StateError {
+            message: error_message,
+            code: error_code,
+        }
+    }
+}
+
+//==============================================================================
+// Structure StateSnapshot Implementation
+
+impl StateSnapshot {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> StateSnapshot {
+        StateSnapshot::default()
+    }
+
+    /// Loads the Snapshot from the File.
+    /// A missing File produces an empty Snapshot as on the first Run
+    pub fn from_file(sfilename: &str) -> Result<StateSnapshot, StateError> {
+        if !Path::new(sfilename).exists() {
+            return Ok(StateSnapshot::new());
+        }
+
+        match fs::read(sfilename) {
+            Ok(vdata) => match serde_json::from_slice(&vdata) {
+                Ok(snapshot) => Ok(snapshot),
+                Err(e) => Err(StateError::new(
+                    format!("State File '{}': Parse failed: '{:?}'", sfilename, e),
+                    1,
+                )),
+            },
+            Err(e) => Err(StateError::new(
+                format!("State File '{}': Read failed: '{:?}'", sfilename, e),
+                1,
+            )),
+        } //match fs::read(sfilename)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Writes the Snapshot to a temporary File and renames it over the State File.
+    /// So the State File contains either the previous or the new State
+    pub fn to_file(&self, sfilename: &str) -> Result<(), StateError> {
+        match serde_json::to_vec(self) {
+            Ok(vdata) => match write_file_atomic(Path::new(sfilename), &vdata) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(StateError::new(e.message, e.code)),
+            },
+            Err(e) => Err(StateError::new(
+                format!("State File '{}': Serialize failed: '{:?}'", sfilename, e),
+                1,
+            )),
+        } //match serde_json::to_vec(self)
+    }
+}
@@ -1,6 +1,7 @@
 pub mod app;
 pub mod model;
 
+use app::options::RunOptions;
 use app::RunClientAccounting;
 
 use std::process::exit;
@@ -8,70 +9,23 @@ use std::process::exit;
 //==============================================================================
 // Auxiliary Functions
 
-fn parse_parameters(application: &mut RunClientAccounting) {
+fn run_app() -> i32 {
     //-------------------------------------
     //Read the Script Parameters
 
-    let mut sarg;
-    let mut iargidx = 0;
-
-    //eprintln!("args: ");
-
-    // Prints each argument on a separate line
-    for argument in std::env::args() {
-        //eprintln!("[{}] '{}'", iargidx, argument.as_str());
-
-        if argument.starts_with("--") {
-            //Parameter with Double Dash
-            sarg = argument.split_at(2).1;
-
-            match sarg.to_lowercase().as_str() {
-                "import" => application.set_import(true),
-                "verbose" => application.set_quiet(false),
-                "debug" => {
-                    //Reenable Notices
-                    application.set_quiet(false);
-                    //Enable Debug Output
-                    application.set_debug(true);
-                }
-                _ => {}
-            } //match sarg
-        } else if argument.starts_with('-') {
-            //Parameter with Single Dash
-            sarg = argument.split_at(1).1;
-
-            match sarg.to_lowercase().as_str() {
-                "i" => application.set_import(true),
-                "v" => application.set_quiet(false),
-                "d" => {
-                    //Reenable Notices
-                    application.set_quiet(false);
-                    //Enable Debug Output
-                    application.set_debug(true);
-                }
-                _ => {}
-            } //match sarg
-        } else if iargidx > 0 {
-            //Any Not First Parameter
-            application.set_transaction_filename(&argument);
-        } //if argument.starts_with("--")
-
-        iargidx += 1;
-    } //for argument in std::env::args()
-
-    //eprintln!("args end.");
-}
+    let options = match RunOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Parameter Error: {}", e.message);
+
+            return e.code as i32;
+        }
+    };
 
-fn run_app() -> i32 {
     //-------------------------------------
     //Create the Application Object
 
-    let mut accounting = RunClientAccounting::new();
-
-    //Suppress Notices by default
-    accounting.set_quiet(true);
-
-    parse_parameters(&mut accounting);
+    let mut accounting = RunClientAccounting::from_options(options);
 
     if accounting.is_debug() && !accounting.is_quiet() {
         eprintln!("app dmp 1:\n{:?}", accounting);
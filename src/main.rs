@@ -1,5 +1,3 @@
-use client_accounting;
-
 fn main() {
     client_accounting::main()
 }
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use csv::{ReaderBuilder, Trim, WriterBuilder};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Deref, DerefMut};
 
 //#[macro_use]
 //extern crate serde_derive;
@@ -9,13 +10,203 @@ use std::collections::HashMap;
 //==============================================================================
 // Structure Account Declaration
 
-#[derive(Debug, Deserialize, Serialize)]
+/// The Balances of a Client.
+/// The Balances in other Currencies are kept in `currencies`
+/// while the Lock applies to the whole Account
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Account {
     pub client: u16,
     pub available: f64,
     pub held: f64,
     pub total: f64,
     pub locked: bool,
+    #[serde(default)]
+    pub currencies: BTreeMap<String, CurrencyBalance>,
+}
+
+//==============================================================================
+// Structure CurrencyBalance Declaration
+
+/// The Balances of an Account in one Currency
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct CurrencyBalance {
+    pub available: f64,
+    pub held: f64,
+    pub total: f64,
+}
+
+//==============================================================================
+// Structure CurrencyGuard Declaration
+
+/// An Account switched to the Balances of one Currency.
+/// The primary Balances are restored when the Guard is dropped
+/// and thus also on every early Return
+#[derive(Debug)]
+pub struct CurrencyGuard<'a> {
+    account: &'a mut Account,
+    currency: Option<String>,
+}
+
+//==============================================================================
+// Structure AccountRow Declaration
+
+/// The Columns of the Accounts Export
+#[derive(Serialize)]
+struct AccountRow {
+    client: u16,
+    available: f64,
+    held: f64,
+    total: f64,
+    locked: bool,
+}
+
+//==============================================================================
+// Structure CurrencyRow Declaration
+
+/// The Columns of the Accounts Export with one Row per Currency
+#[derive(Serialize)]
+struct CurrencyRow<'a> {
+    client: u16,
+    currency: &'a str,
+    available: f64,
+    held: f64,
+    total: f64,
+    locked: bool,
+}
+
+//==============================================================================
+// Structure Account Implementation
+
+impl Account {
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Switches the Account to the Balances in the Currency `ocurrency`
+    /// as long as the returned Guard lives.
+    /// Without Currency the primary Balances are used
+    pub fn switch_to(&mut self, ocurrency: Option<&str>) -> CurrencyGuard<'_> {
+        self.switch_currency(ocurrency);
+
+        CurrencyGuard {
+            account: self,
+            currency: ocurrency.map(String::from),
+        }
+    }
+
+    /// Exchanges the primary Balances with the Balances in the Currency `ocurrency`.
+    /// Calling it a second Time with the same Currency restores the primary Balances.
+    /// Without Currency nothing is changed
+    fn switch_currency(&mut self, ocurrency: Option<&str>) {
+        if let Some(scurrency) = ocurrency {
+            let balance = self.currencies.remove(scurrency).unwrap_or_default();
+
+            self.currencies.insert(
+                String::from(scurrency),
+                CurrencyBalance {
+                    available: self.available,
+                    held: self.held,
+                    total: self.total,
+                },
+            );
+
+            self.available = balance.available;
+            self.held = balance.held;
+            self.total = balance.total;
+        } //if let Some(scurrency) = ocurrency
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the Balances per Currency with the primary Balances under the empty Currency.
+    /// `ocurrency` is the Currency the Account is switched to
+    pub fn get_balances(&self, ocurrency: Option<&str>) -> BTreeMap<String, CurrencyBalance> {
+        let mut lstbalances = self.currencies.clone();
+        let primary = CurrencyBalance {
+            available: self.available,
+            held: self.held,
+            total: self.total,
+        };
+
+        match ocurrency {
+            Some(scurrency) => {
+                let balance = lstbalances.remove(scurrency).unwrap_or_default();
+
+                lstbalances.insert(String::from(scurrency), primary);
+                lstbalances.insert(String::new(), balance);
+            }
+            None => {
+                lstbalances.insert(String::new(), primary);
+            }
+        }
+
+        lstbalances
+    }
+
+    fn to_row(&self) -> AccountRow {
+        AccountRow {
+            client: self.client,
+            available: self.available,
+            held: self.held,
+            total: self.total,
+            locked: self.locked,
+        }
+    }
+
+    /// Returns one Row per Currency. The primary Balances have no Currency
+    /// and are left out when they are empty and other Currencies exist
+    fn to_currency_rows(&self) -> Vec<CurrencyRow<'_>> {
+        let mut vrows = Vec::new();
+
+        if self.currencies.is_empty() || self.total != 0.0 || self.held != 0.0 {
+            vrows.push(CurrencyRow {
+                client: self.client,
+                currency: "",
+                available: self.available,
+                held: self.held,
+                total: self.total,
+                locked: self.locked,
+            });
+        }
+
+        for (scurrency, balance) in self.currencies.iter() {
+            vrows.push(CurrencyRow {
+                client: self.client,
+                currency: scurrency.as_str(),
+                available: balance.available,
+                held: balance.held,
+                total: balance.total,
+                locked: self.locked,
+            });
+        }
+
+        vrows
+    }
+}
+
+//==============================================================================
+// Structure CurrencyGuard Implementation
+
+impl Deref for CurrencyGuard<'_> {
+    type Target = Account;
+
+    fn deref(&self) -> &Account {
+        self.account
+    }
+}
+
+impl DerefMut for CurrencyGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Account {
+        self.account
+    }
+}
+
+impl Drop for CurrencyGuard<'_> {
+    fn drop(&mut self) {
+        self.account.switch_currency(self.currency.as_deref());
+    }
 }
 
 //==============================================================================
@@ -79,6 +270,7 @@ impl AccountFactory {
             held: 0.0,
             total: 0.0,
             locked: false,
+            currencies: BTreeMap::new(),
         };
 
         self.lstaccounts.insert(account.client, account);
@@ -106,10 +298,10 @@ impl AccountFactory {
             .has_headers(bheaders)
             .trim(Trim::All)
             .from_reader(saccounts_csv.as_bytes());
-        let mut iter = rdr.deserialize();
+        let iter = rdr.deserialize();
         let mut icount = 0;
 
-        while let Some(result) = iter.next() {
+        for result in iter {
             match result {
                 Ok(r) => {
                     let record: Account = r;
@@ -124,7 +316,7 @@ impl AccountFactory {
                     }
                 }
             } //match result
-        } //while let Some(result) = iter.next()
+        } //for result in iter
 
         icount
     }
@@ -138,14 +330,14 @@ impl AccountFactory {
         let mut wtr = WriterBuilder::new().from_writer(vec![]);
 
         for acc in self.lstaccounts.iter() {
-            match wtr.serialize(acc.1) {
+            match wtr.serialize(acc.1.to_row()) {
                 Ok(_) => {}
                 Err(e) => {
                     if !bquiet {
                         eprintln!("Account CSV Export Error: '{:?}'", e)
                     }
                 }
-            } //match wtr.serialize(acc.1)
+            } //match wtr.serialize(acc.1.to_row())
         } //for acc in self.lstaccounts.iter()
 
         let data = match wtr.into_inner() {
@@ -174,4 +366,42 @@ impl AccountFactory {
 
         data
     }
+
+    /// Exports the Accounts with one Row per Currency ordered by Client and Currency
+    /// with the Columns `client,currency,available,held,total,locked`
+    #[allow(unused_variables)]
+    pub fn export_currency_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().has_headers(false).from_writer(vec![]);
+        let mut vaccounts: Vec<&Account> = self.lstaccounts.values().collect();
+
+        vaccounts.sort_by_key(|acc| acc.client);
+
+        if let Err(e) =
+            wtr.write_record(["client", "currency", "available", "held", "total", "locked"])
+        {
+            if !bquiet {
+                eprintln!("Account CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for row in vaccounts.iter().flat_map(|acc| acc.to_currency_rows()) {
+            if let Err(e) = wtr.serialize(row) {
+                if !bquiet {
+                    eprintln!("Account CSV Export Error: '{:?}'", e)
+                }
+            }
+        } //for row in vaccounts.iter().flat_map(|acc| acc.to_currency_rows())
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Account CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
 }
@@ -0,0 +1,261 @@
+use csv::WriterBuilder;
+use std::collections::BTreeMap;
+
+use super::account::AccountFactory;
+use super::journal::Journal;
+use super::round_amount;
+use super::transaction::{Transaction, TransactionFactory};
+
+//==============================================================================
+// Structure AuditViolation Declaration
+
+/// A broken Invariant of an Account or a Transaction.
+/// The `check` names the Invariant like `total_mismatch` or `held_mismatch`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditViolation {
+    pub check: String,
+    pub client: u16,
+    pub tx: Option<u32>,
+    pub message: String,
+}
+
+//==============================================================================
+// Structure AuditReport Declaration
+
+/// The Violations found by an Audit ordered by Client and Transaction
+#[derive(Debug, Default)]
+pub struct AuditReport {
+    pub vviolations: Vec<AuditViolation>,
+}
+
+//==============================================================================
+// Structure AuditReport Implementation
+
+impl AuditReport {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Verifies the Invariants of all Accounts and Transactions:
+    /// * `total_mismatch`: `total` equals `available + held` in each Currency
+    /// * `held_mismatch`: `held` equals the Funds held for disputed and clearing Transactions
+    /// * `locked_without_chargeback`: a locked Account has a charged back or a "_freeze_" Transaction
+    /// * `journal_mismatch`: `available` and `held` equal the Balances derived from the Journal Postings
+    /// * `orphan_transaction`: each Transaction belongs to an existing Account
+    /// * `invalid_status`: each Transaction has a known Status
+    ///
+    /// Disputes and Chargebacks are checked on the Account of `Transaction::get_holder()`
+    pub fn new(
+        accfactory: &AccountFactory,
+        txfactory: &TransactionFactory,
+        journal: &Journal,
+    ) -> AuditReport {
+        let mut report = AuditReport::default();
+        let mut vtransactions: Vec<&Transaction> = txfactory.lsttransactions.values().collect();
+        let mut lstheld: BTreeMap<(u16, String), f64> = BTreeMap::new();
+
+        vtransactions.sort_by_key(|txrec| txrec.tx);
+
+        for txrec in vtransactions.iter() {
+            if !accfactory.lstaccounts.contains_key(&txrec.client) {
+                report.add(
+                    "orphan_transaction",
+                    txrec.client,
+                    Some(txrec.tx),
+                    format!("Account (id: '{}') does not exist", txrec.client),
+                );
+            }
+
+            if let Some(idestination) = txrec.destination.filter(|_| txrec.status == 1) {
+                if !accfactory.lstaccounts.contains_key(&idestination) {
+                    report.add(
+                        "orphan_transaction",
+                        txrec.client,
+                        Some(txrec.tx),
+                        format!(
+                            "Destination Account (id: '{}') does not exist",
+                            idestination
+                        ),
+                    );
+                }
+            }
+
+            if !(-2..=2).contains(&txrec.status) {
+                report.add(
+                    "invalid_status",
+                    txrec.client,
+                    Some(txrec.tx),
+                    format!("Status '{}' is unknown", txrec.status),
+                );
+            }
+
+            //Clearing Deposits are on hold as a whole
+            let fheld = if txrec.clearing {
+                txrec.amount - txrec.charged_back
+            } else if txrec.status == -2 {
+                txrec.get_held()
+            } else {
+                0.0
+            };
+
+            //The Funds of a disputed Transfer are held on its Destination
+            if fheld != 0.0 {
+                *lstheld
+                    .entry((
+                        txrec.get_holder(),
+                        txrec.currency.clone().unwrap_or_default(),
+                    ))
+                    .or_default() += fheld;
+            }
+        } //for txrec in vtransactions.iter()
+
+        let mut vaccounts: Vec<_> = accfactory.lstaccounts.values().collect();
+
+        vaccounts.sort_by_key(|acc| acc.client);
+
+        for acc in vaccounts.into_iter() {
+            for (scurrency, balance) in acc.get_balances(None).iter() {
+                let slabel = if scurrency.is_empty() {
+                    String::new()
+                } else {
+                    format!(" in {}", scurrency)
+                };
+                let fsum = round_amount(balance.available + balance.held);
+
+                if round_amount(balance.total) != fsum {
+                    report.add(
+                        "total_mismatch",
+                        acc.client,
+                        None,
+                        format!(
+                            "Total '{}' differs from Available and Held '{}'{}",
+                            balance.total, fsum, slabel
+                        ),
+                    );
+                }
+
+                let fexpected = round_amount(
+                    lstheld
+                        .get(&(acc.client, scurrency.clone()))
+                        .copied()
+                        .unwrap_or(0.0),
+                );
+
+                if round_amount(balance.held) != fexpected {
+                    report.add(
+                        "held_mismatch",
+                        acc.client,
+                        None,
+                        format!(
+                            "Held '{}' differs from the disputed and clearing Funds '{}'{}",
+                            balance.held, fexpected, slabel
+                        ),
+                    );
+                }
+
+                let derived = journal.derive_balance(
+                    acc.client,
+                    Some(scurrency.as_str()).filter(|scurrency| !scurrency.is_empty()),
+                );
+
+                if round_amount(balance.available) != derived.available
+                    || round_amount(balance.held) != derived.held
+                {
+                    report.add(
+                        "journal_mismatch",
+                        acc.client,
+                        None,
+                        format!(
+                            "Available and Held '{} / {}' differ from the Journal '{} / {}'{}",
+                            balance.available,
+                            balance.held,
+                            derived.available,
+                            derived.held,
+                            slabel
+                        ),
+                    );
+                }
+            } //for (scurrency, balance) in acc.get_balances(None).iter()
+
+            if acc.locked
+                && !vtransactions.iter().any(|txrec| {
+                    (txrec.get_holder() == acc.client && txrec.charged_back > 0.0)
+                        || (txrec.client == acc.client
+                            && txrec.tx_type == "freeze"
+                            && txrec.status == 1)
+                })
+            {
+                report.add(
+                    "locked_without_chargeback",
+                    acc.client,
+                    None,
+                    String::from("Account is locked without Chargeback or Freeze"),
+                );
+            }
+        } //for acc in vaccounts.into_iter()
+
+        report
+            .vviolations
+            .sort_by_key(|violation| (violation.client, violation.tx));
+
+        report
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    fn add(&mut self, scheck: &str, client_id: u16, otx: Option<u32>, smessage: String) {
+        self.vviolations.push(AuditViolation {
+            check: String::from(scheck),
+            client: client_id,
+            tx: otx,
+            message: smessage,
+        });
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_clean(&self) -> bool {
+        self.vviolations.is_empty()
+    }
+
+    /// Exports the Violations with the Columns `check,client,tx,message`
+    #[allow(unused_variables)]
+    pub fn export_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        if let Err(e) = wtr.write_record(["check", "client", "tx", "message"]) {
+            if !bquiet {
+                eprintln!("Audit CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for violation in self.vviolations.iter() {
+            if let Err(e) = wtr.write_record([
+                violation.check.clone(),
+                violation.client.to_string(),
+                violation.tx.map(|itx| itx.to_string()).unwrap_or_default(),
+                violation.message.clone(),
+            ]) {
+                if !bquiet {
+                    eprintln!("Audit CSV Export Error: '{:?}'", e)
+                }
+            }
+        } //for violation in self.vviolations.iter()
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Audit CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+}
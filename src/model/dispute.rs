@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+//==============================================================================
+// Structure CaseEntry Declaration
+
+/// A State Change of a Dispute Case
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CaseEntry {
+    /// The Event which changed the Case like `DisputeOpened` or `DisputeResolved`
+    pub event: String,
+    #[serde(default)]
+    pub amount: Option<f64>,
+    pub at: u64,
+    #[serde(default)]
+    pub reason_code: Option<String>,
+}
+
+//==============================================================================
+// Structure DisputeCase Declaration
+
+/// The Record of a Dispute on a Transaction from its Opening until it is closed
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DisputeCase {
+    /// The external Case Reference of the Chargeback Team
+    #[serde(default)]
+    pub case: Option<String>,
+    #[serde(default)]
+    pub reason_code: Option<String>,
+    pub opened_at: u64,
+    #[serde(default)]
+    pub closed_at: Option<u64>,
+    /// `resolved` or `charged_back` when the Case is closed
+    #[serde(default)]
+    pub outcome: Option<String>,
+    #[serde(default)]
+    pub history: Vec<CaseEntry>,
+}
+
+//==============================================================================
+// Structure DisputeCase Implementation
+
+impl DisputeCase {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(iopened: u64, ocase: Option<String>, oreason_code: Option<String>) -> DisputeCase {
+        DisputeCase {
+            case: ocase,
+            reason_code: oreason_code,
+            opened_at: iopened,
+            closed_at: None,
+            outcome: None,
+            history: Vec::new(),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    pub fn add_entry(
+        &mut self,
+        sevent: &str,
+        oamount: Option<f64>,
+        iat: u64,
+        oreason_code: Option<String>,
+    ) {
+        self.history.push(CaseEntry {
+            event: String::from(sevent),
+            amount: oamount,
+            at: iat,
+            reason_code: oreason_code,
+        });
+    }
+
+    pub fn close(&mut self, iclosed: u64, soutcome: &str) {
+        self.closed_at = Some(iclosed);
+        self.outcome = Some(String::from(soutcome));
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_open(&self) -> bool {
+        self.closed_at.is_none()
+    }
+
+    /// Returns the State Changes in the Form `<event>:<amount>@<time>` separated by `;`
+    pub fn format_history(&self) -> String {
+        self.history
+            .iter()
+            .map(|entry| match entry.amount {
+                Some(famount) => format!("{}:{:?}@{}", entry.event, famount, entry.at),
+                None => format!("{}@{}", entry.event, entry.at),
+            })
+            .collect::<Vec<String>>()
+            .join(";")
+    }
+}
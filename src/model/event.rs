@@ -0,0 +1,310 @@
+use serde::{Deserialize, Serialize};
+
+//==============================================================================
+// Enum RejectReason Declaration
+
+/// Reason why a Movement was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RejectReason {
+    InsufficientFunds,
+    AccountLocked,
+    AmountExceeded,
+    TransactionLimitExceeded,
+    WithdrawalLimitExceeded,
+    BalanceLimitExceeded,
+    CurrencyMismatch,
+    RateMissing,
+    DisputeWindowExpired,
+    RuleViolation,
+    ReviewRejected,
+}
+
+//==============================================================================
+// Enum RejectReason Implementation
+
+impl RejectReason {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectReason::InsufficientFunds => "insufficient_funds",
+            RejectReason::AccountLocked => "account_locked",
+            RejectReason::AmountExceeded => "amount_exceeded",
+            RejectReason::TransactionLimitExceeded => "transaction_limit_exceeded",
+            RejectReason::WithdrawalLimitExceeded => "withdrawal_limit_exceeded",
+            RejectReason::BalanceLimitExceeded => "balance_limit_exceeded",
+            RejectReason::CurrencyMismatch => "currency_mismatch",
+            RejectReason::RateMissing => "rate_missing",
+            RejectReason::DisputeWindowExpired => "dispute_window_expired",
+            RejectReason::RuleViolation => "rule_violation",
+            RejectReason::ReviewRejected => "review_rejected",
+        }
+    }
+}
+
+//==============================================================================
+// Enum AccountEvent Declaration
+
+/// State Change of an Account produced by the Processing of a Movement
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountEvent {
+    DepositAccepted {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    DepositCleared {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    DepositRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+    WithdrawalRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+    DisputeOpened {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    DisputeRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+    DebtRecorded {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    DisputeExpired {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    DisputeResolved {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    ChargebackApplied {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    AccountLocked {
+        client: u16,
+        tx: u32,
+    },
+    AccountUnlocked {
+        client: u16,
+        tx: u32,
+    },
+    AdjustmentApplied {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    RefundApplied {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    RefundRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+    TransferSent {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    TransferReceived {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    TransferRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+    FeeCharged {
+        client: u16,
+        tx: u32,
+        amount: f64,
+    },
+    FeeRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+    ExchangeApplied {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        converted: f64,
+    },
+    ExchangeRejected {
+        client: u16,
+        tx: u32,
+        amount: f64,
+        reason: RejectReason,
+    },
+}
+
+//==============================================================================
+// Structure EventRecord Declaration
+
+/// Flat Representation of an `AccountEvent` for the CSV and JSON Export
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EventRecord {
+    pub event: String,
+    pub client: u16,
+    pub tx: u32,
+    pub amount: Option<f64>,
+    pub reason: Option<String>,
+}
+
+//==============================================================================
+// Enum AccountEvent Implementation
+
+impl AccountEvent {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_event_type(&self) -> &'static str {
+        match self {
+            AccountEvent::DepositAccepted { .. } => "DepositAccepted",
+            AccountEvent::DepositCleared { .. } => "DepositCleared",
+            AccountEvent::DepositRejected { .. } => "DepositRejected",
+            AccountEvent::WithdrawalRejected { .. } => "WithdrawalRejected",
+            AccountEvent::DisputeOpened { .. } => "DisputeOpened",
+            AccountEvent::DisputeRejected { .. } => "DisputeRejected",
+            AccountEvent::DebtRecorded { .. } => "DebtRecorded",
+            AccountEvent::DisputeExpired { .. } => "DisputeExpired",
+            AccountEvent::DisputeResolved { .. } => "DisputeResolved",
+            AccountEvent::ChargebackApplied { .. } => "ChargebackApplied",
+            AccountEvent::AccountLocked { .. } => "AccountLocked",
+            AccountEvent::AccountUnlocked { .. } => "AccountUnlocked",
+            AccountEvent::AdjustmentApplied { .. } => "AdjustmentApplied",
+            AccountEvent::RefundApplied { .. } => "RefundApplied",
+            AccountEvent::RefundRejected { .. } => "RefundRejected",
+            AccountEvent::TransferSent { .. } => "TransferSent",
+            AccountEvent::TransferReceived { .. } => "TransferReceived",
+            AccountEvent::TransferRejected { .. } => "TransferRejected",
+            AccountEvent::FeeCharged { .. } => "FeeCharged",
+            AccountEvent::FeeRejected { .. } => "FeeRejected",
+            AccountEvent::ExchangeApplied { .. } => "ExchangeApplied",
+            AccountEvent::ExchangeRejected { .. } => "ExchangeRejected",
+        }
+    }
+
+    pub fn get_client(&self) -> u16 {
+        match self {
+            AccountEvent::DepositAccepted { client, .. }
+            | AccountEvent::DepositCleared { client, .. }
+            | AccountEvent::DepositRejected { client, .. }
+            | AccountEvent::WithdrawalRejected { client, .. }
+            | AccountEvent::DisputeOpened { client, .. }
+            | AccountEvent::DisputeRejected { client, .. }
+            | AccountEvent::DebtRecorded { client, .. }
+            | AccountEvent::DisputeExpired { client, .. }
+            | AccountEvent::DisputeResolved { client, .. }
+            | AccountEvent::ChargebackApplied { client, .. }
+            | AccountEvent::AccountLocked { client, .. }
+            | AccountEvent::AccountUnlocked { client, .. }
+            | AccountEvent::AdjustmentApplied { client, .. }
+            | AccountEvent::RefundApplied { client, .. }
+            | AccountEvent::RefundRejected { client, .. }
+            | AccountEvent::TransferSent { client, .. }
+            | AccountEvent::TransferReceived { client, .. }
+            | AccountEvent::TransferRejected { client, .. }
+            | AccountEvent::FeeCharged { client, .. }
+            | AccountEvent::FeeRejected { client, .. }
+            | AccountEvent::ExchangeApplied { client, .. }
+            | AccountEvent::ExchangeRejected { client, .. } => *client,
+        }
+    }
+
+    pub fn get_transaction(&self) -> u32 {
+        match self {
+            AccountEvent::DepositAccepted { tx, .. }
+            | AccountEvent::DepositCleared { tx, .. }
+            | AccountEvent::DepositRejected { tx, .. }
+            | AccountEvent::WithdrawalRejected { tx, .. }
+            | AccountEvent::DisputeOpened { tx, .. }
+            | AccountEvent::DisputeRejected { tx, .. }
+            | AccountEvent::DebtRecorded { tx, .. }
+            | AccountEvent::DisputeExpired { tx, .. }
+            | AccountEvent::DisputeResolved { tx, .. }
+            | AccountEvent::ChargebackApplied { tx, .. }
+            | AccountEvent::AccountLocked { tx, .. }
+            | AccountEvent::AccountUnlocked { tx, .. }
+            | AccountEvent::AdjustmentApplied { tx, .. }
+            | AccountEvent::RefundApplied { tx, .. }
+            | AccountEvent::RefundRejected { tx, .. }
+            | AccountEvent::TransferSent { tx, .. }
+            | AccountEvent::TransferReceived { tx, .. }
+            | AccountEvent::TransferRejected { tx, .. }
+            | AccountEvent::FeeCharged { tx, .. }
+            | AccountEvent::FeeRejected { tx, .. }
+            | AccountEvent::ExchangeApplied { tx, .. }
+            | AccountEvent::ExchangeRejected { tx, .. } => *tx,
+        }
+    }
+
+    pub fn to_record(&self) -> EventRecord {
+        let (oamount, oreason) = match self {
+            AccountEvent::DepositAccepted { amount, .. }
+            | AccountEvent::DepositCleared { amount, .. }
+            | AccountEvent::DisputeOpened { amount, .. }
+            | AccountEvent::DebtRecorded { amount, .. }
+            | AccountEvent::DisputeExpired { amount, .. }
+            | AccountEvent::DisputeResolved { amount, .. }
+            | AccountEvent::ChargebackApplied { amount, .. }
+            | AccountEvent::AdjustmentApplied { amount, .. }
+            | AccountEvent::RefundApplied { amount, .. }
+            | AccountEvent::TransferSent { amount, .. }
+            | AccountEvent::TransferReceived { amount, .. }
+            | AccountEvent::FeeCharged { amount, .. }
+            | AccountEvent::ExchangeApplied { amount, .. } => (Some(*amount), None),
+            AccountEvent::DepositRejected { amount, reason, .. }
+            | AccountEvent::WithdrawalRejected { amount, reason, .. }
+            | AccountEvent::DisputeRejected { amount, reason, .. }
+            | AccountEvent::RefundRejected { amount, reason, .. }
+            | AccountEvent::TransferRejected { amount, reason, .. }
+            | AccountEvent::FeeRejected { amount, reason, .. }
+            | AccountEvent::ExchangeRejected { amount, reason, .. } => {
+                (Some(*amount), Some(String::from(reason.as_str())))
+            }
+            AccountEvent::AccountLocked { .. } | AccountEvent::AccountUnlocked { .. } => {
+                (None, None)
+            }
+        };
+
+        EventRecord {
+            event: String::from(self.get_event_type()),
+            client: self.get_client(),
+            tx: self.get_transaction(),
+            amount: oamount,
+            reason: oreason,
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use csv::{ReaderBuilder, Trim};
+use serde::Deserialize;
+
+//==============================================================================
+// Structure ExchangeRate Declaration
+
+/// The Rate to convert the Currency `from` into the Currency `to`
+/// which is effective from the Day `date` (`YYYY-MM-DD`) on.
+/// The `spread` in Percent is kept from the converted Amount
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeRate {
+    pub date: String,
+    pub from: String,
+    pub to: String,
+    pub rate: f64,
+    #[serde(default)]
+    pub spread: Option<f64>,
+    #[serde(skip)]
+    iday: i64,
+}
+
+//==============================================================================
+// Enum RoundingMode Declaration
+
+/// Defines how the converted Amounts are rounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    #[default]
+    Nearest,
+    Down,
+    Up,
+}
+
+//==============================================================================
+// Structure Rounding Declaration
+
+/// The Rounding of the converted Amounts to a Number of Decimals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rounding {
+    pub mode: RoundingMode,
+    pub decimals: u32,
+}
+
+//==============================================================================
+// Structure Conversion Declaration
+
+/// The Result of a Currency Conversion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub rate: f64,
+    pub spread: f64,
+    pub converted: f64,
+}
+
+//==============================================================================
+// Structure RateImportError Declaration
+
+#[derive(Debug)]
+pub struct RateImportError {
+    pub ok_count: u32,
+    pub lines: Vec<u32>,
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure ExchangeTable Declaration
+
+/// The Exchange Rates and the Rounding of the converted Amounts
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeTable {
+    pub vrates: Vec<ExchangeRate>,
+    pub rounding: Rounding,
+}
+
+//==============================================================================
+// Structure Rounding Implementation
+
+impl Default for Rounding {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        Rounding {
+            mode: RoundingMode::Nearest,
+            decimals: 4,
+        }
+    }
+}
+
+impl Rounding {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Rounding from its Text Form: `nearest`, `down` or `up`
+    /// optionally followed by the Number of Decimals as in `down:2`
+    pub fn parse(srounding: &str) -> Option<Rounding> {
+        let slower = srounding.trim().to_lowercase();
+        let (smode, odecimals) = match slower.split_once(':') {
+            Some((smode, sdecimals)) => match sdecimals.parse::<u32>() {
+                Ok(idecimals) if idecimals <= 4 => (smode, Some(idecimals)),
+                _ => return None,
+            },
+            None => (slower.as_str(), None),
+        };
+        let mode = match smode {
+            "nearest" => RoundingMode::Nearest,
+            "down" => RoundingMode::Down,
+            "up" => RoundingMode::Up,
+            _ => return None,
+        };
+
+        Some(Rounding {
+            mode,
+            decimals: odecimals.unwrap_or(4),
+        })
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn apply(&self, famount: f64) -> f64 {
+        let ffactor = 10f64.powi(self.decimals as i32);
+        //Remove the Noise of the Multiplication before Rounding down or up
+        let fscaled = (famount * ffactor * 10000.0).round() / 10000.0;
+
+        match self.mode {
+            RoundingMode::Nearest => fscaled.round() / ffactor,
+            RoundingMode::Down => fscaled.floor() / ffactor,
+            RoundingMode::Up => fscaled.ceil() / ffactor,
+        }
+    }
+}
+
+//==============================================================================
+// Structure ExchangeTable Implementation
+
+impl ExchangeTable {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> ExchangeTable {
+        ExchangeTable::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Imports the Exchange Rates with the Columns `date,from,to,rate,spread`
+    pub fn import_rates_str(&mut self, srates_csv: &str) -> Result<u32, RateImportError> {
+        let mut rdr = ReaderBuilder::new()
+            .trim(Trim::All)
+            .from_reader(srates_csv.as_bytes());
+        let mut verrlines: Vec<u32> = Vec::new();
+        let mut serr = String::new();
+        let mut icount = 0;
+
+        for (icsvline, result) in (2..).zip(rdr.deserialize::<ExchangeRate>()) {
+            match result {
+                Ok(mut rate) => match parse_date_days(&rate.date) {
+                    Some(iday) if rate.rate > 0.0 => {
+                        rate.iday = iday;
+                        rate.from = rate.from.to_uppercase();
+                        rate.to = rate.to.to_uppercase();
+
+                        self.vrates.push(rate);
+
+                        icount += 1;
+                    }
+                    _ => {
+                        serr.push_str(&format!(
+                            "Rate Error: '{} / {}' is invalid; ",
+                            &rate.date, rate.rate
+                        ));
+                        verrlines.push(icsvline);
+                    }
+                },
+                Err(e) => {
+                    serr.push_str(&format!("Parse Error: '{:?}'; ", e));
+                    verrlines.push(icsvline);
+                }
+            } //match result
+        } //for (icsvline, result) in (2..).zip(rdr.deserialize::<ExchangeRate>())
+
+        if verrlines.is_empty() {
+            Ok(icount)
+        } else {
+            Err(RateImportError {
+                ok_count: icount,
+                lines: verrlines,
+                message: serr,
+                code: 1,
+            })
+        }
+    }
+
+    pub fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = rounding;
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the Rate which is effective at the Time `inow` in Seconds since the Unix Epoch.
+    /// Without direct Rate the inverse Rate is used
+    pub fn find_rate(&self, sfrom: &str, sto: &str, inow: u64) -> Option<(f64, f64)> {
+        let itoday = (inow / 86400) as i64;
+        let effective = |sfrom: &str, sto: &str| {
+            self.vrates
+                .iter()
+                .filter(|rate| rate.from == sfrom && rate.to == sto && rate.iday <= itoday)
+                .max_by_key(|rate| rate.iday)
+        };
+
+        match effective(sfrom, sto) {
+            Some(rate) => Some((rate.rate, rate.spread.unwrap_or(0.0))),
+            None => effective(sto, sfrom).map(|rate| (1.0 / rate.rate, rate.spread.unwrap_or(0.0))),
+        }
+    }
+
+    /// Converts the Amount and keeps the Spread. The converted Amount is rounded
+    pub fn convert(&self, sfrom: &str, sto: &str, famount: f64, inow: u64) -> Option<Conversion> {
+        self.find_rate(sfrom, sto, inow)
+            .map(|(frate, fspread)| Conversion {
+                rate: frate,
+                spread: fspread,
+                converted: self
+                    .rounding
+                    .apply(famount * frate * (1.0 - fspread / 100.0)),
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vrates.is_empty()
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Converts a Date `YYYY-MM-DD` into the Number of Days since the Unix Epoch
+pub fn parse_date_days(sdate: &str) -> Option<i64> {
+    let vparts: Vec<&str> = sdate.trim().split('-').collect();
+
+    if vparts.len() != 3 {
+        return None;
+    }
+
+    let iyear = vparts[0].parse::<i64>().ok()?;
+    let imonth = vparts[1].parse::<i64>().ok()?;
+    let iday = vparts[2].parse::<i64>().ok()?;
+
+    if !(1..=12).contains(&imonth) || !(1..=31).contains(&iday) {
+        return None;
+    }
+
+    //Days from the Civil Calendar with the Year starting in March
+    let iy = if imonth <= 2 { iyear - 1 } else { iyear };
+    let iera = iy.div_euclid(400);
+    let iyoe = iy - iera * 400;
+    let imp = (imonth + 9) % 12;
+    let idoy = (153 * imp + 2) / 5 + iday - 1;
+    let idoe = iyoe * 365 + iyoe / 4 - iyoe / 100 + idoy;
+
+    Some(iera * 146097 + idoe - 719468)
+}
@@ -0,0 +1,199 @@
+use csv::{ReaderBuilder, Trim};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+//==============================================================================
+// Structure FeeRule Declaration
+
+/// A Fee charged on a Movement Type.
+/// The Fee is the `flat` Amount plus `percent` of the Movement Amount
+/// limited by `min` and `max`.
+/// A Rule with `tier` applies only to the Clients of this Tier
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeeRule {
+    #[serde(rename = "type")]
+    pub tx_type: String,
+    #[serde(default)]
+    pub tier: Option<String>,
+    #[serde(default)]
+    pub flat: Option<f64>,
+    #[serde(default)]
+    pub percent: Option<f64>,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+//==============================================================================
+// Structure ClientTier Declaration
+
+#[derive(Debug, Deserialize)]
+struct ClientTier {
+    client: u16,
+    tier: String,
+}
+
+//==============================================================================
+// Structure FeeImportError Declaration
+
+#[derive(Debug)]
+pub struct FeeImportError {
+    pub ok_count: u32,
+    pub lines: Vec<u32>,
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure FeeSchedule Declaration
+
+/// The Fee Rules and the Tiers of the Clients
+#[derive(Debug, Clone, Default)]
+pub struct FeeSchedule {
+    pub vrules: Vec<FeeRule>,
+    pub lsttiers: HashMap<u16, String>,
+}
+
+//==============================================================================
+// Structure FeeRule Implementation
+
+impl FeeRule {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn calculate(&self, famount: f64) -> f64 {
+        let mut ffee = self.flat.unwrap_or(0.0) + famount * self.percent.unwrap_or(0.0) / 100.0;
+
+        if let Some(fmin) = self.min {
+            ffee = ffee.max(fmin);
+        }
+
+        if let Some(fmax) = self.max {
+            ffee = ffee.min(fmax);
+        }
+
+        (ffee.max(0.0) * 10000.0).round() / 10000.0
+    }
+}
+
+//==============================================================================
+// Structure FeeSchedule Implementation
+
+impl FeeSchedule {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> FeeSchedule {
+        FeeSchedule::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Imports the Fee Rules with the Columns `type,tier,flat,percent,min,max`
+    pub fn import_rules_str(&mut self, srules_csv: &str) -> Result<u32, FeeImportError> {
+        let mut rdr = ReaderBuilder::new()
+            .trim(Trim::All)
+            .from_reader(srules_csv.as_bytes());
+        let mut verrlines: Vec<u32> = Vec::new();
+        let mut serr = String::new();
+        let mut icount = 0;
+
+        for (icsvline, result) in (2..).zip(rdr.deserialize::<FeeRule>()) {
+            match result {
+                Ok(mut rule) => {
+                    rule.tx_type = rule.tx_type.to_lowercase();
+                    rule.tier = rule.tier.filter(|stier| !stier.is_empty());
+
+                    self.vrules.push(rule);
+
+                    icount += 1;
+                }
+                Err(e) => {
+                    serr.push_str(&format!("Parse Error: '{:?}'; ", e));
+                    verrlines.push(icsvline);
+                }
+            } //match result
+        } //for (icsvline, result) in (2..).zip(rdr.deserialize::<FeeRule>())
+
+        if verrlines.is_empty() {
+            Ok(icount)
+        } else {
+            Err(FeeImportError {
+                ok_count: icount,
+                lines: verrlines,
+                message: serr,
+                code: 1,
+            })
+        }
+    }
+
+    /// Imports the Tiers of the Clients with the Columns `client,tier`
+    pub fn import_tiers_str(&mut self, stiers_csv: &str) -> Result<u32, FeeImportError> {
+        let mut rdr = ReaderBuilder::new()
+            .trim(Trim::All)
+            .from_reader(stiers_csv.as_bytes());
+        let mut verrlines: Vec<u32> = Vec::new();
+        let mut serr = String::new();
+        let mut icount = 0;
+
+        for (icsvline, result) in (2..).zip(rdr.deserialize::<ClientTier>()) {
+            match result {
+                Ok(clienttier) => {
+                    self.lsttiers.insert(clienttier.client, clienttier.tier);
+
+                    icount += 1;
+                }
+                Err(e) => {
+                    serr.push_str(&format!("Parse Error: '{:?}'; ", e));
+                    verrlines.push(icsvline);
+                }
+            } //match result
+        } //for (icsvline, result) in (2..).zip(rdr.deserialize::<ClientTier>())
+
+        if verrlines.is_empty() {
+            Ok(icount)
+        } else {
+            Err(FeeImportError {
+                ok_count: icount,
+                lines: verrlines,
+                message: serr,
+                code: 1,
+            })
+        }
+    }
+
+    pub fn set_client_tier(&mut self, client_id: u16, stier: &str) {
+        self.lsttiers.insert(client_id, String::from(stier));
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the Fee for the Movement Type of the Client.
+    /// A Rule for the Tier of the Client takes Precedence over a Rule without Tier
+    pub fn calculate(&self, tx_type: &str, client_id: u16, famount: f64) -> f64 {
+        let otier = self.lsttiers.get(&client_id);
+        let otierrule = self.vrules.iter().find(|rule| {
+            rule.tx_type == tx_type && rule.tier.is_some() && rule.tier.as_ref() == otier
+        });
+
+        match otierrule.or_else(|| {
+            self.vrules
+                .iter()
+                .find(|rule| rule.tx_type == tx_type && rule.tier.is_none())
+        }) {
+            Some(rule) => rule.calculate(famount),
+            None => 0.0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vrules.is_empty()
+    }
+}
@@ -0,0 +1,340 @@
+use serde::{Deserialize, Serialize};
+
+use csv::WriterBuilder;
+use std::collections::BTreeMap;
+
+use super::account::CurrencyBalance;
+use super::ledger::LedgerEntry;
+use super::round_amount;
+
+//==============================================================================
+// Enum BookAccount Declaration
+
+/// The Accounts of the Books on which the Postings are booked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum BookAccount {
+    /// The `available` Funds of a Client
+    ClientAvailable(u16),
+    /// The `held` Funds of a Client
+    ClientHeld(u16),
+    /// The Funds moved in and out of the Books by Deposits, Withdrawals and Transfers
+    Settlement,
+    /// The Funds lost to Chargebacks
+    ChargebackLoss,
+    /// The Fees charged to the Clients
+    FeeIncome,
+}
+
+//==============================================================================
+// Structure Posting Declaration
+
+/// One Side of a Journal Entry. The Postings of one Entry sum up to zero
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Posting {
+    pub entry: u64,
+    pub tx: u32,
+    pub tx_type: String,
+    pub account: BookAccount,
+    #[serde(default)]
+    pub currency: Option<String>,
+    pub amount: f64,
+}
+
+//==============================================================================
+// Structure Journal Declaration
+
+/// The balanced Postings behind the Balances of all Accounts
+#[derive(Debug, Default)]
+pub struct Journal {
+    pub vpostings: Vec<Posting>,
+    ientries: u64,
+}
+
+//==============================================================================
+// Enum BookAccount Implementation
+
+impl BookAccount {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_name(&self) -> String {
+        match self {
+            BookAccount::ClientAvailable(client) => format!("client_available:{}", client),
+            BookAccount::ClientHeld(client) => format!("client_held:{}", client),
+            BookAccount::Settlement => String::from("settlement"),
+            BookAccount::ChargebackLoss => String::from("chargeback_loss"),
+            BookAccount::FeeIncome => String::from("fee_income"),
+        }
+    }
+}
+
+//==============================================================================
+// Structure Journal Implementation
+
+impl Journal {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Journal {
+        Journal::default()
+    }
+
+    /// Restores the Journal from its saved Postings
+    pub fn from_postings(vpostings: Vec<Posting>) -> Journal {
+        Journal {
+            ientries: vpostings
+                .iter()
+                .map(|posting| posting.entry)
+                .max()
+                .unwrap_or(0),
+            vpostings,
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Books the Balance Changes of the Ledger Entry against the Settlement Account.
+    /// Chargebacks are booked against the Chargeback Loss and Fees against the Fee Income
+    pub fn post_entry(&mut self, entry: &LedgerEntry) {
+        let counter = match entry.tx_type.as_str() {
+            "chargeback" => BookAccount::ChargebackLoss,
+            "fee" => BookAccount::FeeIncome,
+            _ => BookAccount::Settlement,
+        };
+
+        self.post(
+            entry.client,
+            entry.tx,
+            &entry.tx_type,
+            entry.currency.as_deref(),
+            entry.available_delta,
+            entry.held_delta,
+            counter,
+        );
+    }
+
+    /// Books Balances which are not explained by the Journal as Opening Entry
+    pub fn post_opening(
+        &mut self,
+        client_id: u16,
+        ocurrency: Option<&str>,
+        balance: &CurrencyBalance,
+    ) {
+        self.post(
+            client_id,
+            0,
+            "opening",
+            ocurrency,
+            balance.available,
+            balance.held,
+            BookAccount::Settlement,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn post(
+        &mut self,
+        client_id: u16,
+        itx: u32,
+        stx_type: &str,
+        ocurrency: Option<&str>,
+        favailable: f64,
+        fheld: f64,
+        counter: BookAccount,
+    ) {
+        let favailable = round_amount(favailable);
+        let fheld = round_amount(fheld);
+        let vsides = [
+            (BookAccount::ClientAvailable(client_id), favailable),
+            (BookAccount::ClientHeld(client_id), fheld),
+            (counter, round_amount(-(favailable + fheld))),
+        ];
+
+        if vsides.iter().all(|(_, famount)| *famount == 0.0) {
+            return;
+        }
+
+        self.ientries += 1;
+
+        for (account, famount) in vsides.iter().filter(|(_, famount)| *famount != 0.0) {
+            self.vpostings.push(Posting {
+                entry: self.ientries,
+                tx: itx,
+                tx_type: String::from(stx_type),
+                account: *account,
+                currency: ocurrency.map(String::from),
+                amount: *famount,
+            });
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the Balances of the Client in the Currency derived from the Postings
+    pub fn derive_balance(&self, client_id: u16, ocurrency: Option<&str>) -> CurrencyBalance {
+        let mut balance = CurrencyBalance::default();
+
+        for posting in self
+            .vpostings
+            .iter()
+            .filter(|posting| posting.currency.as_deref() == ocurrency)
+        {
+            match posting.account {
+                BookAccount::ClientAvailable(client) if client == client_id => {
+                    balance.available += posting.amount
+                }
+                BookAccount::ClientHeld(client) if client == client_id => {
+                    balance.held += posting.amount
+                }
+                _ => {}
+            }
+        }
+
+        balance.available = round_amount(balance.available);
+        balance.held = round_amount(balance.held);
+        balance.total = round_amount(balance.available + balance.held);
+
+        balance
+    }
+
+    /// Returns the Balance of each Book Account per Currency
+    pub fn get_trial_balance(&self) -> BTreeMap<(Option<String>, BookAccount), f64> {
+        let mut lstbalances: BTreeMap<(Option<String>, BookAccount), f64> = BTreeMap::new();
+
+        for posting in self.vpostings.iter() {
+            *lstbalances
+                .entry((posting.currency.clone(), posting.account))
+                .or_default() += posting.amount;
+        }
+
+        for fbalance in lstbalances.values_mut() {
+            *fbalance = round_amount(*fbalance);
+        }
+
+        lstbalances
+    }
+
+    /// Checks that the Book Accounts of each Currency sum up to zero
+    pub fn is_balanced(&self) -> bool {
+        let mut lstsums: BTreeMap<Option<String>, f64> = BTreeMap::new();
+
+        for ((ocurrency, _), fbalance) in self.get_trial_balance().into_iter() {
+            *lstsums.entry(ocurrency).or_default() += fbalance;
+        }
+
+        lstsums.values().all(|fsum| round_amount(*fsum) == 0.0)
+    }
+
+    /// Exports the Postings with the Columns `entry,tx,type,account,currency,amount`
+    #[allow(unused_variables)]
+    pub fn export_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        if let Err(e) = wtr.write_record(["entry", "tx", "type", "account", "currency", "amount"]) {
+            if !bquiet {
+                eprintln!("Journal CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for posting in self.vpostings.iter() {
+            if let Err(e) = wtr.write_record([
+                posting.entry.to_string(),
+                posting.tx.to_string(),
+                posting.tx_type.clone(),
+                posting.account.get_name(),
+                posting.currency.clone().unwrap_or_default(),
+                format!("{:?}", posting.amount),
+            ]) {
+                if !bquiet {
+                    eprintln!("Journal CSV Export Error: '{:?}'", e)
+                }
+            }
+        } //for posting in self.vpostings.iter()
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Journal CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+
+    /// Exports the Trial Balance with the Columns `currency,account,balance`
+    /// and one `total` Row per Currency which is zero when the Books are balanced
+    #[allow(unused_variables)]
+    pub fn export_trial_balance_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+        let mut vrows: Vec<[String; 3]> = Vec::new();
+        let mut ocurrent: Option<Option<String>> = None;
+        let mut fsum = 0.0;
+
+        for ((ocurrency, account), fbalance) in self.get_trial_balance().into_iter() {
+            if let Some(oprevious) = ocurrent.take().filter(|oprevious| *oprevious != ocurrency) {
+                vrows.push(total_row(oprevious, fsum));
+                fsum = 0.0;
+            }
+
+            fsum += fbalance;
+
+            vrows.push([
+                ocurrency.clone().unwrap_or_default(),
+                account.get_name(),
+                format!("{:?}", fbalance),
+            ]);
+
+            ocurrent = Some(ocurrency);
+        }
+
+        if let Some(oprevious) = ocurrent {
+            vrows.push(total_row(oprevious, fsum));
+        }
+
+        if let Err(e) = wtr.write_record(["currency", "account", "balance"]) {
+            if !bquiet {
+                eprintln!("Trial Balance CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for row in vrows.iter() {
+            if let Err(e) = wtr.write_record(row) {
+                if !bquiet {
+                    eprintln!("Trial Balance CSV Export Error: '{:?}'", e)
+                }
+            }
+        }
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Trial Balance CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+fn total_row(ocurrency: Option<String>, fsum: f64) -> [String; 3] {
+    [
+        ocurrency.unwrap_or_default(),
+        String::from("total"),
+        format!("{:?}", round_amount(fsum)),
+    ]
+}
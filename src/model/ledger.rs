@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+
+use csv::WriterBuilder;
+use std::collections::{BTreeMap, HashMap};
+
+use super::account::{Account, CurrencyBalance};
+use super::journal::Journal;
+use super::round_amount;
+
+//==============================================================================
+// Structure LedgerEntry Declaration
+
+/// A Change of the Balances of an Account in one Currency.
+/// The `sequence` counts the Entries of each Account starting at `1`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LedgerEntry {
+    pub client: u16,
+    pub sequence: u64,
+    pub tx: u32,
+    #[serde(rename = "type")]
+    pub tx_type: String,
+    #[serde(default)]
+    pub currency: Option<String>,
+    pub available_delta: f64,
+    pub held_delta: f64,
+    pub total_delta: f64,
+    pub available: f64,
+    pub held: f64,
+    pub total: f64,
+    /// The Time in Seconds since the Unix Epoch when the Balances changed
+    #[serde(default)]
+    pub at: u64,
+}
+
+//==============================================================================
+// Structure Ledger Declaration
+
+/// The Ledger Entries of all Accounts.
+/// Each Entry is also booked in the double-entry `journal`
+#[derive(Debug, Default)]
+pub struct Ledger {
+    pub lstentries: HashMap<u16, Vec<LedgerEntry>>,
+    pub journal: Journal,
+    inow: u64,
+}
+
+//==============================================================================
+// Structure Ledger Implementation
+
+impl Ledger {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> Ledger {
+        Ledger::default()
+    }
+
+    /// Rebuilds the Ledger from the Entries of all Accounts
+    pub fn from_entries(ventries: Vec<LedgerEntry>) -> Ledger {
+        let mut ledger = Ledger::new();
+
+        for entry in ventries.into_iter() {
+            ledger
+                .lstentries
+                .entry(entry.client)
+                .or_default()
+                .push(entry);
+        }
+
+        for ventries in ledger.lstentries.values_mut() {
+            ventries.sort_by_key(|entry| entry.sequence);
+        }
+
+        ledger
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Sets the Time in Seconds since the Unix Epoch for the next Entries
+    pub fn set_time(&mut self, inow: u64) {
+        self.inow = inow;
+    }
+
+    /// Appends one Entry per Currency whose Balances changed since `vbefore`.
+    /// `ocurrency` is the Currency the Account is switched to
+    pub fn record(
+        &mut self,
+        acc: &Account,
+        ocurrency: Option<&str>,
+        vbefore: &BTreeMap<String, CurrencyBalance>,
+        itx: u32,
+        stx_type: &str,
+    ) {
+        let empty = CurrencyBalance::default();
+
+        for (scurrency, after) in acc.get_balances(ocurrency).iter() {
+            let before = vbefore.get(scurrency).unwrap_or(&empty);
+            let favailable = round_amount(after.available - before.available);
+            let fheld = round_amount(after.held - before.held);
+            let ftotal = round_amount(after.total - before.total);
+
+            if favailable == 0.0 && fheld == 0.0 && ftotal == 0.0 {
+                continue;
+            }
+
+            let ventries = self.lstentries.entry(acc.client).or_default();
+            let entry = LedgerEntry {
+                client: acc.client,
+                sequence: ventries.len() as u64 + 1,
+                tx: itx,
+                tx_type: String::from(stx_type),
+                currency: if scurrency.is_empty() {
+                    None
+                } else {
+                    Some(scurrency.clone())
+                },
+                available_delta: favailable,
+                held_delta: fheld,
+                total_delta: ftotal,
+                available: after.available,
+                held: after.held,
+                total: after.total,
+                at: self.inow,
+            };
+
+            self.journal.post_entry(&entry);
+
+            ventries.push(entry);
+        } //for (scurrency, after) in acc.get_balances(ocurrency).iter()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_entries(&self, client_id: u16) -> &[LedgerEntry] {
+        match self.lstentries.get(&client_id) {
+            Some(ventries) => ventries.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// Returns the Entries of all Accounts ordered by Client and Sequence
+    pub fn get_all_entries(&self) -> Vec<&LedgerEntry> {
+        let mut ventries: Vec<&LedgerEntry> = self.lstentries.values().flatten().collect();
+
+        ventries.sort_by_key(|entry| (entry.client, entry.sequence));
+
+        ventries
+    }
+
+    /// Exports the Entries of the Client `oclient` or of all Clients with the Columns
+    /// `client,sequence,tx,type,currency,available_delta,held_delta,total_delta,available,held,total,at`
+    #[allow(unused_variables)]
+    pub fn export_csv(&self, oclient: Option<u16>, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().has_headers(false).from_writer(vec![]);
+
+        if let Err(e) = wtr.write_record([
+            "client",
+            "sequence",
+            "tx",
+            "type",
+            "currency",
+            "available_delta",
+            "held_delta",
+            "total_delta",
+            "available",
+            "held",
+            "total",
+            "at",
+        ]) {
+            if !bquiet {
+                eprintln!("Ledger CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for entry in self
+            .get_all_entries()
+            .into_iter()
+            .filter(|entry| oclient.is_none_or(|iclient| entry.client == iclient))
+        {
+            if let Err(e) = wtr.serialize(entry) {
+                if !bquiet {
+                    eprintln!("Ledger CSV Export Error: '{:?}'", e)
+                }
+            }
+        }
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Ledger CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+}
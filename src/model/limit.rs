@@ -0,0 +1,240 @@
+use csv::{ReaderBuilder, Trim};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+
+//==============================================================================
+// Structure AccountLimits Declaration
+
+/// The Limits configured for the Account of a Client.
+/// Limits which are not set are not enforced
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AccountLimits {
+    pub client: u16,
+    /// The Amount by which the `available` Funds may become negative
+    #[serde(default)]
+    pub overdraft: Option<f64>,
+    /// The Sum of Withdrawals and Transfers allowed within the Withdrawal Window
+    #[serde(default)]
+    pub withdrawal_limit: Option<f64>,
+    /// The highest Amount of a single Movement
+    #[serde(default)]
+    pub max_transaction: Option<f64>,
+    /// The highest `total` Balance reachable by Deposits and Transfers
+    #[serde(default)]
+    pub max_balance: Option<f64>,
+}
+
+//==============================================================================
+// Structure LimitImportError Declaration
+
+#[derive(Debug)]
+pub struct LimitImportError {
+    pub ok_count: u32,
+    pub lines: Vec<u32>,
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure LimitSchedule Declaration
+
+/// The Limits of the Clients and the rolling Window in Seconds
+/// in which the Withdrawal Limits apply
+#[derive(Debug, Clone)]
+pub struct LimitSchedule {
+    pub lstlimits: HashMap<u16, AccountLimits>,
+    pub window: u64,
+    /// Movements beyond the Withdrawal or Transaction Limit are held for a Review
+    /// instead of being refused
+    pub review: bool,
+}
+
+//==============================================================================
+// Structure WithdrawalWindow Declaration
+
+/// The processed Withdrawals and Transfers of each Client in the Order of their Processing.
+/// Entries which left the rolling Window are dropped when the Client is checked again
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawalWindow {
+    lstclients: HashMap<u16, VecDeque<(u64, u32)>>,
+}
+
+//==============================================================================
+// Structure AccountLimits Implementation
+
+impl AccountLimits {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_overdraft(&self) -> f64 {
+        self.overdraft.unwrap_or(0.0).max(0.0)
+    }
+
+    pub fn exceeds_transaction(&self, famount: f64) -> bool {
+        self.max_transaction.is_some_and(|fmax| famount > fmax)
+    }
+
+    pub fn exceeds_balance(&self, ftotal: f64) -> bool {
+        self.max_balance
+            .is_some_and(|fmax| (ftotal * 10000.0).round() / 10000.0 > fmax)
+    }
+
+    pub fn exceeds_withdrawals(&self, fwithdrawn: f64) -> bool {
+        self.withdrawal_limit
+            .is_some_and(|fmax| (fwithdrawn * 10000.0).round() / 10000.0 > fmax)
+    }
+}
+
+//==============================================================================
+// Structure LimitSchedule Implementation
+
+impl Default for LimitSchedule {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
+
+    fn default() -> Self {
+        LimitSchedule::new()
+    }
+}
+
+impl LimitSchedule {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Creates an empty Schedule with a daily Withdrawal Window
+    pub fn new() -> LimitSchedule {
+        LimitSchedule {
+            lstlimits: HashMap::new(),
+            window: 86400,
+            review: false,
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Imports the Limits with the Columns
+    /// `client,overdraft,withdrawal_limit,max_transaction,max_balance`
+    pub fn import_str(&mut self, slimits_csv: &str) -> Result<u32, LimitImportError> {
+        let mut rdr = ReaderBuilder::new()
+            .trim(Trim::All)
+            .from_reader(slimits_csv.as_bytes());
+        let mut verrlines: Vec<u32> = Vec::new();
+        let mut serr = String::new();
+        let mut icount = 0;
+
+        for (icsvline, result) in (2..).zip(rdr.deserialize::<AccountLimits>()) {
+            match result {
+                Ok(limits) => {
+                    self.lstlimits.insert(limits.client, limits);
+
+                    icount += 1;
+                }
+                Err(e) => {
+                    serr.push_str(&format!("Parse Error: '{:?}'; ", e));
+                    verrlines.push(icsvline);
+                }
+            } //match result
+        } //for (icsvline, result) in (2..).zip(rdr.deserialize::<AccountLimits>())
+
+        if verrlines.is_empty() {
+            Ok(icount)
+        } else {
+            Err(LimitImportError {
+                ok_count: icount,
+                lines: verrlines,
+                message: serr,
+                code: 1,
+            })
+        }
+    }
+
+    pub fn set_limits(&mut self, limits: AccountLimits) {
+        self.lstlimits.insert(limits.client, limits);
+    }
+
+    /// Sets the rolling Window in Seconds in which the Withdrawal Limits apply
+    pub fn set_window(&mut self, iseconds: u64) {
+        self.window = iseconds;
+    }
+
+    /// Holds Movements beyond the Limits for a Review instead of refusing them
+    pub fn set_review(&mut self, breview: bool) {
+        self.review = breview;
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn get_limits(&self, client_id: u16) -> Option<&AccountLimits> {
+        self.lstlimits.get(&client_id)
+    }
+
+    pub fn get_overdraft(&self, client_id: u16) -> f64 {
+        match self.lstlimits.get(&client_id) {
+            Some(limits) => limits.get_overdraft(),
+            None => 0.0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lstlimits.is_empty()
+    }
+}
+
+//==============================================================================
+// Structure WithdrawalWindow Implementation
+
+impl WithdrawalWindow {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> WithdrawalWindow {
+        WithdrawalWindow::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Records a Withdrawal or Transfer processed at `iprocessed`.
+    /// A Transaction is recorded only once
+    pub fn record(&mut self, client_id: u16, transaction_id: u32, iprocessed: u64) {
+        let vwindow = self.lstclients.entry(client_id).or_default();
+
+        //A recorded Transaction is found among the Entries of its Processing Time
+        if !vwindow
+            .iter()
+            .rev()
+            .take_while(|(iat, _)| *iat >= iprocessed)
+            .any(|(_, itxid)| *itxid == transaction_id)
+        {
+            vwindow.push_back((iprocessed, transaction_id));
+        }
+    }
+
+    /// Drops the Entries of the Client processed at or before `istart`
+    /// and returns the Transaction IDs still within the Window
+    pub fn get_recent(&mut self, client_id: u16, istart: u64) -> Vec<u32> {
+        match self.lstclients.get_mut(&client_id) {
+            Some(vwindow) => {
+                while vwindow.front().is_some_and(|(iat, _)| *iat <= istart) {
+                    vwindow.pop_front();
+                }
+
+                vwindow
+                    .iter()
+                    .filter(|(iat, _)| *iat > istart)
+                    .map(|(_, itxid)| *itxid)
+                    .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+}
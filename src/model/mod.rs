@@ -1,7 +1,23 @@
 pub mod account;
+pub mod audit;
+pub mod dispute;
+pub mod event;
+pub mod exchange;
+pub mod fee;
+pub mod journal;
+pub mod ledger;
+pub mod limit;
+pub mod policy;
+pub mod reconcile;
+pub mod review;
+pub mod rule;
+pub mod statement;
+pub mod summary;
 pub mod transaction;
 
 use crate::model::account::Account;
+use crate::model::event::{AccountEvent, RejectReason};
+use crate::model::policy::{ClearingPolicy, ExpiryAction, NegativeBalancePolicy, ProcessingPolicy};
 use crate::model::transaction::{Movement, Transaction};
 
 //==============================================================================
@@ -12,13 +28,19 @@ impl Account {
      * Administration Methods
      */
 
+    /// Applies the Movement on the Account according to the Processing Policy.
+    /// `inow` is the current Time in Seconds since the Unix Epoch
     pub fn process_movement(
         &mut self,
         mvrecord: &mut Movement,
         mut otxrecord: Option<&mut Transaction>,
+        policy: &ProcessingPolicy,
+        inow: u64,
         bdebug: bool,
         bquiet: bool,
-    ) {
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
         if !self.locked {
             match &mut otxrecord {
                 Some(txrec) => {
@@ -37,11 +59,62 @@ impl Account {
                                     );
                                 }
 
-                                self.available += txrec.amount;
-                                self.total += txrec.amount;
+                                let olimits = policy.limits.get_limits(self.client);
+
+                                if olimits.is_some_and(|l| l.exceeds_transaction(txrec.amount)) {
+                                    return self.reject_movement(
+                                        txrec,
+                                        RejectReason::TransactionLimitExceeded,
+                                        bquiet,
+                                    );
+                                }
 
-                                //Mark Transaction as accepted and processed
-                                txrec.status = 1;
+                                if olimits
+                                    .is_some_and(|l| l.exceeds_balance(self.total + txrec.amount))
+                                {
+                                    return self.reject_movement(
+                                        txrec,
+                                        RejectReason::BalanceLimitExceeded,
+                                        bquiet,
+                                    );
+                                }
+
+                                if policy.clearing.is_immediate() {
+                                    self.available += txrec.amount;
+                                    self.total += txrec.amount;
+
+                                    //Mark Transaction as accepted and processed
+                                    txrec.status = 1;
+                                } else
+                                //Funds stay on hold until they are cleared
+                                {
+                                    self.held += txrec.amount;
+                                    self.total += txrec.amount;
+
+                                    self.held = (self.held * 10000.0).round() / 10000.0;
+
+                                    //Mark Transaction as accepted and clearing
+                                    txrec.status = 2;
+                                    txrec.clearing = true;
+
+                                    match policy.clearing {
+                                        ClearingPolicy::Movements(icount) => {
+                                            txrec.clears_after = Some(icount)
+                                        }
+                                        ClearingPolicy::Window(iseconds) => {
+                                            txrec.clears_at = Some(inow + iseconds)
+                                        }
+                                        _ => {}
+                                    }
+                                } //if policy.clearing.is_immediate()
+
+                                txrec.booked_at = Some(inow);
+
+                                vevents.push(AccountEvent::DepositAccepted {
+                                    client: self.client,
+                                    tx: txrec.tx,
+                                    amount: txrec.amount,
+                                });
 
                                 if bdebug && !bquiet {
                                     eprintln!(
@@ -61,7 +134,27 @@ impl Account {
                                     );
                                 }
 
-                                if txrec.amount <= self.available {
+                                if policy
+                                    .limits
+                                    .get_limits(self.client)
+                                    .is_some_and(|l| l.exceeds_transaction(txrec.amount))
+                                {
+                                    return self.reject_movement(
+                                        txrec,
+                                        RejectReason::TransactionLimitExceeded,
+                                        bquiet,
+                                    );
+                                }
+
+                                //The Fee must be covered together with the Withdrawal
+                                let ffee =
+                                    policy
+                                        .fees
+                                        .calculate("withdrawal", self.client, txrec.amount);
+
+                                if ((txrec.amount + ffee) * 10000.0).round() / 10000.0
+                                    <= self.available + policy.limits.get_overdraft(self.client)
+                                {
                                     self.available -= txrec.amount;
                                     self.total -= txrec.amount;
 
@@ -70,12 +163,21 @@ impl Account {
 
                                     //Mark Transaction as accepted and processed
                                     txrec.status = 1;
+                                    txrec.processed_at = Some(inow);
+                                    txrec.booked_at = Some(inow);
                                 } else
                                 //Unsufficiant Funds available
                                 {
                                     //Mark Transaction as invalid
                                     txrec.status = -1;
 
+                                    vevents.push(AccountEvent::WithdrawalRejected {
+                                        client: self.client,
+                                        tx: txrec.tx,
+                                        amount: txrec.amount,
+                                        reason: RejectReason::InsufficientFunds,
+                                    });
+
                                     if !bquiet {
                                         eprintln!("Movement Processing Error: Amount '-{} / {}' is not available.", txrec.amount, self.available);
                                     }
@@ -88,122 +190,70 @@ impl Account {
                                     );
                                 }
                             }
-                            "dispute" => {
+                            "exchange" => {
                                 //------------------------
-                                //Dispute Request
+                                //Currency Exchange Transaction
 
-                                if txrec.status == 1 {
-                                    match txrec.tx_type.as_str() {
-                                        "deposit" => {
-                                            self.available -= txrec.amount;
-                                            self.held += txrec.amount;
-
-                                            self.available =
-                                                (self.available * 10000.0).round() / 10000.0;
-
-                                            //Mark Transaction as disputed
-                                            txrec.status = -2;
-                                        }
-                                        "withdrawal" => {
-                                            self.held += txrec.amount;
-                                            self.total += txrec.amount;
+                                //The Rate is taken at the Time of the Movement if it is given
+                                let irateat = mvrecord.timestamp.unwrap_or(inow);
 
-                                            //Mark Transaction as disputed
-                                            txrec.status = -2;
-                                        }
-                                        _ => {
-                                            if !bquiet {
-                                                eprintln!("Movement Processing Error: Movement Type '{}' on '{}': disallowed ", &mvrecord.tx_type, &txrec.tx_type);
-                                            }
-                                        }
-                                    } //match txrec.tx_type.as_str()
-                                } else
-                                //Transaction was not processed or rejected
-                                {
-                                    if !bquiet {
-                                        eprintln!("Movement Processing Error: Transaction State '{}': invalid ", &txrec.status);
-                                    }
-                                } //if txrec.status == 1
+                                vevents.append(
+                                    &mut self
+                                        .apply_exchange(txrec, policy, irateat, bdebug, bquiet),
+                                );
                             }
-                            "resolve" => {
+                            "settle" => {
                                 //------------------------
-                                //Resolve Request
-
-                                if txrec.status == -2 {
-                                    match txrec.tx_type.as_str() {
-                                        "deposit" => {
-                                            self.held -= txrec.amount;
-                                            self.available += txrec.amount;
-
-                                            self.held = (self.held * 10000.0).round() / 10000.0;
-
-                                            //Mark Transaction as accepted and processed
-                                            txrec.status = 1;
-                                        }
-                                        "withdrawal" => {
-                                            self.held -= txrec.amount;
-                                            self.total -= txrec.amount;
-
-                                            self.held = (self.held * 10000.0).round() / 10000.0;
-                                            self.total = (self.total * 10000.0).round() / 10000.0;
+                                //Settlement of a clearing Deposit
 
-                                            //Mark Transaction as accepted and processed
-                                            txrec.status = 1;
-                                        }
-                                        _ => {
-                                            if !bquiet {
-                                                eprintln!("Movement Processing Error: Movement Type '{}' on '{}': disallowed ", &mvrecord.tx_type, &txrec.tx_type);
-                                            }
-                                        }
-                                    } //match txrec.tx_type.as_str()
+                                if txrec.tx_type == "deposit" && txrec.clearing {
+                                    vevents.append(&mut self.clear_deposit(txrec, bdebug, bquiet));
                                 } else
-                                //Transaction was not disputed
+                                //Transaction is not clearing
                                 {
                                     if !bquiet {
-                                        eprintln!("Movement Processing Error: Transaction State '{}': not disputed", &txrec.status);
+                                        eprintln!("Movement Processing Error: Transaction (id: '{}'): not clearing", &txrec.tx);
                                     }
-                                } //if txrec.status == -2
+                                } //if txrec.tx_type == "deposit" && txrec.clearing
                             }
-                            "chargeback" => {
+                            "dispute" | "resolve" | "chargeback" => {
                                 //------------------------
-                                //Chargeback Request
-
-                                if txrec.status == -2 {
-                                    match txrec.tx_type.as_str() {
-                                        "deposit" => {
-                                            if txrec.amount <= self.total {
-                                                self.held -= txrec.amount;
-                                                self.total -= txrec.amount;
-
-                                                self.held = (self.held * 10000.0).round() / 10000.0;
-                                                self.total =
-                                                    (self.total * 10000.0).round() / 10000.0;
-
-                                                self.locked = true;
-
-                                                //Mark Transaction as invalid
-                                                txrec.status = -1;
-                                            } else
-                                            //Unsufficiant Funds available
-                                            {
-                                                if !bquiet {
-                                                    eprintln!("Movement Processing Error: Amount '-{} / {}' is not available.", txrec.amount, self.total);
-                                                }
-                                            } //if txrec.amount <= self.total
-                                        }
-                                        _ => {
-                                            if !bquiet {
-                                                eprintln!("Movement Processing Error: Movement Type '{}' on '{}': disallowed ", &mvrecord.tx_type, &txrec.tx_type);
+                                //Dispute, Resolve or Chargeback Request
+
+                                match txrec.tx_type.as_str() {
+                                    _ if !mvrecord.matches_currency(txrec) => {
+                                        vevents.append(
+                                            &mut self.reject_currency(mvrecord, txrec, bquiet),
+                                        );
+                                    }
+                                    "deposit" | "withdrawal" => {
+                                        let mut vdisputeevents = match mvrecord.tx_type.as_str() {
+                                            "dispute" => self.open_dispute(
+                                                txrec,
+                                                mvrecord.amount,
+                                                policy,
+                                                inow,
+                                                bquiet,
+                                            ),
+                                            "resolve" => {
+                                                self.resolve_dispute(txrec, mvrecord.amount, bquiet)
                                             }
+                                            _ => self.apply_chargeback(
+                                                txrec,
+                                                mvrecord.amount,
+                                                policy,
+                                                bquiet,
+                                            ),
+                                        };
+
+                                        vevents.append(&mut vdisputeevents);
+                                    }
+                                    _ => {
+                                        if !bquiet {
+                                            eprintln!("Movement Processing Error: Movement Type '{}' on '{}': disallowed ", &mvrecord.tx_type, &txrec.tx_type);
                                         }
-                                    } //match txrec.tx_type.as_str()
-                                } else
-                                //Transaction was not disputed
-                                {
-                                    if !bquiet {
-                                        eprintln!("Movement Processing Error: Transaction State '{}': not disputed", &txrec.status);
                                     }
-                                } //if txrec.status == -2
+                                } //match txrec.tx_type.as_str()
                             }
                             _ => {
                                 if !bquiet {
@@ -233,12 +283,1153 @@ impl Account {
             } //match & otxrecord
         } else {
             //Account is locked and Movements stay pending
+            if mvrecord.tx_type.as_str() == "withdrawal" {
+                vevents.push(AccountEvent::WithdrawalRejected {
+                    client: self.client,
+                    tx: mvrecord.tx,
+                    amount: mvrecord.amount.unwrap_or(0.0),
+                    reason: RejectReason::AccountLocked,
+                });
+            }
+
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Account (id: '{}') is locked. Movement refused.",
+                    &self.client
+                );
+            }
+        } //if !self.locked
+
+        vevents
+    }
+
+    /// Places the disputed Portion of a "_deposit_" or a "_withdrawal_" on `held`.
+    /// Without Amount the whole remaining Portion is disputed.
+    /// Several partial Disputes may be opened as long as they do not exceed the Transaction Amount.
+    /// When the Funds of a "_deposit_" are not `available` anymore the Negative Balance Policy
+    /// decides whether the Dispute is refused, produces a Debt or holds only the `available` Funds
+    fn open_dispute(
+        &mut self,
+        txrecord: &mut Transaction,
+        oamount: Option<f64>,
+        policy: &ProcessingPolicy,
+        inow: u64,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        if txrecord.status != 1 && txrecord.status != 2 && txrecord.status != -2 {
+            //Transaction was not processed or rejected
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Transaction State '{}': invalid ",
+                    &txrecord.status
+                );
+            }
+
+            return vevents;
+        }
+
+        //Transactions older than the Dispute Window cannot be disputed
+        if let (Some(iwindow), Some(ibooked)) = (policy.disputes.window, txrecord.booked_at) {
+            if inow > ibooked.saturating_add(iwindow) {
+                vevents.push(AccountEvent::DisputeRejected {
+                    client: self.client,
+                    tx: txrecord.tx,
+                    amount: oamount.unwrap_or(txrecord.get_disputable()),
+                    reason: RejectReason::DisputeWindowExpired,
+                });
+
+                if !bquiet {
+                    eprintln!(
+                        "Movement Processing Error: Transaction (id: '{}') booked at '{}' cannot be disputed anymore.",
+                        txrecord.tx, ibooked
+                    );
+                }
+
+                return vevents;
+            }
+        } //if let (Some(iwindow), Some(ibooked)) = (policy.disputes.window, txrecord.booked_at)
+
+        let fdisputable = txrecord.get_disputable();
+        let famount = match read_portion(oamount, fdisputable, bquiet) {
+            Some(famnt) => famnt,
+            None => return vevents,
+        };
+
+        if famount <= 0.0 || famount > fdisputable {
+            vevents.push(AccountEvent::DisputeRejected {
+                client: self.client,
+                tx: txrecord.tx,
+                amount: famount,
+                reason: RejectReason::AmountExceeded,
+            });
+
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Dispute Amount '{} / {}' exceeds the Transaction.",
+                    famount, fdisputable
+                );
+            }
+
+            return vevents;
+        }
+
+        let fdisputed = txrecord.get_disputed();
+        let fprevheld = txrecord.get_held();
+        let mut fheld = famount;
+
+        if txrecord.tx_type == "withdrawal" {
+            self.held += famount;
+            self.total += famount;
+
+            self.total = (self.total * 10000.0).round() / 10000.0;
+        } else if !txrecord.clearing {
+            //Funds of a clearing Deposit are on hold already
+            if famount > self.available {
+                let fcovered = self.available.max(0.0);
+                let fshortfall = ((famount - fcovered) * 10000.0).round() / 10000.0;
+
+                match policy.negative_balance {
+                    NegativeBalancePolicy::Unchecked => {}
+                    NegativeBalancePolicy::Reject => {
+                        vevents.push(AccountEvent::DisputeRejected {
+                            client: self.client,
+                            tx: txrecord.tx,
+                            amount: famount,
+                            reason: RejectReason::InsufficientFunds,
+                        });
+
+                        if !bquiet {
+                            eprintln!(
+                                "Movement Processing Error: Dispute Amount '{} / {}' is not available.",
+                                famount, self.available
+                            );
+                        }
+
+                        return vevents;
+                    }
+                    NegativeBalancePolicy::Debt => {
+                        txrecord.debt = Some(
+                            ((txrecord.debt.unwrap_or(0.0) + fshortfall) * 10000.0).round()
+                                / 10000.0,
+                        );
+
+                        vevents.push(AccountEvent::DebtRecorded {
+                            client: self.client,
+                            tx: txrecord.tx,
+                            amount: fshortfall,
+                        });
+                    }
+                    NegativeBalancePolicy::Cap => {
+                        fheld = (fcovered * 10000.0).round() / 10000.0;
+                    }
+                } //match policy.negative_balance
+            } //if famount > self.available
+
+            self.available -= fheld;
+            self.held += fheld;
+
+            self.available = (self.available * 10000.0).round() / 10000.0;
+        } //if txrecord.tx_type == "withdrawal"
+
+        self.held = (self.held * 10000.0).round() / 10000.0;
+
+        txrecord.disputed = ((fdisputed + famount) * 10000.0).round() / 10000.0;
+        txrecord.set_held(fprevheld + fheld);
+
+        //Mark Transaction as disputed
+        txrecord.status = -2;
+
+        //The Dispute Opening is reported before the Debt
+        vevents.insert(
+            0,
+            AccountEvent::DisputeOpened {
+                client: self.client,
+                tx: txrecord.tx,
+                amount: fheld,
+            },
+        );
+
+        vevents
+    }
+
+    /// Releases the resolved Portion of a Dispute from `held`.
+    /// Without Amount the whole disputed Portion is resolved
+    fn resolve_dispute(
+        &mut self,
+        txrecord: &mut Transaction,
+        oamount: Option<f64>,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+        let (famount, frelease) = match self.read_disputed_portion(txrecord, oamount, bquiet) {
+            Some(portion) => portion,
+            None => return vevents,
+        };
+
+        if txrecord.tx_type == "withdrawal" {
+            self.held -= frelease;
+            self.total -= frelease;
+
+            self.total = (self.total * 10000.0).round() / 10000.0;
+        } else if !txrecord.clearing {
+            self.held -= frelease;
+            self.available += frelease;
+
+            self.available = (self.available * 10000.0).round() / 10000.0;
+        } else {
+            //Funds stay on hold until they are cleared
+        }
+
+        self.held = (self.held * 10000.0).round() / 10000.0;
+
+        self.close_disputed_portion(txrecord, famount, frelease);
+
+        vevents.push(AccountEvent::DisputeResolved {
+            client: self.client,
+            tx: txrecord.tx,
+            amount: frelease,
+        });
+
+        vevents
+    }
+
+    /// Reverses the charged back Portion of a Dispute and locks the Account.
+    /// Without Amount the whole disputed Portion is charged back
+    fn apply_chargeback(
+        &mut self,
+        txrecord: &mut Transaction,
+        oamount: Option<f64>,
+        policy: &ProcessingPolicy,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+        let (famount, fcharge) = match self.read_disputed_portion(txrecord, oamount, bquiet) {
+            Some(portion) => portion,
+            None => return vevents,
+        };
+
+        if txrecord.tx_type == "withdrawal" {
+            //The withdrawn Funds are returned to the Client.
+            //They were already added to `total` by the Dispute
+            self.held -= fcharge;
+            self.available += fcharge;
+
+            self.available = (self.available * 10000.0).round() / 10000.0;
+        } else if fcharge <= self.total || policy.negative_balance == NegativeBalancePolicy::Debt {
+            self.held -= fcharge;
+            self.total -= fcharge;
+
+            self.total = (self.total * 10000.0).round() / 10000.0;
+        } else
+        //Unsufficiant Funds available
+        {
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Amount '-{} / {}' is not available.",
+                    fcharge, self.total
+                );
+            }
+
+            return vevents;
+        } //if txrecord.tx_type == "withdrawal"
+
+        self.held = (self.held * 10000.0).round() / 10000.0;
+        self.locked = true;
+
+        txrecord.charged_back = ((txrecord.charged_back + famount) * 10000.0).round() / 10000.0;
+
+        self.close_disputed_portion(txrecord, famount, fcharge);
+
+        if txrecord.charged_back >= txrecord.amount {
+            //Mark Transaction as invalid
+            txrecord.status = -1;
+            txrecord.clearing = false;
+            txrecord.clears_after = None;
+            txrecord.clears_at = None;
+        }
+
+        vevents.push(AccountEvent::ChargebackApplied {
+            client: self.client,
+            tx: txrecord.tx,
+            amount: fcharge,
+        });
+        vevents.push(AccountEvent::AccountLocked {
+            client: self.client,
+            tx: txrecord.tx,
+        });
+
+        vevents
+    }
+
+    /// Returns the requested Portion of the Dispute and the Funds held for it
+    fn read_disputed_portion(
+        &self,
+        txrecord: &Transaction,
+        oamount: Option<f64>,
+        bquiet: bool,
+    ) -> Option<(f64, f64)> {
+        let fdisputed = txrecord.get_disputed();
+
+        if txrecord.status != -2 || fdisputed <= 0.0 {
+            //Transaction was not disputed
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Transaction State '{}': not disputed",
+                    &txrecord.status
+                );
+            }
+
+            return None;
+        }
+
+        let famount = read_portion(oamount, fdisputed, bquiet)?;
+
+        if famount > fdisputed {
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Amount '{} / {}' exceeds the Dispute.",
+                    famount, fdisputed
+                );
+            }
+
+            return None;
+        }
+
+        let fheld = txrecord.get_held();
+
+        if famount == fdisputed {
+            Some((famount, fheld))
+        } else {
+            //Capped Disputes release the held Funds proportionally
+            Some((
+                famount,
+                (fheld * famount / fdisputed * 10000.0).round() / 10000.0,
+            ))
+        }
+    }
+
+    /// Removes the closed Portion from the Dispute.
+    /// When nothing is disputed anymore the Transaction returns to its previous State
+    fn close_disputed_portion(&self, txrecord: &mut Transaction, famount: f64, fheld: f64) {
+        let fheldleft = txrecord.get_held() - fheld;
+
+        txrecord.disputed = ((txrecord.get_disputed() - famount) * 10000.0).round() / 10000.0;
+        txrecord.set_held(fheldleft);
+
+        if txrecord.disputed <= 0.0 {
+            txrecord.disputed = 0.0;
+            txrecord.held = None;
+
+            if txrecord.charged_back == 0.0 {
+                //The Dispute is closed without Loss
+                txrecord.debt = None;
+            }
+
+            if txrecord.clearing {
+                //Funds stay on hold until they are cleared
+                txrecord.status = 2;
+            } else {
+                //Mark Transaction as accepted and processed
+                txrecord.status = 1;
+            }
+        } //if txrecord.disputed <= 0.0
+    }
+
+    /// Closes the open Dispute of the Transaction after its Deadline has passed.
+    /// The whole disputed Portion is resolved or charged back according to the Dispute Policy
+    pub fn expire_dispute(
+        &mut self,
+        txrecord: &mut Transaction,
+        policy: &ProcessingPolicy,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        txrecord.expires_after = None;
+        txrecord.expires_at = None;
+
+        if txrecord.status != -2 {
+            return vevents;
+        }
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Dispute Expiry '{}': '{:?}' on '{} / {}'",
+                &txrecord.tx, policy.disputes.expiry, self.held, self.total
+            );
+        }
+
+        vevents.push(AccountEvent::DisputeExpired {
+            client: self.client,
+            tx: txrecord.tx,
+            amount: txrecord.get_disputed(),
+        });
+
+        match policy.disputes.expiry {
+            ExpiryAction::Resolve => {
+                vevents.append(&mut self.resolve_dispute(txrecord, None, bquiet));
+            }
+            ExpiryAction::Chargeback => {
+                vevents.append(&mut self.apply_chargeback(txrecord, None, policy, bquiet));
+            }
+        } //match policy.disputes.expiry
+
+        vevents
+    }
+
+    /// Closes the open Dispute of a "_transfer_" after its Deadline has passed.
+    /// The Dispute expires on the Destination Account which holds it as by `Transaction::get_holder()`.
+    /// Funds taken from the Destination by a Chargeback return to this Account
+    /// as in `Account::process_transfer()`
+    pub fn expire_transfer_dispute(
+        &mut self,
+        accdestination: &mut Account,
+        txrecord: &mut Transaction,
+        policy: &ProcessingPolicy,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let ftotal = accdestination.total;
+        let vevents = accdestination.expire_dispute(txrecord, policy, bdebug, bquiet);
+
+        //The charged back Funds return to this Account
+        let fcharge = ((ftotal - accdestination.total) * 10000.0).round() / 10000.0;
+
+        self.available = ((self.available + fcharge) * 10000.0).round() / 10000.0;
+        self.total = ((self.total + fcharge) * 10000.0).round() / 10000.0;
+
+        vevents
+    }
+
+    /// Releases the Funds of a clearing "_deposit_" from `held` to `available`.
+    /// The disputed Portion of a "_deposit_" stays on `held` until the Dispute is resolved
+    pub fn clear_deposit(
+        &mut self,
+        txrecord: &mut Transaction,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        if !txrecord.clearing {
+            return vevents;
+        }
+
+        txrecord.clearing = false;
+        txrecord.clears_after = None;
+        txrecord.clears_at = None;
+
+        //The disputed and charged back Portions stay out of the `available` Funds
+        let frelease =
+            ((txrecord.amount - txrecord.charged_back - txrecord.get_disputed()) * 10000.0).round()
+                / 10000.0;
+
+        if frelease > 0.0 {
+            self.held -= frelease;
+            self.available += frelease;
+
+            self.held = (self.held * 10000.0).round() / 10000.0;
+            self.available = (self.available * 10000.0).round() / 10000.0;
+
+            vevents.push(AccountEvent::DepositCleared {
+                client: self.client,
+                tx: txrecord.tx,
+                amount: frelease,
+            });
+        } //if frelease > 0.0
+
+        if txrecord.status == 2 {
+            //Mark Transaction as accepted and processed
+            txrecord.status = 1;
+        }
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Clearing Processing '{}' (id: '{}'): funds '{} / {}'",
+                &txrecord.tx_type, txrecord.tx, self.available, self.total
+            );
+        }
+
+        vevents
+    }
+
+    /// Applies a "_refund_" of an earlier "_withdrawal_" or the Reversal of an earlier "_deposit_".
+    /// A Refund without Amount returns the whole remaining Portion of the original Transaction.
+    /// The Refunds of a Transaction cannot exceed its Amount
+    pub fn process_refund(
+        &mut self,
+        txrefund: &mut Transaction,
+        otxoriginal: Option<&mut Transaction>,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Movement Processing '{}': '{} / {}' of '{:?}'",
+                &txrefund.tx_type, txrefund.amount, self.available, &txrefund.original
+            );
+        }
+
+        //Mark Transaction as invalid until it is applied
+        txrefund.status = -1;
+
+        if self.locked {
+            vevents.push(AccountEvent::RefundRejected {
+                client: self.client,
+                tx: txrefund.tx,
+                amount: txrefund.amount,
+                reason: RejectReason::AccountLocked,
+            });
+
             if !bquiet {
                 eprintln!(
                     "Movement Processing Error: Account (id: '{}') is locked. Movement refused.",
                     &self.client
                 );
             }
+
+            return vevents;
+        } //if self.locked
+
+        let txoriginal = match otxoriginal {
+            Some(txorg)
+                if txorg.client == self.client
+                    && txorg.status == 1
+                    && (txorg.tx_type == "deposit" || txorg.tx_type == "withdrawal") =>
+            {
+                txorg
+            }
+            _ => {
+                if !bquiet {
+                    eprintln!(
+                        "Movement Processing Error: Original Transaction (id: '{:?}') cannot be refunded. Movement omitted.",
+                        &txrefund.original
+                    );
+                }
+
+                return vevents;
+            }
+        };
+
+        //The Refund is made in the Currency of the original Transaction
+        if txrefund.currency.is_some() && txrefund.currency != txoriginal.currency {
+            vevents.push(AccountEvent::RefundRejected {
+                client: self.client,
+                tx: txrefund.tx,
+                amount: txrefund.amount,
+                reason: RejectReason::CurrencyMismatch,
+            });
+
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Currency '{:?}' does not match '{:?}'.",
+                    &txrefund.currency, &txoriginal.currency
+                );
+            }
+
+            return vevents;
+        } //if txrefund.currency.is_some() && txrefund.currency != txoriginal.currency
+
+        txrefund.currency = txoriginal.currency.clone();
+
+        //Disputed, charged back and refunded Portions cannot be refunded
+        let frefundable = txoriginal.get_disputable();
+
+        if txrefund.amount == 0.0 {
+            txrefund.amount = frefundable;
+        }
+
+        if txrefund.amount <= 0.0 || txrefund.amount > frefundable {
+            vevents.push(AccountEvent::RefundRejected {
+                client: self.client,
+                tx: txrefund.tx,
+                amount: txrefund.amount,
+                reason: RejectReason::AmountExceeded,
+            });
+
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Refund Amount '{} / {}' exceeds the Transaction.",
+                    txrefund.amount, frefundable
+                );
+            }
+
+            return vevents;
+        }
+
+        if txoriginal.tx_type == "withdrawal" {
+            self.available += txrefund.amount;
+            self.total += txrefund.amount;
+        } else if txrefund.amount <= self.available {
+            //The Reversal of a Deposit takes the Funds back
+            self.available -= txrefund.amount;
+            self.total -= txrefund.amount;
+        } else
+        //Unsufficiant Funds available
+        {
+            vevents.push(AccountEvent::RefundRejected {
+                client: self.client,
+                tx: txrefund.tx,
+                amount: txrefund.amount,
+                reason: RejectReason::InsufficientFunds,
+            });
+
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Amount '-{} / {}' is not available.",
+                    txrefund.amount, self.available
+                );
+            }
+
+            return vevents;
+        } //if txoriginal.tx_type == "withdrawal"
+
+        self.available = (self.available * 10000.0).round() / 10000.0;
+        self.total = (self.total * 10000.0).round() / 10000.0;
+
+        txoriginal.refunded = ((txoriginal.refunded + txrefund.amount) * 10000.0).round() / 10000.0;
+
+        //Mark Transaction as accepted and processed
+        txrefund.status = 1;
+
+        vevents.push(AccountEvent::RefundApplied {
+            client: self.client,
+            tx: txrefund.tx,
+            amount: txrefund.amount,
+        });
+
+        vevents
+    }
+
+    /// Applies a "_transfer_" to the Destination Account or a Dispute, Resolve or Chargeback
+    /// Request on it. Both Accounts are changed together or not at all.
+    /// The Destination Account is the Holder of the Dispute as by `Transaction::get_holder()`:
+    /// the transferred Funds are held and released there. A Chargeback takes them from
+    /// the Destination Account, locks it and returns them to this Account.
+    /// A Chargeback exceeding the `total` Funds of the Destination is refused
+    /// unless the Negative Balance Policy records a Debt
+    #[allow(clippy::too_many_arguments)]
+    pub fn process_transfer(
+        &mut self,
+        accdestination: &mut Account,
+        mvrecord: &Movement,
+        otxrecord: Option<&mut Transaction>,
+        policy: &ProcessingPolicy,
+        inow: u64,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+        let txrec = match otxrecord {
+            Some(txrec) if txrec.client == self.client => txrec,
+            _ => {
+                if !bquiet {
+                    eprintln!("Movement Processing Error: Transaction (id: '{}') does not exist. Movement omitted.", &mvrecord.tx);
+                }
+
+                return vevents;
+            }
+        };
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Movement Processing '{}' on '{}': '{} / {}' to '{}'",
+                &mvrecord.tx_type,
+                &txrec.tx_type,
+                txrec.amount,
+                self.available,
+                accdestination.client
+            );
+        }
+
+        if self.locked || accdestination.locked {
+            if mvrecord.tx_type == "transfer" {
+                //Mark Transaction as invalid
+                txrec.status = -1;
+
+                vevents.push(AccountEvent::TransferRejected {
+                    client: self.client,
+                    tx: txrec.tx,
+                    amount: txrec.amount,
+                    reason: RejectReason::AccountLocked,
+                });
+            }
+
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Account (id: '{}' / '{}') is locked. Movement refused.",
+                    &self.client, &accdestination.client
+                );
+            }
+
+            return vevents;
+        } //if self.locked || accdestination.locked
+
+        match mvrecord.tx_type.as_str() {
+            "transfer" => {
+                if policy
+                    .limits
+                    .get_limits(self.client)
+                    .is_some_and(|l| l.exceeds_transaction(txrec.amount))
+                {
+                    return self.reject_movement(
+                        txrec,
+                        RejectReason::TransactionLimitExceeded,
+                        bquiet,
+                    );
+                }
+
+                if policy
+                    .limits
+                    .get_limits(accdestination.client)
+                    .is_some_and(|l| l.exceeds_balance(accdestination.total + txrec.amount))
+                {
+                    return self.reject_movement(txrec, RejectReason::BalanceLimitExceeded, bquiet);
+                }
+
+                //The Fee must be covered together with the Transfer
+                let ffee = policy.fees.calculate("transfer", self.client, txrec.amount);
+
+                if ((txrec.amount + ffee) * 10000.0).round() / 10000.0
+                    <= self.available + policy.limits.get_overdraft(self.client)
+                {
+                    self.available -= txrec.amount;
+                    self.total -= txrec.amount;
+                    accdestination.available += txrec.amount;
+                    accdestination.total += txrec.amount;
+
+                    self.available = (self.available * 10000.0).round() / 10000.0;
+                    self.total = (self.total * 10000.0).round() / 10000.0;
+                    accdestination.available =
+                        (accdestination.available * 10000.0).round() / 10000.0;
+                    accdestination.total = (accdestination.total * 10000.0).round() / 10000.0;
+
+                    //Mark Transaction as accepted and processed
+                    txrec.status = 1;
+                    txrec.processed_at = Some(inow);
+                    txrec.booked_at = Some(inow);
+
+                    vevents.push(AccountEvent::TransferSent {
+                        client: self.client,
+                        tx: txrec.tx,
+                        amount: txrec.amount,
+                    });
+                    vevents.push(AccountEvent::TransferReceived {
+                        client: accdestination.client,
+                        tx: txrec.tx,
+                        amount: txrec.amount,
+                    });
+                } else
+                //Unsufficiant Funds available
+                {
+                    //Mark Transaction as invalid
+                    txrec.status = -1;
+
+                    vevents.push(AccountEvent::TransferRejected {
+                        client: self.client,
+                        tx: txrec.tx,
+                        amount: txrec.amount,
+                        reason: RejectReason::InsufficientFunds,
+                    });
+
+                    if !bquiet {
+                        eprintln!(
+                            "Movement Processing Error: Amount '-{} / {}' is not available.",
+                            txrec.amount, self.available
+                        );
+                    }
+                } //if txrec.amount <= self.available
+            }
+            "dispute" | "resolve" | "chargeback" if !mvrecord.matches_currency(txrec) => {
+                vevents = self.reject_currency(mvrecord, txrec, bquiet);
+            }
+            "dispute" => {
+                vevents = accdestination.open_dispute(txrec, mvrecord.amount, policy, inow, bquiet);
+            }
+            "resolve" => {
+                vevents = accdestination.resolve_dispute(txrec, mvrecord.amount, bquiet);
+            }
+            "chargeback" => {
+                let ftotal = accdestination.total;
+
+                vevents = accdestination.apply_chargeback(txrec, mvrecord.amount, policy, bquiet);
+
+                //The charged back Funds return to this Account
+                let fcharge = ((ftotal - accdestination.total) * 10000.0).round() / 10000.0;
+
+                self.available = ((self.available + fcharge) * 10000.0).round() / 10000.0;
+                self.total = ((self.total + fcharge) * 10000.0).round() / 10000.0;
+            }
+            _ => {
+                if !bquiet {
+                    eprintln!(
+                        "Movement Processing Error: Movement Type '{}' on '{}': disallowed ",
+                        &mvrecord.tx_type, &txrec.tx_type
+                    );
+                }
+            }
+        } //match mvrecord.tx_type.as_str()
+
+        vevents
+    }
+
+    /// Charges the Fee recorded in the Transaction on the `available` Funds.
+    /// Fees are also charged on locked Accounts and may use the Overdraft `foverdraft`.
+    /// A Fee which is not covered is recorded as invalid
+    pub fn apply_fee(
+        &mut self,
+        txfee: &mut Transaction,
+        foverdraft: f64,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Fee Processing '{:?}': - '{} / {}'",
+                &txfee.reason, txfee.amount, self.available
+            );
+        }
+
+        if txfee.amount <= self.available + foverdraft {
+            self.available -= txfee.amount;
+            self.total -= txfee.amount;
+
+            self.available = (self.available * 10000.0).round() / 10000.0;
+            self.total = (self.total * 10000.0).round() / 10000.0;
+
+            //Mark Transaction as accepted and processed
+            txfee.status = 1;
+
+            vevents.push(AccountEvent::FeeCharged {
+                client: self.client,
+                tx: txfee.tx,
+                amount: txfee.amount,
+            });
+        } else
+        //Unsufficiant Funds available
+        {
+            //Mark Transaction as invalid
+            txfee.status = -1;
+
+            vevents.push(AccountEvent::FeeRejected {
+                client: self.client,
+                tx: txfee.tx,
+                amount: txfee.amount,
+                reason: RejectReason::InsufficientFunds,
+            });
+
+            if !bquiet {
+                eprintln!(
+                    "Fee Processing Error: Amount '-{} / {}' is not available.",
+                    txfee.amount, self.available
+                );
+            }
+        } //if txfee.amount <= self.available
+
+        vevents
+    }
+
+    /// Converts the Amount of an "_exchange_" from its `currency` into its `target` Currency
+    /// with the Rate which is effective at the Time `irateat`.
+    /// The Rate, the Spread and the converted Amount are recorded in the Transaction
+    fn apply_exchange(
+        &mut self,
+        txrecord: &mut Transaction,
+        policy: &ProcessingPolicy,
+        irateat: u64,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+        let (scurrency, starget) = match (&txrecord.currency, &txrecord.target) {
+            (Some(scurrency), Some(starget)) => (scurrency.clone(), starget.clone()),
+            _ => return vevents,
+        };
+        let conversion =
+            match policy
+                .exchange
+                .convert(&scurrency, &starget, txrecord.amount, irateat)
+            {
+                Some(conversion) => conversion,
+                None => return self.reject_movement(txrecord, RejectReason::RateMissing, bquiet),
+            };
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Movement Processing '{}': '{} {}' to '{} {}' at '{}'",
+                &txrecord.tx_type,
+                txrecord.amount,
+                &scurrency,
+                conversion.converted,
+                &starget,
+                conversion.rate
+            );
+        }
+
+        //Debit the Source Currency
+        let bcovered = {
+            let mut source = self.switch_to(Some(&scurrency));
+            let bcovered = txrecord.amount <= source.available;
+
+            if bcovered {
+                source.available =
+                    ((source.available - txrecord.amount) * 10000.0).round() / 10000.0;
+                source.total = ((source.total - txrecord.amount) * 10000.0).round() / 10000.0;
+            }
+
+            bcovered
+        };
+
+        if !bcovered {
+            return self.reject_movement(txrecord, RejectReason::InsufficientFunds, bquiet);
+        }
+
+        //Credit the Target Currency
+        {
+            let mut target = self.switch_to(Some(&starget));
+
+            target.available =
+                ((target.available + conversion.converted) * 10000.0).round() / 10000.0;
+            target.total = ((target.total + conversion.converted) * 10000.0).round() / 10000.0;
+        }
+
+        //Mark Transaction as accepted and processed
+        txrecord.status = 1;
+        txrecord.rate = Some(conversion.rate);
+        txrecord.spread = Some(conversion.spread);
+        txrecord.converted = Some(conversion.converted);
+        txrecord.reason = Some(format!(
+            "{} {} at {} {}/{} spread {}%",
+            conversion.converted,
+            &starget,
+            conversion.rate,
+            &scurrency,
+            &starget,
+            conversion.spread
+        ));
+
+        vevents.push(AccountEvent::ExchangeApplied {
+            client: self.client,
+            tx: txrecord.tx,
+            amount: txrecord.amount,
+            converted: conversion.converted,
+        });
+
+        vevents
+    }
+
+    /// Refuses a Dispute, Resolve or Chargeback in another Currency than the Transaction
+    fn reject_currency(
+        &self,
+        mvrecord: &Movement,
+        txrecord: &Transaction,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        if mvrecord.tx_type == "dispute" {
+            vevents.push(AccountEvent::DisputeRejected {
+                client: self.client,
+                tx: txrecord.tx,
+                amount: mvrecord.amount.unwrap_or(txrecord.amount),
+                reason: RejectReason::CurrencyMismatch,
+            });
+        }
+
+        if !bquiet {
+            eprintln!(
+                "Movement Processing Error: Currency '{:?}' does not match '{:?}'. Movement refused.",
+                &mvrecord.currency, &txrecord.currency
+            );
+        }
+
+        vevents
+    }
+
+    /// Refuses the Movement recorded in the Transaction for the given Reason.
+    /// The Transaction is marked as invalid
+    pub fn reject_movement(
+        &self,
+        txrecord: &mut Transaction,
+        reason: RejectReason,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+        let (client, tx, amount) = (self.client, txrecord.tx, txrecord.amount);
+
+        //Mark Transaction as invalid
+        txrecord.status = -1;
+
+        match txrecord.tx_type.as_str() {
+            "deposit" => vevents.push(AccountEvent::DepositRejected {
+                client,
+                tx,
+                amount,
+                reason,
+            }),
+            "withdrawal" => vevents.push(AccountEvent::WithdrawalRejected {
+                client,
+                tx,
+                amount,
+                reason,
+            }),
+            "transfer" => vevents.push(AccountEvent::TransferRejected {
+                client,
+                tx,
+                amount,
+                reason,
+            }),
+            "exchange" => vevents.push(AccountEvent::ExchangeRejected {
+                client,
+                tx,
+                amount,
+                reason,
+            }),
+            "refund" => vevents.push(AccountEvent::RefundRejected {
+                client,
+                tx,
+                amount,
+                reason,
+            }),
+            _ => {}
+        } //match txrecord.tx_type.as_str()
+
+        if !bquiet {
+            eprintln!(
+                "Movement Processing Error: Transaction (id: '{}'): refused '{}'.",
+                tx,
+                reason.as_str()
+            );
+        }
+
+        vevents
+    }
+
+    /// Applies an administrative Operation recorded in the Transaction.
+    /// Supported Operations are "_unlock_", "_freeze_", "_credit_" and "_debit_".
+    /// They are also applied on locked Accounts
+    pub fn process_admin(
+        &mut self,
+        txrecord: &mut Transaction,
+        bdebug: bool,
+        bquiet: bool,
+    ) -> Vec<AccountEvent> {
+        let mut vevents = Vec::new();
+
+        if bdebug && !bquiet {
+            eprintln!(
+                "Admin Processing '{}': '{} / {}' by '{:?}'",
+                &txrecord.tx_type, txrecord.amount, self.available, &txrecord.operator
+            );
+        }
+
+        match txrecord.tx_type.as_str() {
+            "unlock" => {
+                self.locked = false;
+
+                vevents.push(AccountEvent::AccountUnlocked {
+                    client: self.client,
+                    tx: txrecord.tx,
+                });
+            }
+            "freeze" => {
+                self.locked = true;
+
+                vevents.push(AccountEvent::AccountLocked {
+                    client: self.client,
+                    tx: txrecord.tx,
+                });
+            }
+            "credit" => {
+                self.available += txrecord.amount;
+                self.total += txrecord.amount;
+
+                self.available = (self.available * 10000.0).round() / 10000.0;
+                self.total = (self.total * 10000.0).round() / 10000.0;
+
+                vevents.push(AccountEvent::AdjustmentApplied {
+                    client: self.client,
+                    tx: txrecord.tx,
+                    amount: txrecord.amount,
+                });
+            }
+            "debit" => {
+                if txrecord.amount <= self.available {
+                    self.available -= txrecord.amount;
+                    self.total -= txrecord.amount;
+
+                    self.available = (self.available * 10000.0).round() / 10000.0;
+                    self.total = (self.total * 10000.0).round() / 10000.0;
+
+                    vevents.push(AccountEvent::AdjustmentApplied {
+                        client: self.client,
+                        tx: txrecord.tx,
+                        amount: -txrecord.amount,
+                    });
+                } else
+                //Unsufficiant Funds available
+                {
+                    //Mark Transaction as invalid
+                    txrecord.status = -1;
+
+                    if !bquiet {
+                        eprintln!(
+                            "Admin Processing Error: Amount '-{} / {}' is not available.",
+                            txrecord.amount, self.available
+                        );
+                    }
+                } //if txrecord.amount <= self.available
+            }
+            _ => {
+                //Mark Transaction as invalid
+                txrecord.status = -1;
+
+                if !bquiet {
+                    eprintln!(
+                        "Admin Processing Error: Operation '{}': unknown",
+                        &txrecord.tx_type
+                    );
+                }
+            }
+        } //match txrecord.tx_type.as_str()
+
+        vevents
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Rounds an Amount to 4 Decimals.
+/// Adding zero turns a negative Zero into a positive one
+pub fn round_amount(famount: f64) -> f64 {
+    (famount * 10000.0).round() / 10000.0 + 0.0
+}
+
+/// Reads the Amount of a Dispute, Resolve or Chargeback Request.
+/// Without Amount the given Default Portion is used
+fn read_portion(oamount: Option<f64>, fdefault: f64, bquiet: bool) -> Option<f64> {
+    match oamount {
+        Some(famount) if famount.is_finite() && famount > 0.0 => {
+            Some((famount * 10000.0).round() / 10000.0)
+        }
+        Some(famount) => {
+            if !bquiet {
+                eprintln!(
+                    "Movement Processing Error: Amount '{}' is invalid. Movement omitted.",
+                    famount
+                );
+            }
+
+            None
         }
+        None => Some(fdefault),
     }
 }
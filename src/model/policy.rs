@@ -0,0 +1,233 @@
+use super::exchange::ExchangeTable;
+use super::fee::FeeSchedule;
+use super::limit::LimitSchedule;
+use super::rule::RuleSet;
+
+//==============================================================================
+// Enum ClearingPolicy Declaration
+
+/// Defines when the Funds of a "_deposit_" are released from `held` to `available`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClearingPolicy {
+    /// The Funds are `available` at once
+    #[default]
+    Immediate,
+    /// The Funds are released after the given Number of subsequent Movements on the Account
+    Movements(u32),
+    /// The Funds are released by an explicit "_settle_" Movement
+    Settlement,
+    /// The Funds are released after the given Number of Seconds
+    Window(u64),
+}
+
+//==============================================================================
+// Enum ClearingPolicy Implementation
+
+impl ClearingPolicy {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Policy from its Text Form:
+    /// `immediate`, `settle`, `movements:<count>` or `window:<seconds>`
+    pub fn parse(spolicy: &str) -> Option<ClearingPolicy> {
+        let slower = spolicy.trim().to_lowercase();
+
+        match slower.split_once(':') {
+            Some(("movements", scount)) => match scount.parse::<u32>() {
+                Ok(icount) if icount > 0 => Some(ClearingPolicy::Movements(icount)),
+                _ => None,
+            },
+            Some(("window", sseconds)) => match sseconds.parse::<u64>() {
+                Ok(iseconds) if iseconds > 0 => Some(ClearingPolicy::Window(iseconds)),
+                _ => None,
+            },
+            Some(_) => None,
+            None => match slower.as_str() {
+                "immediate" => Some(ClearingPolicy::Immediate),
+                "settle" | "settlement" => Some(ClearingPolicy::Settlement),
+                _ => None,
+            },
+        } //match slower.split_once(':')
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_immediate(&self) -> bool {
+        *self == ClearingPolicy::Immediate
+    }
+}
+
+//==============================================================================
+// Enum NegativeBalancePolicy Declaration
+
+/// Defines how a Dispute of a "_deposit_" is handled when its Funds are not `available` anymore
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NegativeBalancePolicy {
+    /// The Dispute may produce a negative `available` Balance
+    /// but the Chargeback fails when it is not covered by the `total` Funds
+    #[default]
+    Unchecked,
+    /// The Dispute is refused
+    Reject,
+    /// The Dispute is accepted with a negative `available` Balance and the Shortfall
+    /// is recorded as Debt. The Chargeback is always applied
+    Debt,
+    /// Only the `available` Funds are placed on `held`
+    Cap,
+}
+
+//==============================================================================
+// Enum NegativeBalancePolicy Implementation
+
+impl NegativeBalancePolicy {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Policy from its Text Form: `unchecked`, `reject`, `debt` or `cap`
+    pub fn parse(spolicy: &str) -> Option<NegativeBalancePolicy> {
+        match spolicy.trim().to_lowercase().as_str() {
+            "unchecked" => Some(NegativeBalancePolicy::Unchecked),
+            "reject" => Some(NegativeBalancePolicy::Reject),
+            "debt" => Some(NegativeBalancePolicy::Debt),
+            "cap" => Some(NegativeBalancePolicy::Cap),
+            _ => None,
+        }
+    }
+}
+
+//==============================================================================
+// Enum DisputeDeadline Declaration
+
+/// Defines when an open Dispute expires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisputeDeadline {
+    /// The Dispute stays open until it is resolved or charged back
+    #[default]
+    Unlimited,
+    /// The Dispute expires after the given Number of subsequent Movements on the Account
+    Movements(u32),
+    /// The Dispute expires after the given Number of Seconds
+    Window(u64),
+}
+
+//==============================================================================
+// Enum DisputeDeadline Implementation
+
+impl DisputeDeadline {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Deadline from its Text Form:
+    /// `unlimited`, `movements:<count>` or `window:<seconds>`
+    pub fn parse(sdeadline: &str) -> Option<DisputeDeadline> {
+        let slower = sdeadline.trim().to_lowercase();
+
+        match slower.split_once(':') {
+            Some(("movements", scount)) => match scount.parse::<u32>() {
+                Ok(icount) if icount > 0 => Some(DisputeDeadline::Movements(icount)),
+                _ => None,
+            },
+            Some(("window", sseconds)) => match sseconds.parse::<u64>() {
+                Ok(iseconds) if iseconds > 0 => Some(DisputeDeadline::Window(iseconds)),
+                _ => None,
+            },
+            Some(_) => None,
+            None => match slower.as_str() {
+                "unlimited" => Some(DisputeDeadline::Unlimited),
+                _ => None,
+            },
+        } //match slower.split_once(':')
+    }
+}
+
+//==============================================================================
+// Enum ExpiryAction Declaration
+
+/// Defines how an expired Dispute is closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpiryAction {
+    /// The held Funds are released to the Client
+    #[default]
+    Resolve,
+    /// The held Funds are charged back and the Account is locked
+    Chargeback,
+}
+
+//==============================================================================
+// Enum ExpiryAction Implementation
+
+impl ExpiryAction {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Action from its Text Form: `resolve` or `chargeback`
+    pub fn parse(saction: &str) -> Option<ExpiryAction> {
+        match saction.trim().to_lowercase().as_str() {
+            "resolve" => Some(ExpiryAction::Resolve),
+            "chargeback" => Some(ExpiryAction::Chargeback),
+            _ => None,
+        }
+    }
+}
+
+//==============================================================================
+// Structure DisputePolicy Declaration
+
+/// The Time Limits of Disputes
+#[derive(Debug, Clone, Default)]
+pub struct DisputePolicy {
+    /// The Number of Seconds after the Booking in which a Transaction can be disputed
+    pub window: Option<u64>,
+    pub deadline: DisputeDeadline,
+    pub expiry: ExpiryAction,
+}
+
+//==============================================================================
+// Structure ProcessingPolicy Declaration
+
+/// The configurable Rules applied by the Engine on the Processing of the Movements
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingPolicy {
+    pub clearing: ClearingPolicy,
+    pub negative_balance: NegativeBalancePolicy,
+    pub fees: FeeSchedule,
+    pub limits: LimitSchedule,
+    pub exchange: ExchangeTable,
+    pub rules: RuleSet,
+    pub disputes: DisputePolicy,
+}
+
+//==============================================================================
+// Structure ProcessingPolicy Implementation
+
+impl ProcessingPolicy {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> ProcessingPolicy {
+        ProcessingPolicy::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns a Copy of the Policy without the Transaction Limit of the Client.
+    /// It applies to Movements which were approved by an Operator
+    pub fn without_transaction_limit(&self, client_id: u16) -> ProcessingPolicy {
+        let mut policy = self.clone();
+
+        if let Some(limits) = policy.limits.lstlimits.get_mut(&client_id) {
+            limits.max_transaction = None;
+        }
+
+        policy
+    }
+}
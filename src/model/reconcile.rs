@@ -0,0 +1,158 @@
+use csv::WriterBuilder;
+use std::collections::BTreeSet;
+
+use super::account::{Account, AccountFactory};
+
+//==============================================================================
+// Structure ReconcileDiff Declaration
+
+/// A Difference between the expected and the computed Accounts.
+/// The `kind` is `missing` for expected Clients without Account, `extra` for Accounts
+/// which are not expected and `mismatch` for a differing `field`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconcileDiff {
+    pub client: u16,
+    pub kind: String,
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+//==============================================================================
+// Structure Reconciliation Declaration
+
+/// The Differences between the expected and the computed Accounts ordered by Client
+#[derive(Debug, Default)]
+pub struct Reconciliation {
+    pub vdiffs: Vec<ReconcileDiff>,
+}
+
+//==============================================================================
+// Structure Reconciliation Implementation
+
+impl Reconciliation {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Compares the computed Accounts with the expected Accounts.
+    /// Balances which differ by not more than `ftolerance` are equal
+    pub fn new(
+        actual: &AccountFactory,
+        expected: &AccountFactory,
+        ftolerance: f64,
+    ) -> Reconciliation {
+        let mut reconciliation = Reconciliation::default();
+        let lstclients: BTreeSet<u16> = actual
+            .lstaccounts
+            .keys()
+            .chain(expected.lstaccounts.keys())
+            .copied()
+            .collect();
+
+        for iclient in lstclients.into_iter() {
+            match (
+                actual.lstaccounts.get(&iclient),
+                expected.lstaccounts.get(&iclient),
+            ) {
+                (Some(acc), Some(accexp)) => reconciliation.compare(acc, accexp, ftolerance),
+                (None, Some(_)) => reconciliation.add(iclient, "missing", "", "", ""),
+                (Some(_), None) => reconciliation.add(iclient, "extra", "", "", ""),
+                (None, None) => {}
+            }
+        }
+
+        reconciliation
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    fn compare(&mut self, acc: &Account, accexpected: &Account, ftolerance: f64) {
+        let vfields = [
+            ("available", acc.available, accexpected.available),
+            ("held", acc.held, accexpected.held),
+            ("total", acc.total, accexpected.total),
+        ];
+
+        for (sfield, factual, fexpected) in vfields.iter() {
+            //Compare on the rounded Difference to leave out the Noise of the Arithmetics
+            if ((factual - fexpected).abs() * 10000.0).round() / 10000.0 > ftolerance {
+                self.add(
+                    acc.client,
+                    "mismatch",
+                    sfield,
+                    &format!("{:?}", fexpected),
+                    &format!("{:?}", factual),
+                );
+            }
+        }
+
+        if acc.locked != accexpected.locked {
+            self.add(
+                acc.client,
+                "mismatch",
+                "locked",
+                &accexpected.locked.to_string(),
+                &acc.locked.to_string(),
+            );
+        }
+    }
+
+    fn add(&mut self, client_id: u16, skind: &str, sfield: &str, sexpected: &str, sactual: &str) {
+        self.vdiffs.push(ReconcileDiff {
+            client: client_id,
+            kind: String::from(skind),
+            field: String::from(sfield),
+            expected: String::from(sexpected),
+            actual: String::from(sactual),
+        });
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_clean(&self) -> bool {
+        self.vdiffs.is_empty()
+    }
+
+    /// Exports the Differences with the Columns `client,kind,field,expected,actual`
+    #[allow(unused_variables)]
+    pub fn export_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        if let Err(e) = wtr.write_record(["client", "kind", "field", "expected", "actual"]) {
+            if !bquiet {
+                eprintln!("Reconciliation CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for diff in self.vdiffs.iter() {
+            if let Err(e) = wtr.write_record([
+                diff.client.to_string().as_str(),
+                diff.kind.as_str(),
+                diff.field.as_str(),
+                diff.expected.as_str(),
+                diff.actual.as_str(),
+            ]) {
+                if !bquiet {
+                    eprintln!("Reconciliation CSV Export Error: '{:?}'", e)
+                }
+            }
+        } //for diff in self.vdiffs.iter()
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Reconciliation CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+}
@@ -0,0 +1,229 @@
+use csv::WriterBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::transaction::Movement;
+
+//==============================================================================
+// Structure ReviewItem Declaration
+
+/// A Movement which is parked until an Operator approves or rejects it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewItem {
+    pub movement: Movement,
+    /// The Rule or Limit which flagged the Movement.
+    /// Without Reason the Movement only waits behind an earlier Movement of the Client
+    #[serde(default)]
+    pub reason: Option<String>,
+    pub held_at: u64,
+}
+
+//==============================================================================
+// Structure ReviewQueue Declaration
+
+/// The parked Movements in the Order of their Arrival.
+/// A flagged Movement blocks all later Movements of its Client until an Operator decides it.
+/// The Movements do not expire, so their Age is reported from `held_at`
+#[derive(Debug, Clone, Default)]
+pub struct ReviewQueue {
+    pub vitems: Vec<ReviewItem>,
+}
+
+//==============================================================================
+// Structure ReviewItem Implementation
+
+impl ReviewItem {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Whether the Movement was flagged and needs a Decision
+    pub fn is_flagged(&self) -> bool {
+        self.reason.is_some()
+    }
+}
+
+//==============================================================================
+// Structure ReviewQueue Implementation
+
+impl ReviewQueue {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> ReviewQueue {
+        ReviewQueue::default()
+    }
+
+    pub fn from_items(vitems: Vec<ReviewItem>) -> ReviewQueue {
+        ReviewQueue { vitems }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Parks the Movement behind all Movements of the Client
+    pub fn push(&mut self, mvrecord: Movement, oreason: Option<String>, inow: u64) {
+        self.vitems.push(ReviewItem {
+            movement: mvrecord,
+            reason: oreason,
+            held_at: inow,
+        });
+    }
+
+    /// Parks the Movement in front of all Movements of the Client
+    pub fn push_front(&mut self, mvrecord: Movement, oreason: Option<String>, inow: u64) {
+        let iposition = self
+            .vitems
+            .iter()
+            .position(|item| item.movement.client == mvrecord.client)
+            .unwrap_or(self.vitems.len());
+
+        self.vitems.insert(
+            iposition,
+            ReviewItem {
+                movement: mvrecord,
+                reason: oreason,
+                held_at: inow,
+            },
+        );
+    }
+
+    /// Takes the first parked Movement of the Client out of the Queue
+    pub fn pop_client(&mut self, client_id: u16) -> Option<ReviewItem> {
+        let iposition = self
+            .vitems
+            .iter()
+            .position(|item| item.movement.client == client_id)?;
+
+        Some(self.vitems.remove(iposition))
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_empty(&self) -> bool {
+        self.vitems.is_empty()
+    }
+
+    pub fn has_client(&self, client_id: u16) -> bool {
+        self.vitems
+            .iter()
+            .any(|item| item.movement.client == client_id)
+    }
+
+    pub fn get_item(&self, transaction_id: u32) -> Option<&ReviewItem> {
+        self.vitems
+            .iter()
+            .find(|item| item.movement.tx == transaction_id)
+    }
+
+    /// The first parked Movement of the Client which is decided next
+    pub fn get_first(&self, client_id: u16) -> Option<&ReviewItem> {
+        self.vitems
+            .iter()
+            .find(|item| item.movement.client == client_id)
+    }
+
+    /// Exports the flagged Movements held for at least `imaxage` Seconds at the Time `inow`
+    /// with the Columns `client,tx,type,reason,held_at,age,waiting`.
+    /// `waiting` is the Number of later Movements of the Client blocked behind it
+    #[allow(unused_variables)]
+    pub fn export_aged_csv(&self, inow: u64, imaxage: u64, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+        let mut lstbehind: HashMap<u16, usize> = HashMap::new();
+        let mut vaged = Vec::new();
+
+        //The Queue is walked backwards to count the Movements behind each Item
+        for item in self.vitems.iter().rev() {
+            let ibehind = lstbehind.entry(item.movement.client).or_insert(0);
+            let iage = inow.saturating_sub(item.held_at);
+
+            if item.is_flagged() && iage >= imaxage {
+                vaged.push((item, iage, *ibehind));
+            }
+
+            *ibehind += 1;
+        }
+
+        if let Err(e) = wtr.write_record([
+            "client", "tx", "type", "reason", "held_at", "age", "waiting",
+        ]) {
+            if !bquiet {
+                eprintln!("Review Queue CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for (item, iage, iwaiting) in vaged.into_iter().rev() {
+            if let Err(e) = wtr.write_record([
+                item.movement.client.to_string(),
+                item.movement.tx.to_string(),
+                item.movement.tx_type.clone(),
+                item.reason.clone().unwrap_or_default(),
+                item.held_at.to_string(),
+                iage.to_string(),
+                iwaiting.to_string(),
+            ]) {
+                if !bquiet {
+                    eprintln!("Review Queue CSV Export Error: '{:?}'", e)
+                }
+            }
+        } //for (item, iage, iwaiting) in vaged.into_iter().rev()
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Review Queue CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+
+    /// Exports the parked Movements with the Columns `client,tx,type,amount,reason,held_at`
+    #[allow(unused_variables)]
+    pub fn export_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        if let Err(e) = wtr.write_record(["client", "tx", "type", "amount", "reason", "held_at"]) {
+            if !bquiet {
+                eprintln!("Review Queue CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for item in self.vitems.iter() {
+            if let Err(e) = wtr.write_record([
+                item.movement.client.to_string(),
+                item.movement.tx.to_string(),
+                item.movement.tx_type.clone(),
+                item.movement
+                    .amount
+                    .map(|famount| format!("{:?}", famount))
+                    .unwrap_or_default(),
+                item.reason.clone().unwrap_or_default(),
+                item.held_at.to_string(),
+            ]) {
+                if !bquiet {
+                    eprintln!("Review Queue CSV Export Error: '{:?}'", e)
+                }
+            }
+        } //for item in self.vitems.iter()
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Review Queue CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+}
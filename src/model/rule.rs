@@ -0,0 +1,342 @@
+use csv::{ReaderBuilder, Trim, WriterBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::transaction::{Movement, Transaction, TransactionFactory};
+
+//==============================================================================
+// Enum RuleKind Declaration
+
+/// The Pattern of Activity a Fraud Rule looks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// A "_withdrawal_" or "_transfer_" exceeding `threshold` Times the average Deposit
+    WithdrawalRatio,
+    /// More than `threshold` Disputes of the Client within `window` Seconds
+    DisputeCount,
+    /// A "_withdrawal_" or "_transfer_" of at least `threshold` Times a Deposit
+    /// which was booked within `window` Seconds before
+    RapidCycle,
+    /// Any Movement within `window` Seconds after the Account was unlocked
+    AfterUnlock,
+}
+
+//==============================================================================
+// Enum RuleAction Declaration
+
+/// What happens to a Movement which matches a Fraud Rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    /// The Movement is applied and the Hit is reported
+    Flag,
+    /// The Movement is not applied and kept for a manual Review
+    Hold,
+    /// The Movement is refused
+    Reject,
+}
+
+//==============================================================================
+// Structure FraudRule Declaration
+
+/// A configured Fraud Rule. The `threshold` and the `window` in Seconds
+/// are interpreted according to the `kind`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FraudRule {
+    pub rule: String,
+    pub kind: RuleKind,
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    #[serde(default)]
+    pub window: Option<u64>,
+    pub action: RuleAction,
+}
+
+//==============================================================================
+// Structure RuleHit Declaration
+
+/// A Movement which matched a Fraud Rule
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RuleHit {
+    pub rule: String,
+    pub action: RuleAction,
+    pub client: u16,
+    pub tx: u32,
+    pub tx_type: String,
+    pub amount: Option<f64>,
+    pub at: u64,
+    pub message: String,
+}
+
+//==============================================================================
+// Structure RuleImportError Declaration
+
+#[derive(Debug)]
+pub struct RuleImportError {
+    pub ok_count: u32,
+    pub lines: Vec<u32>,
+    pub message: String,
+    pub code: i8,
+}
+
+//==============================================================================
+// Structure RuleSet Declaration
+
+/// The Fraud Rules evaluated on each Movement before it is applied
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    pub vrules: Vec<FraudRule>,
+}
+
+//==============================================================================
+// Enum RuleAction Implementation
+
+impl RuleAction {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleAction::Flag => "flag",
+            RuleAction::Hold => "hold",
+            RuleAction::Reject => "reject",
+        }
+    }
+}
+
+//==============================================================================
+// Structure RuleSet Implementation
+
+impl RuleSet {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> RuleSet {
+        RuleSet::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Imports the Rules with the Columns `rule,kind,threshold,window,action`
+    /// where `kind` is one of `withdrawal_ratio`, `dispute_count`, `rapid_cycle`
+    /// or `after_unlock` and `action` is one of `flag`, `hold` or `reject`
+    pub fn import_str(&mut self, srules_csv: &str) -> Result<u32, RuleImportError> {
+        let mut rdr = ReaderBuilder::new()
+            .trim(Trim::All)
+            .from_reader(srules_csv.as_bytes());
+        let mut verrlines: Vec<u32> = Vec::new();
+        let mut serr = String::new();
+        let mut icount = 0;
+
+        for (icsvline, result) in (2..).zip(rdr.deserialize::<FraudRule>()) {
+            match result {
+                Ok(rule) => {
+                    self.vrules.push(rule);
+
+                    icount += 1;
+                }
+                Err(e) => {
+                    serr.push_str(&format!("Parse Error: '{:?}'; ", e));
+                    verrlines.push(icsvline);
+                }
+            } //match result
+        } //for (icsvline, result) in (2..).zip(rdr.deserialize::<FraudRule>())
+
+        if verrlines.is_empty() {
+            Ok(icount)
+        } else {
+            Err(RuleImportError {
+                ok_count: icount,
+                lines: verrlines,
+                message: serr,
+                code: 1,
+            })
+        }
+    }
+
+    pub fn add_rule(&mut self, rule: FraudRule) {
+        self.vrules.push(rule);
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_empty(&self) -> bool {
+        self.vrules.is_empty()
+    }
+
+    /// Evaluates all Rules on the Movement before it is applied.
+    /// Only the Transactions of the Client are looked at through the Client Index.
+    /// `inow` is the current Time in Seconds since the Unix Epoch
+    pub fn evaluate(
+        &self,
+        mvrecord: &Movement,
+        txfactory: &TransactionFactory,
+        inow: u64,
+    ) -> Vec<RuleHit> {
+        let mut vhits = Vec::new();
+        //The Transaction created by the Movement itself
+        let otxown = txfactory.lsttransactions.get(&mvrecord.tx).filter(|txrec| {
+            txrec.client == mvrecord.client
+                && txrec.status == 0
+                && txrec.tx_type == mvrecord.tx_type
+        });
+        let oamount = match otxown {
+            Some(txrec) => Some(txrec.amount),
+            None => mvrecord.amount,
+        };
+        let boutgoing = otxown.is_some()
+            && (mvrecord.tx_type == "withdrawal" || mvrecord.tx_type == "transfer");
+        let vdeposits: Vec<&Transaction> = match otxown.filter(|_| boutgoing) {
+            Some(txown) => txfactory
+                .get_client_transactions(mvrecord.client)
+                .filter(|txrec| {
+                    txrec.tx_type == "deposit"
+                        && txrec.status != -1
+                        && txrec.status != 0
+                        && txrec.currency == txown.currency
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        for rule in self.vrules.iter() {
+            let fthreshold = rule.threshold.unwrap_or(0.0);
+            let iwindow = rule.window.unwrap_or(0);
+            let omessage = match rule.kind {
+                RuleKind::WithdrawalRatio if boutgoing && !vdeposits.is_empty() => {
+                    let famount = oamount.unwrap_or(0.0);
+                    let faverage = vdeposits.iter().map(|txrec| txrec.amount).sum::<f64>()
+                        / vdeposits.len() as f64;
+                    let faverage = (faverage * 10000.0).round() / 10000.0;
+
+                    if famount > fthreshold * faverage {
+                        Some(format!(
+                            "Amount '{}' exceeds {} Times the average Deposit '{}'",
+                            famount, fthreshold, faverage
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                RuleKind::DisputeCount if mvrecord.tx_type == "dispute" => {
+                    let istart = inow.saturating_sub(iwindow);
+                    //The Dispute of the Movement is counted as well
+                    let idisputes = txfactory
+                        .get_client_transactions(mvrecord.client)
+                        .flat_map(|txrec| txrec.cases.iter())
+                        .filter(|dispute| dispute.opened_at >= istart)
+                        .count()
+                        + 1;
+
+                    if idisputes as f64 > fthreshold {
+                        Some(format!(
+                            "'{}' Disputes within '{}' Seconds",
+                            idisputes, iwindow
+                        ))
+                    } else {
+                        None
+                    }
+                }
+                RuleKind::RapidCycle if boutgoing => {
+                    let istart = inow.saturating_sub(iwindow);
+                    let famount = oamount.unwrap_or(0.0);
+
+                    vdeposits
+                        .iter()
+                        .filter(|txrec| txrec.booked_at.is_some_and(|iat| iat >= istart))
+                        .find(|txrec| famount >= fthreshold * txrec.amount)
+                        .map(|txrec| {
+                            format!(
+                                "Amount '{}' leaves within '{}' Seconds after the Deposit (id: '{}')",
+                                famount, iwindow, txrec.tx
+                            )
+                        })
+                }
+                RuleKind::AfterUnlock => {
+                    let istart = inow.saturating_sub(iwindow);
+
+                    txfactory
+                        .get_client_transactions(mvrecord.client)
+                        .filter(|txrec| txrec.tx_type == "unlock" && txrec.status == 1)
+                        .find(|txrec| txrec.booked_at.is_some_and(|iat| iat >= istart))
+                        .map(|txrec| {
+                            format!(
+                                "Movement within '{}' Seconds after the Unlock (id: '{}')",
+                                iwindow, txrec.tx
+                            )
+                        })
+                }
+                _ => None,
+            }; //match rule.kind
+
+            if let Some(smessage) = omessage {
+                vhits.push(RuleHit {
+                    rule: rule.rule.clone(),
+                    action: rule.action,
+                    client: mvrecord.client,
+                    tx: mvrecord.tx,
+                    tx_type: mvrecord.tx_type.clone(),
+                    amount: oamount,
+                    at: inow,
+                    message: smessage,
+                });
+            }
+        } //for rule in self.vrules.iter()
+
+        vhits
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Exports the Rule Hits with the Columns `rule,action,client,tx,type,amount,at,message`
+#[allow(unused_variables)]
+pub fn export_hits_csv(vhits: &[RuleHit], bdebug: bool, bquiet: bool) -> String {
+    let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+    if let Err(e) = wtr.write_record([
+        "rule", "action", "client", "tx", "type", "amount", "at", "message",
+    ]) {
+        if !bquiet {
+            eprintln!("Rule Hits CSV Export Error: '{:?}'", e)
+        }
+    }
+
+    for hit in vhits.iter() {
+        if let Err(e) = wtr.write_record([
+            hit.rule.clone(),
+            String::from(hit.action.as_str()),
+            hit.client.to_string(),
+            hit.tx.to_string(),
+            hit.tx_type.clone(),
+            hit.amount
+                .map(|famount| format!("{:?}", famount))
+                .unwrap_or_default(),
+            hit.at.to_string(),
+            hit.message.clone(),
+        ]) {
+            if !bquiet {
+                eprintln!("Rule Hits CSV Export Error: '{:?}'", e)
+            }
+        }
+    } //for hit in vhits.iter()
+
+    match wtr.into_inner() {
+        Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+        Err(e) => {
+            if !bquiet {
+                eprintln!("Rule Hits CSV Export Error: '{:?}'", e);
+            }
+
+            //Return empty String
+            String::new()
+        }
+    } //match wtr.into_inner()
+}
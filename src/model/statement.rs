@@ -0,0 +1,394 @@
+use serde::Serialize;
+
+use csv::WriterBuilder;
+
+use super::account::CurrencyBalance;
+use super::exchange::parse_date_days;
+use super::ledger::Ledger;
+use super::transaction::TransactionFactory;
+
+//==============================================================================
+// Enum StatementRange Declaration
+
+/// The Part of the Account History covered by a Statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatementRange {
+    /// The whole History
+    #[default]
+    All,
+    /// The Ledger Entries from the first to the last Sequence Number
+    Sequence(u64, u64),
+    /// The Ledger Entries from the first to the last Second since the Unix Epoch
+    Period(u64, u64),
+}
+
+//==============================================================================
+// Enum StatementFormat Declaration
+
+/// The Layout of an exported Statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatementFormat {
+    #[default]
+    Csv,
+    Json,
+    Text,
+}
+
+//==============================================================================
+// Structure StatementLine Declaration
+
+/// A Change of the Balances with the Transaction which caused it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatementLine {
+    pub sequence: u64,
+    pub at: u64,
+    pub tx: u32,
+    #[serde(rename = "type")]
+    pub tx_type: String,
+    /// The current Status of the Transaction
+    pub status: Option<i8>,
+    /// The Amount of the Transaction
+    pub amount: Option<f64>,
+    pub available: f64,
+    pub held: f64,
+    pub balance: f64,
+}
+
+//==============================================================================
+// Structure Statement Declaration
+
+/// The Statement of the Account of a Client in one Currency
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Statement {
+    pub client: u16,
+    pub currency: Option<String>,
+    pub opening: CurrencyBalance,
+    pub lines: Vec<StatementLine>,
+    pub closing: CurrencyBalance,
+    /// The Number of Disputes within the Range
+    pub disputes: u32,
+    /// The Number of Chargebacks within the Range
+    pub chargebacks: u32,
+}
+
+//==============================================================================
+// Enum StatementRange Implementation
+
+impl StatementRange {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Range from its Text Form: `all`, `sequence:<first>-<last>`
+    /// or `date:<YYYY-MM-DD>..<YYYY-MM-DD>` where both Days are included
+    pub fn parse(srange: &str) -> Option<StatementRange> {
+        let slower = srange.trim().to_lowercase();
+
+        match slower.split_once(':') {
+            Some(("sequence", ssequences)) => {
+                let (sfirst, slast) = ssequences.split_once('-')?;
+                let ifirst = sfirst.trim().parse::<u64>().ok()?;
+                let ilast = slast.trim().parse::<u64>().ok()?;
+
+                if ifirst > ilast {
+                    return None;
+                }
+
+                Some(StatementRange::Sequence(ifirst, ilast))
+            }
+            Some(("date", sdates)) => {
+                let (sfirst, slast) = sdates.split_once("..")?;
+                let ifirst = parse_date_days(sfirst)?;
+                let ilast = parse_date_days(slast)?;
+
+                if ifirst < 0 || ifirst > ilast {
+                    return None;
+                }
+
+                Some(StatementRange::Period(
+                    ifirst as u64 * 86400,
+                    (ilast as u64 + 1) * 86400 - 1,
+                ))
+            }
+            Some(_) => None,
+            None => match slower.as_str() {
+                "all" => Some(StatementRange::All),
+                _ => None,
+            },
+        } //match slower.split_once(':')
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    fn is_before(&self, isequence: u64, iat: u64) -> bool {
+        match self {
+            StatementRange::All => false,
+            StatementRange::Sequence(ifirst, _) => isequence < *ifirst,
+            StatementRange::Period(ifirst, _) => iat < *ifirst,
+        }
+    }
+
+    fn contains(&self, isequence: u64, iat: u64) -> bool {
+        match self {
+            StatementRange::All => true,
+            StatementRange::Sequence(ifirst, ilast) => (*ifirst..=*ilast).contains(&isequence),
+            StatementRange::Period(ifirst, ilast) => (*ifirst..=*ilast).contains(&iat),
+        }
+    }
+}
+
+//==============================================================================
+// Enum StatementFormat Implementation
+
+impl StatementFormat {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Parses the Format from its Text Form: `csv`, `json` or `text`
+    pub fn parse(sformat: &str) -> Option<StatementFormat> {
+        match sformat.trim().to_lowercase().as_str() {
+            "csv" => Some(StatementFormat::Csv),
+            "json" => Some(StatementFormat::Json),
+            "text" | "txt" => Some(StatementFormat::Text),
+            _ => None,
+        }
+    }
+}
+
+//==============================================================================
+// Structure Statement Implementation
+
+impl Statement {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Builds the Statement from the Ledger Entries of the Client in the Currency
+    /// and the Transactions in the Transaction History which caused them
+    pub fn new(
+        client_id: u16,
+        ocurrency: Option<&str>,
+        range: &StatementRange,
+        txfactory: &TransactionFactory,
+        ledger: &Ledger,
+    ) -> Statement {
+        let mut statement = Statement {
+            client: client_id,
+            currency: ocurrency.map(String::from),
+            opening: CurrencyBalance::default(),
+            lines: Vec::new(),
+            closing: CurrencyBalance::default(),
+            disputes: 0,
+            chargebacks: 0,
+        };
+
+        for entry in ledger
+            .get_entries(client_id)
+            .iter()
+            .filter(|entry| entry.currency.as_deref() == ocurrency)
+        {
+            let balance = CurrencyBalance {
+                available: entry.available,
+                held: entry.held,
+                total: entry.total,
+            };
+
+            if range.is_before(entry.sequence, entry.at) {
+                statement.opening = balance.clone();
+                statement.closing = balance;
+
+                continue;
+            }
+
+            if !range.contains(entry.sequence, entry.at) {
+                continue;
+            }
+
+            let otxrec = txfactory.lsttransactions.get(&entry.tx);
+
+            match entry.tx_type.as_str() {
+                "dispute" => statement.disputes += 1,
+                "chargeback" => statement.chargebacks += 1,
+                _ => {}
+            }
+
+            statement.lines.push(StatementLine {
+                sequence: entry.sequence,
+                at: entry.at,
+                tx: entry.tx,
+                tx_type: entry.tx_type.clone(),
+                status: otxrec.map(|txrec| txrec.status),
+                amount: otxrec.map(|txrec| txrec.amount),
+                available: entry.available,
+                held: entry.held,
+                balance: entry.total,
+            });
+
+            statement.closing = balance;
+        } //for entry in ledger.get_entries(client_id).iter()
+
+        statement
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn export(&self, format: StatementFormat, bdebug: bool, bquiet: bool) -> String {
+        match format {
+            StatementFormat::Csv => self.export_csv(bdebug, bquiet),
+            StatementFormat::Json => self.export_json(bquiet),
+            StatementFormat::Text => self.export_text(),
+        }
+    }
+
+    /// Exports the Statement with the Columns
+    /// `sequence,at,tx,type,status,amount,available,held,balance`.
+    /// The Opening and Closing Balances are exported as Rows of the Type `opening` and `closing`
+    #[allow(unused_variables)]
+    pub fn export_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+        let mut vrows: Vec<[String; 9]> = Vec::new();
+
+        vrows.push(balance_row("opening", &self.opening));
+
+        for line in self.lines.iter() {
+            vrows.push([
+                line.sequence.to_string(),
+                line.at.to_string(),
+                line.tx.to_string(),
+                line.tx_type.clone(),
+                line.status
+                    .map(|istatus| istatus.to_string())
+                    .unwrap_or_default(),
+                line.amount
+                    .map(|famount| format!("{:?}", famount))
+                    .unwrap_or_default(),
+                format!("{:?}", line.available),
+                format!("{:?}", line.held),
+                format!("{:?}", line.balance),
+            ]);
+        }
+
+        vrows.push(balance_row("closing", &self.closing));
+
+        if let Err(e) = wtr.write_record([
+            "sequence",
+            "at",
+            "tx",
+            "type",
+            "status",
+            "amount",
+            "available",
+            "held",
+            "balance",
+        ]) {
+            if !bquiet {
+                eprintln!("Statement CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for row in vrows.iter() {
+            if let Err(e) = wtr.write_record(row) {
+                if !bquiet {
+                    eprintln!("Statement CSV Export Error: '{:?}'", e)
+                }
+            }
+        }
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Statement CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+
+    pub fn export_json(&self, bquiet: bool) -> String {
+        match serde_json::to_string(self) {
+            Ok(sjson) => sjson,
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Statement JSON Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        }
+    }
+
+    /// Exports the Statement as Text Table for the Client
+    pub fn export_text(&self) -> String {
+        let mut stext = format!("Statement of Client {}", self.client);
+
+        if let Some(scurrency) = &self.currency {
+            stext.push_str(&format!(" in {}", scurrency));
+        }
+
+        stext.push('\n');
+        stext.push_str(&format!(
+            "Opening Balance: available {:?} held {:?} total {:?}\n",
+            self.opening.available, self.opening.held, self.opening.total
+        ));
+        stext.push_str(&format!(
+            "{:>8} {:>12} {:>10} {:<12} {:>6} {:>12} {:>12} {:>12} {:>12}\n",
+            "Sequence", "Time", "Tx", "Type", "Status", "Amount", "Available", "Held", "Balance"
+        ));
+
+        for line in self.lines.iter() {
+            stext.push_str(&format!(
+                "{:>8} {:>12} {:>10} {:<12} {:>6} {:>12} {:>12} {:>12} {:>12}\n",
+                line.sequence,
+                line.at,
+                line.tx,
+                line.tx_type,
+                line.status
+                    .map(|istatus| istatus.to_string())
+                    .unwrap_or_default(),
+                line.amount
+                    .map(|famount| format!("{:?}", famount))
+                    .unwrap_or_default(),
+                format!("{:?}", line.available),
+                format!("{:?}", line.held),
+                format!("{:?}", line.balance),
+            ));
+        }
+
+        stext.push_str(&format!(
+            "Closing Balance: available {:?} held {:?} total {:?}\n",
+            self.closing.available, self.closing.held, self.closing.total
+        ));
+        stext.push_str(&format!(
+            "Disputes: {} Chargebacks: {}\n",
+            self.disputes, self.chargebacks
+        ));
+
+        stext
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+fn balance_row(stype: &str, balance: &CurrencyBalance) -> [String; 9] {
+    [
+        String::new(),
+        String::new(),
+        String::new(),
+        String::from(stype),
+        String::new(),
+        String::new(),
+        format!("{:?}", balance.available),
+        format!("{:?}", balance.held),
+        format!("{:?}", balance.total),
+    ]
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::event::AccountEvent;
+use super::round_amount;
+use super::transaction::Transaction;
+
+//==============================================================================
+// Structure RunSummary Declaration
+
+/// The Statistics of a Run over the processed Movements.
+/// The Amounts are summed per Currency where the primary Currency has the empty Name
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RunSummary {
+    /// The Number of Movement Lines read from the Input
+    pub read: u64,
+    /// The Number of Movements which could be parsed
+    pub parsed: u64,
+    /// The Number of rejected Movements by Reason like `insufficient_funds`
+    pub rejected: BTreeMap<String, u64>,
+    /// The Number of processed Movements by Type
+    pub types: BTreeMap<String, u64>,
+    pub accounts_created: u64,
+    pub accounts_locked: u64,
+    #[serde(deserialize_with = "deserialize_amounts")]
+    pub deposited: BTreeMap<String, f64>,
+    #[serde(deserialize_with = "deserialize_amounts")]
+    pub withdrawn: BTreeMap<String, f64>,
+    /// The Funds on hold at the End of the Run
+    #[serde(deserialize_with = "deserialize_amounts")]
+    pub held: BTreeMap<String, f64>,
+    #[serde(deserialize_with = "deserialize_amounts")]
+    pub charged_back: BTreeMap<String, f64>,
+    /// The Duration of all Runs in Milliseconds
+    pub elapsed_ms: u64,
+    /// The parsed Movements per Second
+    pub throughput: f64,
+}
+
+//==============================================================================
+// Structure RunSummary Implementation
+
+impl RunSummary {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new() -> RunSummary {
+        RunSummary::default()
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Counts the Lines of an Input Batch where `iparsed` of `iread` Lines could be parsed
+    pub fn add_input(&mut self, iread: u64, iparsed: u64) {
+        self.read += iread;
+        self.parsed += iparsed;
+    }
+
+    pub fn add_movement(&mut self, stype: &str) {
+        *self.types.entry(String::from(stype)).or_default() += 1;
+    }
+
+    pub fn add_account(&mut self) {
+        self.accounts_created += 1;
+    }
+
+    pub fn add_withdrawal(&mut self, ocurrency: Option<&str>, famount: f64) {
+        add_amount(&mut self.withdrawn, ocurrency, famount);
+    }
+
+    /// Counts the Rejections, accepted Deposits, Chargebacks and Locks among the Events.
+    /// The Amounts are summed in the Currency of their Transactions in `lsttransactions`
+    pub fn add_events(
+        &mut self,
+        vevents: &[AccountEvent],
+        lsttransactions: &HashMap<u32, Transaction>,
+    ) {
+        for event in vevents.iter() {
+            let ocurrency = lsttransactions
+                .get(&event.get_transaction())
+                .and_then(|txrec| txrec.currency.as_deref());
+
+            match event {
+                AccountEvent::DepositAccepted { amount, .. } => {
+                    add_amount(&mut self.deposited, ocurrency, *amount)
+                }
+                AccountEvent::ChargebackApplied { amount, .. } => {
+                    add_amount(&mut self.charged_back, ocurrency, *amount)
+                }
+                AccountEvent::AccountLocked { .. } => self.accounts_locked += 1,
+                _ => {
+                    if let Some(sreason) = event.to_record().reason {
+                        *self.rejected.entry(sreason).or_default() += 1;
+                    }
+                }
+            }
+        } //for event in vevents.iter()
+    }
+
+    /// Sets the Duration of the Run and calculates the Throughput from it
+    pub fn set_elapsed(&mut self, ielapsed_ms: u64) {
+        self.elapsed_ms = ielapsed_ms;
+        self.throughput = if ielapsed_ms > 0 {
+            (self.parsed as f64 * 1000.0 / ielapsed_ms as f64 * 100.0).round() / 100.0
+        } else {
+            0.0
+        };
+    }
+
+    /// Adds the Duration of a further Run on the same State
+    pub fn add_elapsed(&mut self, ielapsed_ms: u64) {
+        self.set_elapsed(self.elapsed_ms + ielapsed_ms);
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// The Number of Lines which could not be parsed
+    pub fn get_parse_errors(&self) -> u64 {
+        self.read.saturating_sub(self.parsed)
+    }
+
+    pub fn get_rejected_count(&self) -> u64 {
+        self.rejected.values().sum()
+    }
+
+    pub fn export_json(&self, bquiet: bool) -> String {
+        match serde_json::to_string(self) {
+            Ok(sjson) => sjson,
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Summary JSON Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        }
+    }
+
+    /// Exports the Summary as Text Report with one Figure per Line
+    pub fn export_text(&self) -> String {
+        let mut stext = String::from("Run Summary:\n");
+
+        stext.push_str(&format!("  Movements read: {}\n", self.read));
+        stext.push_str(&format!("  Movements parsed: {}\n", self.parsed));
+        stext.push_str(&format!("  Parse Errors: {}\n", self.get_parse_errors()));
+
+        for (stype, icount) in self.types.iter() {
+            stext.push_str(&format!("  Type '{}': {}\n", stype, icount));
+        }
+
+        stext.push_str(&format!(
+            "  Movements rejected: {}\n",
+            self.get_rejected_count()
+        ));
+
+        for (sreason, icount) in self.rejected.iter() {
+            stext.push_str(&format!("  Rejected '{}': {}\n", sreason, icount));
+        }
+
+        stext.push_str(&format!("  Accounts created: {}\n", self.accounts_created));
+        stext.push_str(&format!("  Accounts locked: {}\n", self.accounts_locked));
+        for (slabel, lstamounts) in [
+            ("Deposited", &self.deposited),
+            ("Withdrawn", &self.withdrawn),
+            ("Held", &self.held),
+            ("Charged back", &self.charged_back),
+        ] {
+            for (scurrency, famount) in lstamounts.iter() {
+                if scurrency.is_empty() {
+                    stext.push_str(&format!("  {}: {:?}\n", slabel, famount));
+                } else {
+                    stext.push_str(&format!("  {} '{}': {:?}\n", slabel, scurrency, famount));
+                }
+            }
+        } //for (slabel, lstamounts) in [ ... ]
+
+        stext.push_str(&format!("  Elapsed: {} ms\n", self.elapsed_ms));
+        stext.push_str(&format!(
+            "  Throughput: {:?} Movements/s\n",
+            self.throughput
+        ));
+
+        stext
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Adds the Amount to the Sum of its Currency
+fn add_amount(lstamounts: &mut BTreeMap<String, f64>, ocurrency: Option<&str>, famount: f64) {
+    let fsum = lstamounts
+        .entry(String::from(ocurrency.unwrap_or_default()))
+        .or_default();
+
+    *fsum = round_amount(*fsum + famount);
+}
+
+/// Reads the Amounts per Currency.
+/// Summaries saved with one Sum over all Currencies are read as Amounts of the primary Currency
+fn deserialize_amounts<'de, D>(deserializer: D) -> Result<BTreeMap<String, f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amounts {
+        Sum(f64),
+        PerCurrency(BTreeMap<String, f64>),
+    }
+
+    Ok(match Amounts::deserialize(deserializer)? {
+        Amounts::Sum(0.0) => BTreeMap::new(),
+        Amounts::Sum(famount) => BTreeMap::from([(String::new(), famount)]),
+        Amounts::PerCurrency(lstamounts) => lstamounts,
+    })
+}
@@ -1,12 +1,14 @@
 use csv::{ReaderBuilder, Trim, WriterBuilder};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::vec::Vec;
 
+use super::dispute::DisputeCase;
+
 //==============================================================================
 // Structure Transaction Declaration
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Transaction {
     #[serde(rename = "type")]
     pub tx_type: String,
@@ -14,43 +16,323 @@ pub struct Transaction {
     pub tx: u32,
     pub amount: f64,
     pub status: i8,
+    #[serde(default)]
+    pub operator: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// The Funds of a "_deposit_" are still on `held` until they are cleared
+    #[serde(default)]
+    pub clearing: bool,
+    /// The Number of Movements on the Account left until the Funds are cleared
+    #[serde(default)]
+    pub clears_after: Option<u32>,
+    /// The Time in Seconds since the Unix Epoch when the Funds are cleared
+    #[serde(default)]
+    pub clears_at: Option<u64>,
+    /// The Portion of the Amount which is under Dispute
+    #[serde(default)]
+    pub disputed: f64,
+    /// The Funds placed on `held` by the Disputes when they differ from the disputed Portion
+    #[serde(default)]
+    pub held: Option<f64>,
+    /// The Portion of the Amount which was charged back
+    #[serde(default)]
+    pub charged_back: f64,
+    /// The Shortfall of the Dispute that was not covered by the `available` Funds
+    #[serde(default)]
+    pub debt: Option<f64>,
+    /// The Transaction which is refunded by a "_refund_"
+    #[serde(default)]
+    pub original: Option<u32>,
+    /// The Portion of the Amount which was refunded
+    #[serde(default)]
+    pub refunded: f64,
+    /// The Client which receives a "_transfer_".
+    /// It holds the disputed Funds of the "_transfer_" as returned by `get_holder()`
+    #[serde(default)]
+    pub destination: Option<u16>,
+    /// The Time in Seconds since the Unix Epoch when the Funds left the Account
+    #[serde(default)]
+    pub processed_at: Option<u64>,
+    /// The Currency of the Amount. Without Currency the primary Balances of the Account are used
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// The Currency into which an "_exchange_" converts the Amount
+    #[serde(default)]
+    pub target: Option<String>,
+    /// The Exchange Rate applied by an "_exchange_"
+    #[serde(default)]
+    pub rate: Option<f64>,
+    /// The Spread in Percent kept by an "_exchange_"
+    #[serde(default)]
+    pub spread: Option<f64>,
+    /// The converted Amount credited in the `target` Currency
+    #[serde(default)]
+    pub converted: Option<f64>,
+    /// The Time in Seconds since the Unix Epoch when the Transaction was booked on the Account
+    #[serde(default)]
+    pub booked_at: Option<u64>,
+    /// The Number of Movements on the Account left until the open Dispute expires
+    #[serde(default)]
+    pub expires_after: Option<u32>,
+    /// The Time in Seconds since the Unix Epoch when the open Dispute expires
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// The Dispute Cases opened on the Transaction
+    #[serde(default)]
+    pub cases: Vec<DisputeCase>,
+}
+
+//==============================================================================
+// Structure TransactionRow Declaration
+
+/// The Columns of a `Transaction` in the Transactions CSV Export
+#[derive(Debug, Serialize)]
+struct TransactionRow<'a> {
+    #[serde(rename = "type")]
+    tx_type: &'a str,
+    client: u16,
+    tx: u32,
+    amount: f64,
+    status: i8,
+}
+
+//==============================================================================
+// Structure HistoryRow Declaration
+
+/// The Columns of a `Transaction` in the Transaction History CSV Export
+#[derive(Debug, Serialize)]
+struct HistoryRow<'a> {
+    #[serde(rename = "type")]
+    tx_type: &'a str,
+    client: u16,
+    tx: u32,
+    amount: f64,
+    status: i8,
+    operator: Option<&'a str>,
+    reason: Option<&'a str>,
 }
 
 //==============================================================================
 // Structure Transaction Implementation
 
-impl Clone for Transaction {
+impl Transaction {
     /*----------------------------------------------------------------------------
-     * Administration Methods
+     * Constructors
      */
 
-    fn clone(&self) -> Transaction {
+    /// Creates a new Transaction with the Amount rounded to 4 Decimals.
+    /// It is pending and has no Disputes, Refunds, Clearing or Currency yet
+    pub fn base(tx_type: &str, client_id: u16, transaction_id: u32, famount: f64) -> Transaction {
+        Transaction {
+            tx_type: String::from(tx_type),
+            client: client_id,
+            tx: transaction_id,
+            amount: (famount * 10000.0).round() / 10000.0,
+            status: 0,
+            operator: None,
+            reason: None,
+            clearing: false,
+            clears_after: None,
+            clears_at: None,
+            disputed: 0.0,
+            held: None,
+            charged_back: 0.0,
+            debt: None,
+            original: None,
+            refunded: 0.0,
+            destination: None,
+            processed_at: None,
+            currency: None,
+            target: None,
+            rate: None,
+            spread: None,
+            converted: None,
+            booked_at: None,
+            expires_after: None,
+            expires_at: None,
+            cases: Vec::new(),
+        }
+    }
+
+    /// Creates the Record of an administrative Operation on the Account.
+    /// It is accepted and processed from the Start
+    pub fn new_admin(
+        tx_type: &str,
+        client_id: u16,
+        transaction_id: u32,
+        famount: f64,
+        soperator: &str,
+        sreason: &str,
+    ) -> Transaction {
+        Transaction {
+            status: 1,
+            operator: Some(String::from(soperator)),
+            reason: Some(String::from(sreason)).filter(|sreason| !sreason.is_empty()),
+            ..Transaction::base(tx_type, client_id, transaction_id, famount)
+        }
+    }
+
+    /// Creates the Record of a Fee charged on the Movement of the Type `sfee_type`
+    /// on the Transaction `original_id`
+    pub fn new_fee(
+        client_id: u16,
+        transaction_id: u32,
+        famount: f64,
+        sfee_type: &str,
+        original_id: u32,
+    ) -> Transaction {
         Transaction {
-            tx_type: String::from(self.tx_type.as_str()),
+            reason: Some(String::from(sfee_type)),
+            original: Some(original_id),
+            ..Transaction::base("fee", client_id, transaction_id, famount)
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Records the Funds held for the disputed Portion.
+    /// They are only kept when they differ from the disputed Portion
+    pub fn set_held(&mut self, fheld: f64) {
+        let fheld = (fheld * 10000.0).round() / 10000.0;
+
+        if fheld != self.disputed {
+            self.held = Some(fheld);
+        } else {
+            self.held = None;
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the Portion of the Amount which is under Dispute.
+    /// Transactions disputed before partial Disputes were recorded are disputed as a whole
+    pub fn get_disputed(&self) -> f64 {
+        if self.status == -2 && self.disputed == 0.0 {
+            self.amount
+        } else {
+            self.disputed
+        }
+    }
+
+    /// Returns the Client whose Account holds the Disputes of the Transaction.
+    /// The Funds of a "_transfer_" are disputed on its Destination Account
+    pub fn get_holder(&self) -> u16 {
+        match self.destination {
+            Some(idestination) if self.tx_type == "transfer" => idestination,
+            _ => self.client,
+        }
+    }
+
+    /// Returns the Funds placed on `held` for the disputed Portion
+    pub fn get_held(&self) -> f64 {
+        self.held.unwrap_or_else(|| self.get_disputed())
+    }
+
+    /// Returns the Portion of the Amount which can still be disputed
+    pub fn get_disputable(&self) -> f64 {
+        ((self.amount - self.charged_back - self.refunded - self.get_disputed()) * 10000.0).round()
+            / 10000.0
+    }
+
+    fn to_row(&self) -> TransactionRow<'_> {
+        TransactionRow {
+            tx_type: self.tx_type.as_str(),
             client: self.client,
             tx: self.tx,
             amount: self.amount,
             status: self.status,
         }
     }
+
+    fn to_history_row(&self) -> HistoryRow<'_> {
+        HistoryRow {
+            tx_type: self.tx_type.as_str(),
+            client: self.client,
+            tx: self.tx,
+            amount: self.amount,
+            status: self.status,
+            operator: self.operator.as_deref(),
+            reason: self.reason.as_deref(),
+        }
+    }
 }
 
 //==============================================================================
 // Structure Movement Declaration
 
-#[derive(Debug, Deserialize)]
+/// One Line of the Movement Feed.
+/// A "_dispute_", "_resolve_" or "_chargeback_" is requested by the `client` of the referenced
+/// Transaction. On a "_transfer_" it changes the Destination Account, which received the Funds:
+/// the Dispute holds the Funds there, the Resolve releases them there and the Chargeback
+/// takes them from the Destination, locks the Destination and returns them to the `client`
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Movement {
     #[serde(rename = "type")]
     pub tx_type: String,
     pub client: u16,
     pub tx: u32,
     pub amount: Option<f64>,
+    /// The Transaction referenced by a "_refund_"
+    #[serde(default)]
+    pub original: Option<u32>,
+    /// The Client which receives a "_transfer_"
+    #[serde(default)]
+    pub destination: Option<u16>,
+    /// The Currency of the Amount
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// The Currency into which an "_exchange_" converts the Amount
+    #[serde(default)]
+    pub target: Option<String>,
+    /// The Time or Sequence Number which orders the Movements within the Reorder Window
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+    /// The Reason Code of a Dispute, Resolve or Chargeback
+    #[serde(default)]
+    pub reason_code: Option<String>,
+    /// The external Case Reference of a Dispute, Resolve or Chargeback
+    #[serde(default)]
+    pub case: Option<String>,
 }
 
 //==============================================================================
 // Structure Movement Implementation
 
 impl Movement {
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the Currency Code in upper Case. An empty Currency is no Currency
+    pub fn get_currency(&self) -> Option<String> {
+        self.currency
+            .as_ref()
+            .map(|scurrency| scurrency.trim().to_uppercase())
+            .filter(|scurrency| !scurrency.is_empty())
+    }
+
+    /// Returns the Target Currency Code of an "_exchange_" in upper Case
+    pub fn get_target(&self) -> Option<String> {
+        self.target
+            .as_ref()
+            .map(|starget| starget.trim().to_uppercase())
+            .filter(|starget| !starget.is_empty())
+    }
+
+    /// Checks whether the Movement refers to the Currency of the Transaction.
+    /// A Movement without Currency matches any Transaction
+    pub fn matches_currency(&self, txrecord: &Transaction) -> bool {
+        match self.get_currency() {
+            Some(scurrency) => txrecord.currency.as_deref() == Some(scurrency.as_str()),
+            None => true,
+        }
+    }
+
     /*----------------------------------------------------------------------------
      * Administration Methods
      */
@@ -62,11 +344,8 @@ impl Movement {
                     Some(amnt) => {
                         if amnt.is_sign_positive() {
                             Some(Transaction {
-                                tx_type: String::from(self.tx_type.as_str()),
-                                client: self.client,
-                                tx: self.tx,
-                                amount: (amnt * 10000.0).round() / 10000.0,
-                                status: 0,
+                                currency: self.get_currency(),
+                                ..Transaction::base(&self.tx_type, self.client, self.tx, amnt)
                             })
                         } else
                         //Invalid Negative Amount
@@ -91,6 +370,78 @@ impl Movement {
                     }
                 } //match self.amount
             }
+            "transfer" => match (self.destination, self.amount) {
+                (Some(idestination), Some(amnt))
+                    if idestination != self.client && amnt.is_finite() && amnt > 0.0 =>
+                {
+                    Some(Transaction {
+                        destination: Some(idestination),
+                        currency: self.get_currency(),
+                        ..Transaction::base(&self.tx_type, self.client, self.tx, amnt)
+                    })
+                }
+                (odestination, oamount) => {
+                    eprintln!(
+                        "Movement Processing Error: Transfer to '{:?}' of '{:?}' is invalid. Movement will be omitted.",
+                        odestination, oamount
+                    );
+
+                    //Do not create an Transaction Object
+                    None
+                }
+            },
+            "exchange" => match (self.get_currency(), self.get_target(), self.amount) {
+                (Some(scurrency), Some(starget), Some(amnt))
+                    if scurrency != starget && amnt.is_finite() && amnt > 0.0 =>
+                {
+                    Some(Transaction {
+                        currency: Some(scurrency),
+                        target: Some(starget),
+                        ..Transaction::base(&self.tx_type, self.client, self.tx, amnt)
+                    })
+                }
+                (ocurrency, otarget, oamount) => {
+                    eprintln!(
+                        "Movement Processing Error: Exchange of '{:?}' from '{:?}' to '{:?}' is invalid. Movement will be omitted.",
+                        oamount, ocurrency, otarget
+                    );
+
+                    //Do not create an Transaction Object
+                    None
+                }
+            },
+            "refund" => match (self.original, self.amount) {
+                (Some(ioriginal), oamount) if oamount.is_none_or(|a| a > 0.0) => {
+                    //Without Amount the Refund is determined by the original Transaction
+                    Some(Transaction {
+                        currency: self.get_currency(),
+                        original: Some(ioriginal),
+                        ..Transaction::base(
+                            &self.tx_type,
+                            self.client,
+                            self.tx,
+                            oamount.unwrap_or(0.0),
+                        )
+                    })
+                }
+                (None, _) => {
+                    eprintln!(
+                        "Movement Processing Error: Original Transaction is missing. Movement will be omitted."
+                    );
+
+                    //Do not create an Transaction Object
+                    None
+                }
+                (_, oamount) => {
+                    eprintln!(
+                        "Movement Processing Error: Amount '{:?}' is invalid. Movement will be omitted.",
+                        oamount
+                    );
+
+                    //Do not create an Transaction Object
+                    None
+                }
+            },
             _ => {
                 //Do not create an Transaction Object
                 None
@@ -125,7 +476,7 @@ impl MovementImportError {
         error_code: i8,
     ) -> MovementImportError {
         MovementImportError {
-            ok_count: ok_count,
+            ok_count,
             lines: error_lines,
             message: error_message,
             code: error_code,
@@ -147,10 +498,18 @@ pub struct TransactionImportError {
 //==============================================================================
 // Structure TransactionFactory Declaration
 
+/// The first Transaction ID reserved for internally created Transactions.
+/// Movements of the Feed must use lower IDs
+pub const INTERNAL_ID_START: u32 = 0xF000_0000;
+
 #[derive(Debug)]
 pub struct TransactionFactory {
     pub vmovements: Vec<Movement>,
     pub lsttransactions: HashMap<u32, Transaction>,
+    /// The next Transaction ID for internally created Transactions
+    pub inextinternal: u32,
+    /// The Transaction IDs of each Client in the Order they were added
+    lstclients: HashMap<u16, Vec<u32>>,
 }
 
 //==============================================================================
@@ -176,6 +535,8 @@ impl TransactionFactory {
         let factory = TransactionFactory {
             vmovements: Vec::new(),
             lsttransactions: HashMap::new(),
+            inextinternal: INTERNAL_ID_START,
+            lstclients: HashMap::new(),
         };
 
         //Return the New TransactionFactory Object
@@ -191,6 +552,8 @@ impl TransactionFactory {
         let mut factory = TransactionFactory {
             vmovements: Vec::new(),
             lsttransactions: HashMap::new(),
+            inextinternal: INTERNAL_ID_START,
+            lstclients: HashMap::new(),
         };
 
         match factory.import_transactions_str(stransactions_csv, bheaders, bdebug, bquiet) {
@@ -215,6 +578,8 @@ impl TransactionFactory {
         let mut factory = TransactionFactory {
             vmovements: Vec::new(),
             lsttransactions: HashMap::new(),
+            inextinternal: INTERNAL_ID_START,
+            lstclients: HashMap::new(),
         };
 
         match factory.import_csv_str(smovements_csv, bheaders, bdebug, bquiet) {
@@ -234,14 +599,38 @@ impl TransactionFactory {
      * Administration Methods
      */
 
+    /// Adds the Transaction and indexes it under its Client
     pub fn add_transaction(&mut self, transaction: Transaction) -> Option<&mut Transaction> {
         let transaction_id = transaction.tx;
-
-        self.lsttransactions.insert(transaction.tx, transaction);
+        let client_id = transaction.client;
+
+        match self.lsttransactions.insert(transaction_id, transaction) {
+            Some(txold) if txold.client == client_id => {}
+            _ => self
+                .lstclients
+                .entry(client_id)
+                .or_default()
+                .push(transaction_id),
+        }
 
         self.lsttransactions.get_mut(&transaction_id)
     }
 
+    /// Takes the next Transaction ID for internally created Transactions.
+    /// They are counted up from `INTERNAL_ID_START` which the Movement Feed cannot use.
+    /// IDs of older States that counted down from the highest ID are skipped
+    pub fn next_internal_id(&mut self) -> u32 {
+        let mut itxid = self.inextinternal;
+
+        while self.lsttransactions.contains_key(&itxid) {
+            itxid += 1;
+        }
+
+        self.inextinternal = itxid + 1;
+
+        itxid
+    }
+
     pub fn import_csv_bytes(
         &mut self,
         vmovements_csv: &[u8],
@@ -249,11 +638,13 @@ impl TransactionFactory {
         bdebug: bool,
         bquiet: bool,
     ) -> Result<u32, MovementImportError> {
+        //The Column "original" is only needed for Refunds
         let mut rdr = ReaderBuilder::new()
             .has_headers(bheaders)
+            .flexible(true)
             .trim(Trim::All)
             .from_reader(vmovements_csv);
-        let mut iter = rdr.deserialize();
+        let iter = rdr.deserialize();
         let mut serr = String::new();
         let mut verrlines: Vec<u32> = Vec::new();
         let mut icsvline = 1;
@@ -265,19 +656,27 @@ impl TransactionFactory {
             icsvline += 1;
         }
 
-        while let Some(result) = iter.next() {
+        for result in iter {
             match result {
                 Ok(r) => {
                     let mvrecord: Movement = r;
-                    let otx = mvrecord.build_transaction();
 
-                    self.vmovements.push(mvrecord);
+                    if mvrecord.tx >= INTERNAL_ID_START {
+                        //The ID is reserved for internally created Transactions
+                        serr.push_str(&format!("Reserved Transaction ID: '{}'; ", mvrecord.tx));
+                        verrlines.push(icsvline);
+                        ierr = 1;
+                    } else {
+                        let otx = mvrecord.build_transaction();
 
-                    if let Some(txrec) = otx {
-                        self.lsttransactions.insert(txrec.tx, txrec);
-                    }
+                        self.vmovements.push(mvrecord);
 
-                    icount += 1;
+                        if let Some(txrec) = otx {
+                            self.add_transaction(txrec);
+                        }
+
+                        icount += 1;
+                    } //if mvrecord.tx >= INTERNAL_ID_START
                 }
                 Err(e) => {
                     serr.push_str(&format!("Parse Error: '{:?}'; ", e));
@@ -288,7 +687,7 @@ impl TransactionFactory {
 
             //Keep track of the Input Lines
             icsvline += 1;
-        } //while let Some(result) = iter.next()
+        } //for result in iter
 
         if bdebug && !bquiet {
             eprintln!("Movements CSV Import Count: '{}'", icount);
@@ -322,7 +721,7 @@ impl TransactionFactory {
             .has_headers(bheaders)
             .trim(Trim::All)
             .from_reader(stransactions_csv.as_bytes());
-        let mut iter = rdr.deserialize();
+        let iter = rdr.deserialize();
         let mut serr = String::new();
         let mut verrlines: Vec<u32> = Vec::new();
         let mut icsvline = 1;
@@ -334,12 +733,12 @@ impl TransactionFactory {
             icsvline += 1;
         }
 
-        while let Some(result) = iter.next() {
+        for result in iter {
             match result {
                 Ok(r) => {
                     let txrecord: Transaction = r;
 
-                    self.lsttransactions.insert(txrecord.tx, txrecord);
+                    self.add_transaction(txrecord);
 
                     icount += 1;
                 }
@@ -352,7 +751,7 @@ impl TransactionFactory {
 
             //Keep track of the Input Lines
             icsvline += 1;
-        } //while let Some(result) = iter.next()
+        } //for result in iter
 
         if bdebug && !bquiet {
             eprintln!("Transactions CSV Import Count: '{}'", icount);
@@ -374,19 +773,42 @@ impl TransactionFactory {
      * Consultation Methods
      */
 
+    /// Returns the Transactions of the Client in the Order they were added
+    pub fn get_client_transactions(&self, client_id: u16) -> impl Iterator<Item = &Transaction> {
+        self.lstclients
+            .get(&client_id)
+            .into_iter()
+            .flatten()
+            .filter_map(move |itxid| self.lsttransactions.get(itxid))
+            .filter(move |txrec| txrec.client == client_id)
+    }
+
+    /// Returns the Refunds of the original Transaction ordered by Transaction ID
+    pub fn get_refunds(&self, original_id: u32) -> Vec<&Transaction> {
+        let mut vrefunds: Vec<&Transaction> = self
+            .lsttransactions
+            .values()
+            .filter(|txrec| txrec.tx_type == "refund" && txrec.original == Some(original_id))
+            .collect();
+
+        vrefunds.sort_by_key(|txrec| txrec.tx);
+
+        vrefunds
+    }
+
     #[allow(unused_variables)]
     pub fn export_transactions_csv(&self, bdebug: bool, bquiet: bool) -> String {
         let mut wtr = WriterBuilder::new().from_writer(vec![]);
 
         for txrec in self.lsttransactions.iter() {
-            match wtr.serialize(txrec.1) {
+            match wtr.serialize(txrec.1.to_row()) {
                 Ok(_) => {}
                 Err(e) => {
                     if !bquiet {
                         eprintln!("Transaction CSV Export Error: '{:?}'", e)
                     }
                 }
-            } //match wtr.serialize(txrec.1)
+            } //match wtr.serialize(txrec.1.to_row())
         } //for txrec in self.lsttransactions.iter()
 
         let data = match wtr.into_inner() {
@@ -415,4 +837,148 @@ impl TransactionFactory {
 
         data
     }
+
+    /// Exports the charged Fees summed up per Movement Type with the Columns `type,count,income`
+    #[allow(unused_variables)]
+    pub fn export_fee_report_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut lstincome: BTreeMap<&str, (u32, f64)> = BTreeMap::new();
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        for txrec in self
+            .lsttransactions
+            .values()
+            .filter(|txrec| txrec.tx_type == "fee" && txrec.status == 1)
+        {
+            let income = lstincome
+                .entry(txrec.reason.as_deref().unwrap_or(""))
+                .or_insert((0, 0.0));
+
+            income.0 += 1;
+            income.1 = ((income.1 + txrec.amount) * 10000.0).round() / 10000.0;
+        }
+
+        if let Err(e) = wtr.write_record(["type", "count", "income"]) {
+            if !bquiet {
+                eprintln!("Fee Report CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for (stype, (icount, fincome)) in lstincome.iter() {
+            if let Err(e) = wtr.write_record([
+                stype.to_string(),
+                icount.to_string(),
+                format!("{:?}", fincome),
+            ]) {
+                if !bquiet {
+                    eprintln!("Fee Report CSV Export Error: '{:?}'", e)
+                }
+            }
+        }
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Fee Report CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+
+    /// Exports the open and closed Dispute Cases ordered by Transaction ID with the Columns
+    /// `client,tx,case,reason_code,state,outcome,opened_at,closed_at,history`
+    #[allow(unused_variables)]
+    pub fn export_cases_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+        let mut vtransactions: Vec<&Transaction> = self
+            .lsttransactions
+            .values()
+            .filter(|txrec| !txrec.cases.is_empty())
+            .collect();
+
+        vtransactions.sort_by_key(|txrec| txrec.tx);
+
+        if let Err(e) = wtr.write_record([
+            "client",
+            "tx",
+            "case",
+            "reason_code",
+            "state",
+            "outcome",
+            "opened_at",
+            "closed_at",
+            "history",
+        ]) {
+            if !bquiet {
+                eprintln!("Dispute Cases CSV Export Error: '{:?}'", e)
+            }
+        }
+
+        for txrec in vtransactions.iter() {
+            for dcase in txrec.cases.iter() {
+                if let Err(e) = wtr.write_record([
+                    txrec.client.to_string(),
+                    txrec.tx.to_string(),
+                    dcase.case.clone().unwrap_or_default(),
+                    dcase.reason_code.clone().unwrap_or_default(),
+                    String::from(if dcase.is_open() { "open" } else { "closed" }),
+                    dcase.outcome.clone().unwrap_or_default(),
+                    dcase.opened_at.to_string(),
+                    dcase
+                        .closed_at
+                        .map(|iclosed| iclosed.to_string())
+                        .unwrap_or_default(),
+                    dcase.format_history(),
+                ]) {
+                    if !bquiet {
+                        eprintln!("Dispute Cases CSV Export Error: '{:?}'", e)
+                    }
+                }
+            } //for dcase in txrec.cases.iter()
+        } //for txrec in vtransactions.iter()
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Dispute Cases CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
+
+    /// Exports all Transactions with all their Details ordered by Transaction ID
+    #[allow(unused_variables)]
+    pub fn export_history_csv(&self, bdebug: bool, bquiet: bool) -> String {
+        let mut vtransactions: Vec<&Transaction> = self.lsttransactions.values().collect();
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        vtransactions.sort_by_key(|txrec| txrec.tx);
+
+        for txrec in vtransactions.iter() {
+            if let Err(e) = wtr.serialize(txrec.to_history_row()) {
+                if !bquiet {
+                    eprintln!("Transaction CSV Export Error: '{:?}'", e)
+                }
+            }
+        }
+
+        match wtr.into_inner() {
+            Ok(iwtr) => String::from_utf8(iwtr).unwrap_or_default(),
+            Err(e) => {
+                if !bquiet {
+                    eprintln!("Transactions CSV Export Error: '{:?}'", e);
+                }
+
+                //Return empty String
+                String::new()
+            }
+        } //match wtr.into_inner()
+    }
 }
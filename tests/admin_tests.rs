@@ -0,0 +1,265 @@
+use client_accounting::app::importer::MovementImporter;
+
+#[cfg(test)]
+mod admin_tests {
+    use std::fs;
+
+    use client_accounting::model::account::AccountFactory;
+    use client_accounting::model::transaction::{TransactionFactory, INTERNAL_ID_START};
+
+    /// ### Test `unlock_after_chargeback()`
+    /// This test locks an Account with a "_chargeback_" of a "_deposit_"
+    /// The next "_deposit_" is refused
+    /// After the Account is unlocked by an Operator a "_deposit_" is accepted
+    /// The Unlock is recorded in the Transaction History with the Operator
+    #[test]
+    fn unlock_after_chargeback() {
+        //-------------------------------------
+        //Test Unlock an Account locked by a Chargeback
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nchargeback,11,5,\ndeposit,11,6,2.0\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,0.0,0.0,0.0,true\n"
+        );
+
+        let itxid = imp
+            .unlock_account(11, "operator-7", "chargeback settled")
+            .unwrap();
+
+        assert_eq!(itxid, INTERNAL_ID_START);
+        assert!(imp.unlock_account(11, "operator-7", "").is_err());
+
+        assert_eq!(imp.import_movements_str("deposit,11,7,3.0\n", false), 0);
+
+        let saccounts = imp.export_accounts_str();
+        let shistory = imp.export_history_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", shistory.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,3.0,0.0,3.0,false\n"
+        );
+        assert_eq!(
+            shistory.as_str(),
+            "type,client,tx,amount,status,operator,reason\n\
+            deposit,11,5,1.1,-1,,\n\
+            deposit,11,6,2.0,0,,\n\
+            deposit,11,7,3.0,1,,\n\
+            unlock,11,4026531840,0.0,1,operator-7,chargeback settled\n"
+        );
+    }
+
+    /// ### Test `manual_adjustments()`
+    /// This test credits and debits an Account manually
+    /// A Debit exceeding the `available` Funds fails but is recorded as invalid
+    /// Adjustments without Reason or Operator are refused
+    #[test]
+    fn manual_adjustments() {
+        //-------------------------------------
+        //Test Credit and Debit Adjustments
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,11,5,1.1\n", true),
+            0
+        );
+
+        assert!(imp.adjust_account(11, 2.0, "operator-7", "").is_err());
+        assert!(imp.adjust_account(11, 2.0, "", "goodwill").is_err());
+        assert!(imp
+            .adjust_account(12, 2.0, "operator-7", "goodwill")
+            .is_err());
+
+        assert_eq!(
+            imp.adjust_account(11, 2.0, "operator-7", "goodwill")
+                .unwrap(),
+            INTERNAL_ID_START
+        );
+
+        let oerr = imp.adjust_account(11, -5.0, "operator-7", "fee correction");
+
+        assert!(oerr.is_err());
+        assert_eq!(oerr.unwrap_err().code, 2);
+        assert_eq!(
+            imp.adjust_account(11, -0.6, "operator-7", "fee correction")
+                .unwrap(),
+            INTERNAL_ID_START + 2
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let shistory = imp.export_history_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", shistory.as_str());
+
+        let accfact = AccountFactory::from_str(saccounts.as_str(), true, true, false);
+        let acc = accfact.lstaccounts.get(&11).unwrap();
+
+        assert_eq!(acc.available, 2.5);
+        assert_eq!(acc.total, 2.5);
+
+        let txfact = TransactionFactory::from_str(shistory.as_str(), true, true, false);
+        let txcredit = txfact.lsttransactions.get(&INTERNAL_ID_START).unwrap();
+        let txfailed = txfact
+            .lsttransactions
+            .get(&(INTERNAL_ID_START + 1))
+            .unwrap();
+        let txdebit = txfact
+            .lsttransactions
+            .get(&(INTERNAL_ID_START + 2))
+            .unwrap();
+
+        assert_eq!(txcredit.tx_type, "credit");
+        assert_eq!(txcredit.operator.as_deref(), Some("operator-7"));
+        assert_eq!(txcredit.reason.as_deref(), Some("goodwill"));
+        assert_eq!(txfailed.tx_type, "debit");
+        assert_eq!(txfailed.status, -1);
+        assert_eq!(txdebit.amount, 0.6);
+        assert_eq!(txdebit.status, 1);
+    }
+
+    /// ### Test `freeze_without_chargeback()`
+    /// This test freezes an Account without a Chargeback
+    /// All following Movements are refused
+    /// The Account cannot be frozen twice
+    #[test]
+    fn freeze_without_chargeback() {
+        //-------------------------------------
+        //Test Freeze an Account
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,11,5,1.1\n", true),
+            0
+        );
+
+        assert!(imp
+            .freeze_account(11, "operator-7", "suspicious activity")
+            .is_ok());
+        assert!(imp.freeze_account(11, "operator-7", "").is_err());
+
+        assert_eq!(imp.import_movements_str("withdrawal,11,6,0.5\n", false), 0);
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,1.1,0.0,1.1,true\n"
+        );
+    }
+
+    /// ### Test `reserved_transaction_id()`
+    /// This test imports a "_deposit_" with a Transaction ID from the Range
+    /// of the internally created Transactions
+    /// The Movement is refused as an invalid Line and does not reach the Account
+    #[test]
+    fn reserved_transaction_id() {
+        //-------------------------------------
+        //Test Refuse a Movement with a reserved Transaction ID
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount
+deposit,11,5,1.1
+deposit,11,4026531840,2.0
+",
+                true
+            ),
+            1
+        );
+        assert!(imp.get_transaction(INTERNAL_ID_START).is_none());
+
+        assert_eq!(
+            imp.adjust_account(11, 2.0, "operator-7", "goodwill")
+                .unwrap(),
+            INTERNAL_ID_START
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,3.1,0.0,3.1,false\n"
+        );
+    }
+
+    /// ### Test `internal_id_after_restart()`
+    /// This test loads a State with an Operation counted down from the highest ID
+    /// The next Operations count up from `INTERNAL_ID_START`
+    /// and continue after the State is saved and loaded again
+    #[test]
+    fn internal_id_after_restart() {
+        //-------------------------------------
+        //Test Internal Transaction IDs over two Runs
+
+        let pdirectory = std::env::temp_dir().join(format!(
+            "client-accounting-internal-id-{}",
+            std::process::id()
+        ));
+        let sstatefile = pdirectory.join("state.json");
+
+        fs::create_dir_all(&pdirectory).unwrap();
+        fs::write(
+            &sstatefile,
+            "{\"offsets\":[],\"accounts\":[\
+            {\"client\":11,\"available\":3.0,\"held\":0.0,\"total\":3.0,\"locked\":false}],\
+            \"transactions\":[\
+            {\"type\":\"deposit\",\"client\":11,\"tx\":5,\"amount\":1.0,\"status\":1},\
+            {\"type\":\"credit\",\"client\":11,\"tx\":4294967295,\"amount\":2.0,\"status\":1}]}",
+        )
+        .unwrap();
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(
+            imp.adjust_account(11, 1.0, "operator-7", "goodwill")
+                .unwrap(),
+            INTERNAL_ID_START
+        );
+        assert!(imp.save_state().is_ok());
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(
+            imp.adjust_account(11, 1.0, "operator-7", "goodwill")
+                .unwrap(),
+            INTERNAL_ID_START + 1
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,5.0,0.0,5.0,false\n"
+        );
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::policy::ClearingPolicy;
+
+use std::fs;
+
+#[cfg(test)]
+mod audit_tests {
+    use super::*;
+
+    /// ### Test `audit_processed_movements()`
+    /// This test processes clearing Deposits, partial Disputes, a disputed Withdrawal,
+    /// a Transfer, a Chargeback and Movements in "_EUR_"
+    /// The Audit finds no Violations
+    #[test]
+    fn audit_processed_movements() {
+        //-------------------------------------
+        //Test Audit of a consistent State
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clearing_policy(ClearingPolicy::Movements(2));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,10.0,,,\n\
+                deposit,1,2,6.0,,,EUR\n\
+                dispute,1,1,4.0,,,\n\
+                deposit,2,3,8.0,,,\n\
+                withdrawal,1,4,2.0,,,EUR\n\
+                dispute,1,4,,,,EUR\n\
+                deposit,2,5,1.0,,,\n\
+                deposit,2,6,1.0,,,\n\
+                transfer,2,7,3.0,,1,\n\
+                dispute,2,3,2.0,,,\n\
+                chargeback,2,3,,,,\n",
+                true
+            ),
+            0
+        );
+
+        let report = imp.audit();
+
+        println!("{}", report.export_csv(true, false));
+
+        assert!(report.is_clean());
+    }
+
+    /// ### Test `audit_disputed_transfer()`
+    /// This test disputes a Transfer from Account (id: '1') to Account (id: '2')
+    /// The Funds are held on the Destination Account
+    /// The Audit finds no `held_mismatch` on either Account
+    #[test]
+    fn audit_disputed_transfer() {
+        //-------------------------------------
+        //Test Audit of a disputed Transfer
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,1,1,10.0,,\n\
+                deposit,2,2,1.0,,\n\
+                transfer,1,3,4.0,,2\n\
+                dispute,1,3,,,\n",
+                true
+            ),
+            0
+        );
+
+        let report = imp.audit();
+
+        println!("{}", report.export_csv(true, false));
+
+        assert_eq!(imp.get_transaction(3).unwrap().status, -2);
+        assert!(report.is_clean());
+    }
+
+    /// ### Test `audit_charged_back_transfer()`
+    /// This test charges back a Transfer which locks the Destination Account (id: '2')
+    /// The Audit finds no `locked_without_chargeback` on the Destination Account
+    #[test]
+    fn audit_charged_back_transfer() {
+        //-------------------------------------
+        //Test Audit of a charged back Transfer
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,1,1,10.0,,\n\
+                transfer,1,3,4.0,,2\n\
+                dispute,1,3,,,\n\
+                chargeback,1,3,,,\n",
+                true
+            ),
+            0
+        );
+
+        let report = imp.audit();
+
+        println!("{}", report.export_csv(true, false));
+
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+        assert!(report.is_clean());
+    }
+
+    /// ### Test `audit_broken_state()`
+    /// This test loads a State File with broken Balances, an unknown Status,
+    /// a Transaction without Account and a locked Account without Chargeback
+    /// Each Violation is reported with its Client and Transaction
+    #[test]
+    fn audit_broken_state() {
+        //-------------------------------------
+        //Test Audit of an inconsistent State
+
+        let pdirectory = std::env::temp_dir().join(format!(
+            "client-accounting-audit-broken-{}",
+            std::process::id()
+        ));
+        let sstatefile = pdirectory.join("state.json");
+
+        fs::create_dir_all(&pdirectory).unwrap();
+        fs::write(
+            &sstatefile,
+            "{\"offsets\":[],\"accounts\":[\
+            {\"client\":1,\"available\":5.0,\"held\":2.0,\"total\":8.0,\"locked\":false},\
+            {\"client\":2,\"available\":3.0,\"held\":0.0,\"total\":3.0,\"locked\":true}],\
+            \"transactions\":[\
+            {\"type\":\"deposit\",\"client\":1,\"tx\":1,\"amount\":8.0,\"status\":1},\
+            {\"type\":\"deposit\",\"client\":2,\"tx\":2,\"amount\":3.0,\"status\":5},\
+            {\"type\":\"deposit\",\"client\":3,\"tx\":3,\"amount\":1.0,\"status\":1}]}",
+        )
+        .unwrap();
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imp.load_state().is_ok());
+
+        let report = imp.audit();
+
+        assert!(!report.is_clean());
+        assert_eq!(
+            report.export_csv(true, false).as_str(),
+            "check,client,tx,message\n\
+            total_mismatch,1,,Total '8' differs from Available and Held '7'\n\
+            held_mismatch,1,,Held '2' differs from the disputed and clearing Funds '0'\n\
+            locked_without_chargeback,2,,Account is locked without Chargeback or Freeze\n\
+            invalid_status,2,2,Status '5' is unknown\n\
+            orphan_transaction,3,3,Account (id: '3') does not exist\n"
+        );
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+
+    /// ### Test `audit_tampered_state()`
+    /// This test raises the Balances of Account (id: '1') in a saved State File with a Journal
+    /// No Opening Entries are booked for the Difference
+    /// The Audit reports the `journal_mismatch`
+    #[test]
+    fn audit_tampered_state() {
+        //-------------------------------------
+        //Test Audit of Balances changed outside the Journal
+
+        let pdirectory = std::env::temp_dir().join(format!(
+            "client-accounting-audit-tampered-{}",
+            std::process::id()
+        ));
+        let sstatefile = pdirectory.join("state.json");
+
+        fs::create_dir_all(&pdirectory).unwrap();
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,1,1,10.0\n", true),
+            0
+        );
+        assert!(imp.save_state().is_ok());
+
+        let sstate = fs::read_to_string(&sstatefile).unwrap();
+
+        assert!(sstate.contains("\"available\":10.0"));
+
+        fs::write(
+            &sstatefile,
+            sstate
+                .replace("\"available\":10.0", "\"available\":15.0")
+                .replace("\"total\":10.0", "\"total\":15.0"),
+        )
+        .unwrap();
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(imp.derive_account(1).unwrap().available, 10.0);
+        assert!(imp.get_journal().is_balanced());
+        assert_eq!(
+            imp.audit().export_csv(true, false).as_str(),
+            "check,client,tx,message\n\
+            journal_mismatch,1,,Available and Held '15 / 0' differ from the Journal '10 / 0'\n"
+        );
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::policy::ClearingPolicy;
+
+#[cfg(test)]
+mod clearing_tests {
+    use client_accounting::model::transaction::TransactionFactory;
+
+    /// ### Test `deposit_held_until_settled()`
+    /// This test places a "_deposit_" on hold until a "_settle_" Movement arrives
+    /// A "_withdrawal_" before the Settlement fails
+    /// After the Settlement the Funds are `available`
+    #[test]
+    fn deposit_held_until_settled() {
+        //-------------------------------------
+        //Test Deposit cleared by Settlement
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clearing_policy(super::ClearingPolicy::Settlement);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,6,0.5\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,0.0,1.1,1.1,false\n"
+        );
+
+        assert_eq!(
+            imp.import_movements_str("settle,11,5,\nwithdrawal,11,7,0.5\n", false),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let stransactions = imp.export_transactions_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", stransactions.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,0.6,0.0,0.6,false\n"
+        );
+
+        let txfact = TransactionFactory::from_str(stransactions.as_str(), true, true, false);
+
+        assert_eq!(txfact.lsttransactions.get(&5).unwrap().status, 1);
+        assert_eq!(txfact.lsttransactions.get(&6).unwrap().status, -1);
+        assert_eq!(txfact.lsttransactions.get(&7).unwrap().status, 1);
+    }
+
+    /// ### Test `deposit_cleared_after_movements()`
+    /// This test releases a "_deposit_" after 2 subsequent Movements on the Account
+    /// Movements of other Accounts do not count
+    #[test]
+    fn deposit_cleared_after_movements() {
+        //-------------------------------------
+        //Test Deposit cleared after Movements
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clearing_policy(super::ClearingPolicy::Movements(2));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\ndeposit,12,6,2.0\ndeposit,11,7,0.5\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_transactions_str()
+                .lines()
+                .filter(|sline| sline.ends_with(",2"))
+                .count(),
+            3
+        );
+
+        assert_eq!(imp.import_movements_str("withdrawal,11,8,0.1\n", false), 0);
+
+        let saccounts = imp.export_accounts_str();
+        let stransactions = imp.export_transactions_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", stransactions.as_str());
+
+        let txfact = TransactionFactory::from_str(stransactions.as_str(), true, true, false);
+
+        //The Withdrawal is refused because it clears the Deposit only after its Processing
+        assert_eq!(txfact.lsttransactions.get(&5).unwrap().status, 1);
+        assert_eq!(txfact.lsttransactions.get(&6).unwrap().status, 2);
+        assert_eq!(txfact.lsttransactions.get(&7).unwrap().status, 2);
+        assert_eq!(txfact.lsttransactions.get(&8).unwrap().status, -1);
+    }
+
+    /// ### Test `deposit_cleared_after_window()`
+    /// This test releases a "_deposit_" after its Clearing Window has passed
+    #[test]
+    fn deposit_cleared_after_window() {
+        //-------------------------------------
+        //Test Deposit cleared after Time Window
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clearing_policy(super::ClearingPolicy::Window(60));
+        imp.set_clock(1000);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,11,5,1.1\n", true),
+            0
+        );
+
+        imp.set_clock(1059);
+
+        assert_eq!(imp.process_clearing(), 0);
+
+        imp.set_clock(1060);
+
+        assert_eq!(imp.process_clearing(), 1);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,1.1,0.0,1.1,false\n"
+        );
+    }
+
+    /// ### Test `chargeback_on_clearing_deposit()`
+    /// This test replays `dispute_fails_tests::chargeback_insufficient_funds` with Clearing.
+    /// The "_withdrawal_" of the held Funds fails
+    /// So the Chargeback of the "_deposit_" can be satisfied
+    #[test]
+    fn chargeback_on_clearing_deposit() {
+        //-------------------------------------
+        //Test Chargeback a clearing Deposit
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clearing_policy(super::ClearingPolicy::Settlement);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nchargeback,11,9,\n",
+                true
+            ),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let stransactions = imp.export_transactions_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", stransactions.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,0.0,0.0,0.0,true\n"
+        );
+
+        let txfact = TransactionFactory::from_str(stransactions.as_str(), true, true, false);
+
+        assert_eq!(txfact.lsttransactions.get(&3).unwrap().status, -1);
+        assert_eq!(txfact.lsttransactions.get(&9).unwrap().status, -1);
+    }
+
+    /// ### Test `resolve_on_clearing_deposit()`
+    /// This test resolves a Dispute on a clearing "_deposit_"
+    /// The Funds stay on hold until the Settlement
+    #[test]
+    fn resolve_on_clearing_deposit() {
+        //-------------------------------------
+        //Test Resolve a clearing Deposit
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clearing_policy(super::ClearingPolicy::Settlement);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,9,1.1\ndispute,11,9,\nresolve,11,9,\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,0.0,1.1,1.1,false\n"
+        );
+
+        assert_eq!(imp.import_movements_str("settle,11,9,\n", false), 0);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,1.1,0.0,1.1,false\n"
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use client_accounting::app::consumer::{DirectoryLog, LogConsumer};
+use client_accounting::app::importer::MovementImporter;
+
+use std::fs;
+
+//==============================================================================
+// Auxiliary Functions
+
+fn create_log_directory(sname: &str) -> String {
+    let pdirectory = std::env::temp_dir().join(format!(
+        "client-accounting-{}-{}",
+        sname,
+        std::process::id()
+    ));
+
+    if pdirectory.exists() {
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+
+    pdirectory.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod log_tests {
+    use super::*;
+    use client_accounting::model::account::AccountFactory;
+
+    /// ### Test `partition_by_client()`
+    /// This test appends Movements of 3 Clients to a Log with 2 Partitions
+    /// Client (id: '2') must land in Partition `0`
+    /// Clients (id: '1' and '3') must land in Partition `1`
+    /// Nothing is committed yet
+    #[test]
+    fn partition_by_client() {
+        //-------------------------------------
+        //Test Movements are partitioned by Client
+
+        let sdirectory = create_log_directory("partition-by-client");
+        let mut log = DirectoryLog::create(&sdirectory, 2).unwrap();
+
+        assert_eq!(
+            log.append_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,1.0\ndeposit,2,2,2.0\ndeposit,3,3,3.0\nwithdrawal,1,4,0.5\n",
+                true
+            )
+            .unwrap(),
+            4
+        );
+
+        assert_eq!(log.get_partition_count(), 2);
+        assert_eq!(log.get_end_offset(0).unwrap(), 1);
+        assert_eq!(log.get_end_offset(1).unwrap(), 3);
+        assert_eq!(log.get_committed_offset(0), 0);
+        assert_eq!(log.get_committed_offset(1), 0);
+
+        let vrecords = log.poll(10).unwrap();
+
+        assert_eq!(vrecords.len(), 4);
+        assert_eq!(vrecords[0].partition, 0);
+        assert_eq!(vrecords[0].payload, "deposit,2,2,2.0");
+        assert_eq!(vrecords[3].partition, 1);
+        assert_eq!(vrecords[3].offset, 2);
+        assert_eq!(vrecords[3].payload, "withdrawal,1,4,0.5");
+
+        fs::remove_dir_all(&sdirectory).unwrap();
+    }
+
+    /// ### Test `append_by_header_names()`
+    /// This test appends Movements with a reordered Header and with a partial Header
+    /// which adds the Columns `currency` and `timestamp`
+    /// The Records must be stored in the standard Column Order
+    /// An unparsable Record must be reported and its Offset committed
+    /// Account (id: '1') must have `available` Funds (`1.5`)
+    /// Account (id: '2') must have `available` Funds (`2.0`) in "EUR"
+    #[test]
+    fn append_by_header_names() {
+        //-------------------------------------
+        //Test Columns are matched by the Header
+
+        let sdirectory = create_log_directory("append-by-header-names");
+        let mut log = DirectoryLog::create(&sdirectory, 1).unwrap();
+        let mut imp = MovementImporter::new();
+
+        imp.set_quiet(true);
+
+        assert_eq!(
+            log.append_movements_str(
+                "client,type,tx,amount\n1,deposit,1,2.0\n1,withdrawal,2,0.5\n",
+                true
+            )
+            .unwrap(),
+            2
+        );
+        assert_eq!(
+            log.append_movements_str(
+                "type,client,tx,amount,currency,timestamp\ndeposit,2,3,2.0,EUR,7\n",
+                true
+            )
+            .unwrap(),
+            1
+        );
+        assert!(log
+            .append_movements_str("client,type,tx,amount\nx,deposit,4,1.0\n", true)
+            .is_err());
+
+        let vrecords = log.poll(10).unwrap();
+
+        assert_eq!(vrecords.len(), 3);
+        assert_eq!(vrecords[0].payload, "deposit,1,1,2.0");
+        assert_eq!(vrecords[1].payload, "withdrawal,1,2,0.5");
+        assert_eq!(vrecords[2].payload, "deposit,2,3,2.0,,,EUR,,7");
+
+        //A broken Record written by another Producer
+        log.append(0, "deposit,x,5,1.0").unwrap();
+        log.seek(0, 0);
+
+        assert_eq!(imp.consume_log(&mut log, 10), 1);
+        assert_eq!(log.get_committed_offset(0), 4);
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        let accfact = AccountFactory::from_str(saccounts.as_str(), true, true, false);
+
+        assert_eq!(accfact.lstaccounts.get(&1).unwrap().available, 1.5);
+        assert!(imp
+            .export_currencies_str()
+            .contains("2,EUR,2.0,0.0,2.0,false\n"));
+
+        fs::remove_dir_all(&sdirectory).unwrap();
+    }
+
+    /// ### Test `resume_from_committed_offset()`
+    /// This test consumes Movements from a Log and commits the Offsets
+    /// After a Restart only the newly appended Movements are consumed
+    /// Account (id: '1') must have `available` Funds (`2.5`)
+    /// Account (id: '2') must have `available` Funds (`1.0`)
+    #[test]
+    fn resume_from_committed_offset() {
+        //-------------------------------------
+        //Test Consumer resumes from committed Offsets
+
+        let sdirectory = create_log_directory("resume-from-committed-offset");
+        let mut log = DirectoryLog::create(&sdirectory, 2).unwrap();
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        log.append_movements_str(
+            "type, client, tx, amount\ndeposit,1,1,1.0\ndeposit,2,2,2.0\ndeposit,1,3,2.0\n",
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(imp.consume_log(&mut log, 2), 0);
+        assert_eq!(log.get_committed_offset(0), 1);
+        assert_eq!(log.get_committed_offset(1), 2);
+
+        //Restart the Consumer on the same Directory
+        drop(log);
+
+        let mut log = DirectoryLog::open(&sdirectory).unwrap();
+
+        assert_eq!(log.get_position(0), 1);
+        assert_eq!(log.get_position(1), 2);
+
+        log.append_movements_str("withdrawal,1,4,0.5\nwithdrawal,2,5,1.0\n", false)
+            .unwrap();
+
+        assert_eq!(imp.consume_log(&mut log, 2), 0);
+        assert_eq!(log.get_committed_offset(0), 2);
+        assert_eq!(log.get_committed_offset(1), 3);
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        let accfact = AccountFactory::from_str(saccounts.as_str(), true, true, false);
+
+        assert_eq!(accfact.lstaccounts.get(&1).unwrap().available, 2.5);
+        assert_eq!(accfact.lstaccounts.get(&2).unwrap().available, 1.0);
+
+        fs::remove_dir_all(&sdirectory).unwrap();
+    }
+
+    /// ### Test `poll_continues_after_position()`
+    /// This test polls a Partition in small Steps while Records are appended
+    /// After a Seek back and after a Restart without Byte Positions in `offsets.csv`
+    /// the Records must be read again from the requested Offset
+    #[test]
+    fn poll_continues_after_position() {
+        //-------------------------------------
+        //Test Poll continues from the Reading Position
+
+        let sdirectory = create_log_directory("poll-continues-after-position");
+        let mut log = DirectoryLog::create(&sdirectory, 1).unwrap();
+
+        log.append_movements_str(
+            "type, client, tx, amount\ndeposit,1,1,1.0\ndeposit,1,2,2.0\n",
+            true,
+        )
+        .unwrap();
+
+        let vrecords = log.poll(1).unwrap();
+
+        assert_eq!(vrecords.len(), 1);
+        assert_eq!(vrecords[0].payload, "deposit,1,1,1.0");
+
+        log.append_movements_str("deposit,1,3,3.0\n", false)
+            .unwrap();
+
+        let vrecords = log.poll(10).unwrap();
+
+        assert_eq!(vrecords.len(), 2);
+        assert_eq!(vrecords[0].offset, 1);
+        assert_eq!(vrecords[0].payload, "deposit,1,2,2.0");
+        assert_eq!(vrecords[1].offset, 2);
+        assert_eq!(vrecords[1].payload, "deposit,1,3,3.0");
+        assert_eq!(log.poll(10).unwrap().len(), 0);
+
+        log.commit(0, 2).unwrap();
+        log.seek(0, 1);
+
+        let vrecords = log.poll(10).unwrap();
+
+        assert_eq!(vrecords.len(), 2);
+        assert_eq!(vrecords[0].payload, "deposit,1,2,2.0");
+
+        //Offsets written without Byte Positions
+        fs::write(
+            std::path::Path::new(&sdirectory).join("offsets.csv"),
+            "partition,offset\n0,2\n",
+        )
+        .unwrap();
+        drop(log);
+
+        let mut log = DirectoryLog::open(&sdirectory).unwrap();
+        let vrecords = log.poll(10).unwrap();
+
+        assert_eq!(vrecords.len(), 1);
+        assert_eq!(vrecords[0].offset, 2);
+        assert_eq!(vrecords[0].payload, "deposit,1,3,3.0");
+
+        fs::remove_dir_all(&sdirectory).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod exactly_once_tests {
+    use super::*;
+
+    const MOVEMENTS_CSV: &str = "type, client, tx, amount\n\
+        deposit,1,1,3.0\ndeposit,2,2,2.0\ndeposit,3,3,5.0\nwithdrawal,1,4,1.5\n\
+        dispute,2,2,\ndeposit,1,5,0.25\nwithdrawal,3,6,1.0\nresolve,2,2,\n\
+        dispute,3,3,\nwithdrawal,2,7,0.5\nchargeback,3,3,\ndeposit,3,8,1.0\n";
+
+    fn sorted_lines(sdata: &str) -> Vec<String> {
+        let mut vlines: Vec<String> = sdata.lines().map(String::from).collect();
+
+        vlines.sort();
+
+        vlines
+    }
+
+    fn create_log(sname: &str) -> (String, DirectoryLog) {
+        let sdirectory = create_log_directory(sname);
+        let mut log = DirectoryLog::create(&sdirectory, 2).unwrap();
+
+        log.append_movements_str(MOVEMENTS_CSV, true).unwrap();
+
+        (sdirectory, log)
+    }
+
+    fn clean_run(sname: &str) -> (Vec<String>, Vec<String>) {
+        let (sdirectory, mut log) = create_log(sname);
+        let mut imp = MovementImporter::new();
+
+        imp.set_quiet(true);
+
+        assert_eq!(imp.consume_log(&mut log, 100), 0);
+
+        fs::remove_dir_all(&sdirectory).unwrap();
+
+        (
+            sorted_lines(&imp.export_accounts_str()),
+            sorted_lines(&imp.export_transactions_str()),
+        )
+    }
+
+    /// ### Test `crash_before_state_commit()`
+    /// This test consumes the Movements in Batches and kills the Processing
+    /// after every possible Batch before its State could be committed
+    /// After the Restart from the State File the Accounts and Transactions
+    /// must be equal to a Run without Crash
+    #[test]
+    fn crash_before_state_commit() {
+        //-------------------------------------
+        //Test Restart after Crash applies each Movement exactly once
+
+        let (vaccounts, vtransactions) = clean_run("clean-run-state-commit");
+
+        for ibatchsize in 1..4 {
+            for ikillbatch in 0..(12 / ibatchsize) {
+                let (sdirectory, mut log) = create_log("crash-before-state-commit");
+                let sstatefile = format!("{}/state.json", sdirectory);
+                let sbrokenfile = format!("{}/missing/state.json", sdirectory);
+                let mut imp = MovementImporter::new();
+
+                imp.set_quiet(true);
+                imp.set_state_filename(&sstatefile);
+
+                for _ in 0..ikillbatch {
+                    assert!(imp.consume_log_batch(&mut log, ibatchsize).is_ok());
+                }
+
+                //The next Batch is applied but its State cannot be committed
+                imp.set_state_filename(&sbrokenfile);
+
+                assert!(imp.consume_log_batch(&mut log, ibatchsize).is_err());
+
+                //Kill the Processing and restart from the State File
+                drop(imp);
+                drop(log);
+
+                let mut log = DirectoryLog::open(&sdirectory).unwrap();
+                let mut imp = MovementImporter::new();
+
+                imp.set_quiet(true);
+                imp.set_state_filename(&sstatefile);
+
+                assert!(imp.load_state().is_ok());
+                assert_eq!(imp.consume_log(&mut log, ibatchsize), 0);
+
+                assert_eq!(sorted_lines(&imp.export_accounts_str()), vaccounts);
+                assert_eq!(sorted_lines(&imp.export_transactions_str()), vtransactions);
+
+                fs::remove_dir_all(&sdirectory).unwrap();
+            } //for ikillbatch in 0..(12 / ibatchsize)
+        } //for ibatchsize in 1..4
+    }
+
+    /// ### Test `crash_before_log_commit()`
+    /// This test commits the State of each Batch but loses the Offsets
+    /// committed to the Log as if the Processing was killed in between
+    /// The Input Offsets of the State File must take precedence
+    /// and no Movement must be applied twice
+    #[test]
+    fn crash_before_log_commit() {
+        //-------------------------------------
+        //Test State Offsets take Precedence over Log Offsets
+
+        let (vaccounts, vtransactions) = clean_run("clean-run-log-commit");
+
+        for ikillbatch in 1..6 {
+            let (sdirectory, mut log) = create_log("crash-before-log-commit");
+            let sstatefile = format!("{}/state.json", sdirectory);
+            let mut imp = MovementImporter::new();
+
+            imp.set_quiet(true);
+            imp.set_state_filename(&sstatefile);
+
+            for _ in 0..ikillbatch {
+                assert!(imp.consume_log_batch(&mut log, 2).is_ok());
+            }
+
+            //Lose the Log Commits
+            log.commit(0, 0).unwrap();
+            log.commit(1, 0).unwrap();
+
+            drop(imp);
+            drop(log);
+
+            let mut log = DirectoryLog::open(&sdirectory).unwrap();
+            let mut imp = MovementImporter::new();
+
+            imp.set_quiet(true);
+            imp.set_state_filename(&sstatefile);
+
+            assert!(imp.load_state().is_ok());
+            assert_eq!(imp.consume_log(&mut log, 2), 0);
+
+            assert_eq!(sorted_lines(&imp.export_accounts_str()), vaccounts);
+            assert_eq!(sorted_lines(&imp.export_transactions_str()), vtransactions);
+            assert_eq!(log.get_committed_offset(0), 4);
+            assert_eq!(log.get_committed_offset(1), 8);
+
+            fs::remove_dir_all(&sdirectory).unwrap();
+        } //for ikillbatch in 1..6
+    }
+
+    /// ### Test `reorder_across_batches()`
+    /// This test consumes Movements with Timestamps in Batches of one Record
+    /// on a State File and restarts the Engine after each Batch
+    /// The older "_deposit_" must still be applied before the "_withdrawal_"
+    /// and the newest "_deposit_" must wait in the Reorder Buffer
+    #[test]
+    fn reorder_across_batches() {
+        //-------------------------------------
+        //Test Reorder Buffer is kept across Batches
+
+        let sdirectory = create_log_directory("reorder-across-batches");
+        let sstatefile = format!("{}/state.json", sdirectory);
+        let mut log = DirectoryLog::create(&sdirectory, 1).unwrap();
+
+        log.append_movements_str(
+            "withdrawal,1,1,2.0,,,,,105\ndeposit,1,2,5.0,,,,,100\ndeposit,1,3,1.0,,,,,120\n",
+            false,
+        )
+        .unwrap();
+
+        for _ in 0..3 {
+            let mut imp = MovementImporter::new();
+
+            imp.set_debug(true);
+            imp.set_reorder_window(10);
+            imp.set_state_filename(&sstatefile);
+
+            assert!(imp.load_state().is_ok());
+            assert_eq!(imp.consume_log_batch(&mut log, 1).ok(), Some(1));
+        }
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_reorder_window(10);
+        imp.set_state_filename(&sstatefile);
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(imp.get_transaction(1).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(3).unwrap().status, 0);
+        assert_eq!(log.get_committed_offset(0), 3);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,3.0,0.0,3.0,false\n"
+        );
+
+        //The final Run applies the Buffer
+        imp.flush_movements();
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,4.0,0.0,4.0,false\n"
+        );
+
+        fs::remove_dir_all(&sdirectory).unwrap();
+    }
+}
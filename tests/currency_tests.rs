@@ -0,0 +1,160 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::account::{Account, AccountFactory};
+
+#[cfg(test)]
+mod currency_tests {
+    use super::*;
+
+    /// ### Test `balances_per_currency()`
+    /// This test deposits and withdraws Funds in "_EUR_" and "_USD_"
+    /// Each Currency has its own Balances and is exported in its own Row
+    /// The legacy Export shows only the Balances without Currency
+    #[test]
+    fn balances_per_currency() {
+        //-------------------------------------
+        //Test Balances per Currency
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,10.0,,,EUR\n\
+                deposit,1,2,5.0,,,usd\n\
+                withdrawal,1,3,7.0,,,USD\n\
+                withdrawal,1,4,3.0,,,EUR\n\
+                deposit,1,5,2.0,,,\n",
+                true
+            ),
+            0
+        );
+
+        let scurrencies = imp.export_currencies_str();
+
+        println!("{}", scurrencies.as_str());
+
+        assert_eq!(
+            scurrencies.as_str(),
+            "client,currency,available,held,total,locked\n\
+            1,,2.0,0.0,2.0,false\n\
+            1,EUR,7.0,0.0,7.0,false\n\
+            1,USD,5.0,0.0,5.0,false\n"
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,2.0,0.0,2.0,false\n"
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+        assert_eq!(
+            imp.get_transaction(2).unwrap().currency.as_deref(),
+            Some("USD")
+        );
+    }
+
+    /// ### Test `dispute_currency_mismatch()`
+    /// This test disputes a "_EUR_" Deposit with a "_USD_" Dispute
+    /// The Dispute is refused and a Dispute in the right Currency is accepted
+    #[test]
+    fn dispute_currency_mismatch() {
+        //-------------------------------------
+        //Test Dispute in another Currency fails
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,10.0,,,EUR\n\
+                dispute,1,1,,,,USD\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(1).unwrap().status, 1);
+
+        assert_eq!(imp.import_movements_str("dispute,1,1,,,,EUR\n", false), 0);
+
+        assert_eq!(imp.get_transaction(1).unwrap().status, -2);
+        assert_eq!(
+            imp.export_currencies_str().as_str(),
+            "client,currency,available,held,total,locked\n1,EUR,0.0,10.0,10.0,false\n"
+        );
+    }
+
+    /// ### Test `import_legacy_accounts()`
+    /// This test imports Accounts without Currency Balances
+    #[test]
+    fn import_legacy_accounts() {
+        //-------------------------------------
+        //Test Import Accounts without Currencies
+
+        let accfact = AccountFactory::from_str(
+            "client,available,held,total,locked\n3,1.5,0.0,1.5,false\n",
+            true,
+            true,
+            false,
+        );
+
+        assert_eq!(
+            accfact.export_csv(true, false).as_str(),
+            "client,available,held,total,locked\n3,1.5,0.0,1.5,false\n"
+        );
+        assert!(accfact.lstaccounts.get(&3).unwrap().currencies.is_empty());
+    }
+
+    /// Debits the "_EUR_" Balances and returns early when they do not cover the Amount
+    fn debit_euro(acc: &mut Account, famount: f64) -> bool {
+        let mut euro = acc.switch_to(Some("EUR"));
+
+        if famount > euro.available {
+            return false;
+        }
+
+        euro.available -= famount;
+        euro.total -= famount;
+
+        true
+    }
+
+    /// ### Test `switch_currency_on_return()`
+    /// This test debits the "_EUR_" Balances through a Currency Guard
+    /// A refused Debit returns early and the primary Balances are restored as well
+    #[test]
+    fn switch_currency_on_return() {
+        //-------------------------------------
+        //Test Currency Guard on early Return
+
+        let mut accfact = AccountFactory::from_str(
+            "client,available,held,total,locked\n3,1.5,0.0,1.5,false\n",
+            true,
+            true,
+            false,
+        );
+        let acc = accfact.lstaccounts.get_mut(&3).unwrap();
+
+        assert!(debit_euro(acc, 0.0));
+        assert!(!debit_euro(acc, 2.0));
+
+        assert_eq!(acc.available, 1.5);
+        assert_eq!(acc.currencies.get("EUR").unwrap().available, 0.0);
+
+        {
+            let mut euro = acc.switch_to(Some("EUR"));
+
+            euro.available = 4.0;
+            euro.total = 4.0;
+        }
+
+        assert!(debit_euro(acc, 2.5));
+
+        assert_eq!(acc.available, 1.5);
+        assert_eq!(acc.total, 1.5);
+        assert_eq!(acc.currencies.get("EUR").unwrap().available, 1.5);
+        assert_eq!(acc.currencies.get("EUR").unwrap().total, 1.5);
+    }
+}
@@ -0,0 +1,116 @@
+use client_accounting::app::importer::MovementImporter;
+
+#[cfg(test)]
+mod dispute_case_tests {
+    use super::*;
+
+    /// ### Test `case_opened_and_resolved()`
+    /// This test disputes a "_deposit_" with Reason Code and Case Reference and resolves it
+    /// The Case records the Dispute and the Resolution and is closed as `resolved`
+    #[test]
+    fn case_opened_and_resolved() {
+        //-------------------------------------
+        //Test Dispute Case is opened and resolved
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(1000);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency,target,timestamp,reason_code,case\n\
+                deposit,1,1,10.0,,,,,,,\n\
+                dispute,1,1,,,,,,,fraud,CB-17\n",
+                true
+            ),
+            0
+        );
+
+        let txrec = imp.get_transaction(1).unwrap();
+
+        assert_eq!(txrec.cases.len(), 1);
+        assert_eq!(txrec.cases[0].case.as_deref(), Some("CB-17"));
+        assert_eq!(txrec.cases[0].reason_code.as_deref(), Some("fraud"));
+        assert!(txrec.cases[0].is_open());
+
+        imp.set_clock(1500);
+
+        assert_eq!(imp.import_movements_str("resolve,1,1,\n", false), 0);
+
+        let txrec = imp.get_transaction(1).unwrap();
+
+        assert_eq!(txrec.cases[0].closed_at, Some(1500));
+        assert_eq!(txrec.cases[0].outcome.as_deref(), Some("resolved"));
+        assert_eq!(
+            txrec.cases[0].format_history(),
+            "DisputeOpened:10.0@1000;DisputeResolved:10.0@1500"
+        );
+    }
+
+    /// ### Test `case_charged_back()`
+    /// This test charges a disputed "_deposit_" back
+    /// The Case is closed as `charged_back`
+    #[test]
+    fn case_charged_back() {
+        //-------------------------------------
+        //Test Dispute Case is charged back
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(2000);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency,target,timestamp,reason_code,case\n\
+                deposit,1,1,10.0,,,,,,,\n\
+                dispute,1,1,4.0,,,,,,not_received,CB-3\n\
+                chargeback,1,1,,,,,,,,\n",
+                true
+            ),
+            0
+        );
+
+        let txrec = imp.get_transaction(1).unwrap();
+
+        assert_eq!(txrec.cases.len(), 1);
+        assert_eq!(txrec.cases[0].outcome.as_deref(), Some("charged_back"));
+        assert_eq!(txrec.cases[0].history.len(), 2);
+        assert_eq!(txrec.cases[0].history[1].event, "ChargebackApplied");
+    }
+
+    /// ### Test `export_cases()`
+    /// This test exports one closed and one open Case
+    /// The Export lists both Cases ordered by Transaction
+    #[test]
+    fn export_cases() {
+        //-------------------------------------
+        //Test Export of open and closed Dispute Cases
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency,target,timestamp,reason_code,case\n\
+                deposit,1,1,10.0,,,,,,,\n\
+                deposit,2,2,5.0,,,,,,,\n\
+                dispute,2,2,,,,,,,duplicate,CB-9\n\
+                dispute,1,1,,,,,,,fraud,CB-8\n\
+                resolve,1,1,,,,,,,,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_cases_str().as_str(),
+            "client,tx,case,reason_code,state,outcome,opened_at,closed_at,history\n\
+            1,1,CB-8,fraud,closed,resolved,100,100,DisputeOpened:10.0@100;DisputeResolved:10.0@100\n\
+            2,2,CB-9,duplicate,open,,100,,DisputeOpened:5.0@100\n"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::account::AccountFactory;
+use client_accounting::model::policy::{DisputeDeadline, DisputePolicy, ExpiryAction};
+
+#[cfg(test)]
+mod dispute_expiry_tests {
+    use super::*;
+
+    /// ### Test `dispute_outside_window()`
+    /// This test disputes a "_deposit_" after the Dispute Window has passed
+    /// The Dispute is refused and the Transaction stays processed
+    #[test]
+    fn dispute_outside_window() {
+        //-------------------------------------
+        //Test Dispute outside the Dispute Window fails
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_dispute_policy(DisputePolicy {
+            window: Some(100),
+            ..DisputePolicy::default()
+        });
+        imp.set_clock(1000);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,1,1,10.0\n", true),
+            0
+        );
+
+        imp.set_clock(1101);
+
+        assert_eq!(imp.import_movements_str("dispute,1,1,\n", false), 0);
+
+        assert_eq!(imp.get_transaction(1).unwrap().booked_at, Some(1000));
+        assert_eq!(imp.get_transaction(1).unwrap().status, 1);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,10.0,0.0,10.0,false\n"
+        );
+    }
+
+    /// ### Test `expire_after_movements()`
+    /// This test leaves a Dispute open for 2 subsequent Movements
+    /// The Dispute expires and is resolved
+    #[test]
+    fn expire_after_movements() {
+        //-------------------------------------
+        //Test Dispute expires after Movements
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_dispute_policy(DisputePolicy {
+            deadline: DisputeDeadline::Movements(2),
+            ..DisputePolicy::default()
+        });
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\ndispute,1,1,\ndeposit,1,2,1.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(1).unwrap().status, -2);
+        assert_eq!(imp.get_transaction(1).unwrap().expires_after, Some(1));
+
+        assert_eq!(imp.import_movements_str("deposit,1,3,1.0\n", false), 0);
+
+        assert_eq!(imp.get_transaction(1).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(1).unwrap().expires_after, None);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,12.0,0.0,12.0,false\n"
+        );
+    }
+
+    /// ### Test `expire_by_time_with_chargeback()`
+    /// This test leaves a Dispute open beyond its Deadline of `60` Seconds
+    /// The Expiry Sweep charges the Dispute back and locks the Account
+    #[test]
+    fn expire_by_time_with_chargeback() {
+        //-------------------------------------
+        //Test Dispute expires by Time with Chargeback
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_dispute_policy(DisputePolicy {
+            window: None,
+            deadline: DisputeDeadline::Window(60),
+            expiry: ExpiryAction::Chargeback,
+        });
+        imp.set_clock(1000);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\ndeposit,1,2,2.0\ndispute,1,1,\n",
+                true
+            ),
+            0
+        );
+
+        imp.set_clock(1059);
+
+        assert_eq!(imp.process_expiry(), 0);
+
+        imp.set_clock(1060);
+
+        assert_eq!(imp.process_expiry(), 1);
+        assert_eq!(imp.get_transaction(1).unwrap().status, -1);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,2.0,0.0,2.0,true\n"
+        );
+    }
+
+    /// ### Test `expire_transfer_by_time()`
+    /// This test disputes a "_transfer_" of `4.0` and leaves the Dispute open
+    /// beyond its Deadline of `10` Seconds
+    /// The Expiry Sweep charges the Dispute back on the Destination Account (id: '2'),
+    /// locks it and returns the Funds to Account (id: '1')
+    #[test]
+    fn expire_transfer_by_time() {
+        //-------------------------------------
+        //Test Transfer Dispute expires on its Destination
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_dispute_policy(DisputePolicy {
+            window: None,
+            deadline: DisputeDeadline::Window(10),
+            expiry: ExpiryAction::Chargeback,
+        });
+        imp.set_clock(1000);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,1,1,10.0,,\n\
+                transfer,1,2,4.0,,2\n\
+                dispute,1,2,,,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(2).unwrap().status, -2);
+        assert_eq!(imp.get_transaction(2).unwrap().expires_at, Some(1010));
+        let accfact = AccountFactory::from_str(&imp.export_accounts_str(), true, true, false);
+
+        assert_eq!(accfact.lstaccounts.get(&1).unwrap().total, 6.0);
+        assert_eq!(accfact.lstaccounts.get(&2).unwrap().held, 4.0);
+
+        imp.set_clock(1010);
+
+        assert_eq!(imp.process_expiry(), 1);
+        assert_eq!(imp.get_transaction(2).unwrap().status, -1);
+        assert_eq!(imp.get_transaction(2).unwrap().expires_at, None);
+        let accfact = AccountFactory::from_str(&imp.export_accounts_str(), true, true, false);
+
+        assert_eq!(accfact.lstaccounts.get(&1).unwrap().available, 10.0);
+        assert_eq!(accfact.lstaccounts.get(&2).unwrap().total, 0.0);
+        assert!(accfact.lstaccounts.get(&2).unwrap().locked);
+    }
+}
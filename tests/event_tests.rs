@@ -0,0 +1,248 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::app::notifier::{EventFileSink, EventFormat, WebhookSink};
+
+use std::fs;
+
+//==============================================================================
+// Auxiliary Functions
+
+fn create_events_filename(sname: &str) -> String {
+    let pfile = std::env::temp_dir().join(format!(
+        "client-accounting-{}-{}",
+        std::process::id(),
+        sname
+    ));
+
+    if pfile.exists() {
+        fs::remove_file(&pfile).unwrap();
+    }
+
+    pfile.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod event_file_tests {
+    use super::*;
+
+    /// ### Test `events_csv_file()`
+    /// This test processes a "_deposit_", a refused "_withdrawal_",
+    /// a "_dispute_" and a "_chargeback_" and a "_withdrawal_" on the locked Account
+    /// The Events File must contain the Events in the Order they happened
+    #[test]
+    fn events_csv_file() {
+        //-------------------------------------
+        //Test Events are written to a CSV File
+
+        let sfilename = create_events_filename("events.csv");
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.add_observer(Box::new(EventFileSink::new(
+            &sfilename,
+            EventFormat::Csv,
+            false,
+        )));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,6,2.0\ndispute,11,5,\nchargeback,11,5,\nwithdrawal,11,7,0.5\n",
+                true
+            ),
+            0
+        );
+
+        drop(imp);
+
+        let sevents = fs::read_to_string(&sfilename).unwrap();
+
+        println!("{}", sevents.as_str());
+
+        assert_eq!(
+            sevents.as_str(),
+            "event,client,tx,amount,reason\n\
+            DepositAccepted,11,5,1.1,\n\
+            WithdrawalRejected,11,6,2.0,insufficient_funds\n\
+            DisputeOpened,11,5,1.1,\n\
+            ChargebackApplied,11,5,1.1,\n\
+            AccountLocked,11,5,,\n\
+            WithdrawalRejected,11,7,0.5,account_locked\n"
+        );
+
+        fs::remove_file(&sfilename).unwrap();
+    }
+
+    /// ### Test `events_ndjson_file()`
+    /// This test processes a "_deposit_", a "_dispute_" and a "_resolve_"
+    /// The Events File must contain one JSON Object per Line
+    #[test]
+    fn events_ndjson_file() {
+        //-------------------------------------
+        //Test Events are written to a NDJSON File
+
+        let sfilename = create_events_filename("events.ndjson");
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.add_observer(Box::new(EventFileSink::from_filename(&sfilename, false)));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nresolve,11,5,\n",
+                true
+            ),
+            0
+        );
+
+        drop(imp);
+
+        let sevents = fs::read_to_string(&sfilename).unwrap();
+
+        println!("{}", sevents.as_str());
+
+        assert_eq!(
+            sevents.as_str(),
+            "{\"event\":\"DepositAccepted\",\"client\":11,\"tx\":5,\"amount\":1.1,\"reason\":null}\n\
+            {\"event\":\"DisputeOpened\",\"client\":11,\"tx\":5,\"amount\":1.1,\"reason\":null}\n\
+            {\"event\":\"DisputeResolved\",\"client\":11,\"tx\":5,\"amount\":1.1,\"reason\":null}\n"
+        );
+
+        fs::remove_file(&sfilename).unwrap();
+    }
+
+    /// ### Test `events_file_open_failed()`
+    /// This test opens an Events File in a Directory which does not exist
+    /// The quiet Sink keeps the Error Code `1`
+    #[test]
+    fn events_file_open_failed() {
+        //-------------------------------------
+        //Test Events File cannot be opened
+
+        let sfilename = create_events_filename("missing-directory/events.csv");
+        let sink = EventFileSink::from_filename(&sfilename, true);
+
+        assert_eq!(sink.get_error_code(), 1);
+    }
+}
+
+#[cfg(test)]
+mod webhook_tests {
+    use super::*;
+    use client_accounting::model::event::EventRecord;
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Accepts `irequests` HTTP Requests, answers them with `200 OK`
+    /// and sends their Bodies back to the Test
+    fn start_webhook_stub(irequests: usize) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let surl = format!("http://{}/events", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for _ in 0..irequests {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut icontentlength = 0;
+                let mut sline = String::new();
+
+                while reader.read_line(&mut sline).unwrap() > 0 && sline != "\r\n" {
+                    if let Some(slength) = sline.strip_prefix("Content-Length: ") {
+                        icontentlength = slength.trim().parse::<usize>().unwrap();
+                    }
+
+                    sline.clear();
+                }
+
+                let mut vbody = vec![0; icontentlength];
+
+                reader.read_exact(&mut vbody).unwrap();
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .unwrap();
+
+                sender.send(String::from_utf8(vbody).unwrap()).unwrap();
+            } //for _ in 0..irequests
+        });
+
+        (surl, receiver)
+    }
+
+    /// ### Test `webhook_posts_events()`
+    /// This test processes a "_deposit_", a "_dispute_" and a "_chargeback_"
+    /// On the Flush the local Webhook Stub must receive the 4 Events
+    /// as one JSON Array in a single Request
+    #[test]
+    fn webhook_posts_events() {
+        //-------------------------------------
+        //Test Events are posted to a Webhook
+
+        let (surl, receiver) = start_webhook_stub(1);
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.add_observer(Box::new(WebhookSink::new(&surl).unwrap()));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nchargeback,11,5,\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(imp.flush_events(), 0);
+
+        let vevents: Vec<EventRecord> = serde_json::from_str(&receiver.recv().unwrap()).unwrap();
+
+        println!("{:?}", vevents);
+
+        assert_eq!(vevents.len(), 4);
+        assert_eq!(vevents[0].event, "DepositAccepted");
+        assert_eq!(vevents[1].event, "DisputeOpened");
+        assert_eq!(vevents[2].event, "ChargebackApplied");
+        assert_eq!(vevents[2].amount, Some(1.1));
+        assert_eq!(vevents[3].event, "AccountLocked");
+        assert_eq!(vevents[3].client, 11);
+
+        //Nothing is pending anymore
+        assert_eq!(imp.flush_events(), 0);
+    }
+
+    /// ### Test `webhook_delivery_failed()`
+    /// This test posts Events to a Port where nothing listens
+    /// The Flush must fail with the Error Code `1`
+    /// and the Importer must report the Error
+    #[test]
+    fn webhook_delivery_failed() {
+        //-------------------------------------
+        //Test failed Deliveries are reported
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let surl = format!("http://{}/events", listener.local_addr().unwrap());
+
+        drop(listener);
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.add_observer(Box::new(WebhookSink::new(&surl).unwrap()));
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,11,5,1.1\n", true),
+            0
+        );
+        assert_eq!(imp.flush_events(), 1);
+        assert_eq!(imp.get_error_code(), 1);
+    }
+
+    /// ### Test `webhook_url_invalid()`
+    /// Only plain `http://` URLs with a Host are accepted
+    #[test]
+    fn webhook_url_invalid() {
+        assert!(WebhookSink::new("https://localhost/events").is_none());
+        assert!(WebhookSink::new("http:///events").is_none());
+        assert!(WebhookSink::new("http://localhost").is_some());
+    }
+}
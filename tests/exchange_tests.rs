@@ -0,0 +1,181 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::exchange::{parse_date_days, ExchangeTable, Rounding};
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Builds the Exchange Rates used by the Tests
+fn build_exchange_table() -> ExchangeTable {
+    let mut exchange = ExchangeTable::new();
+
+    assert_eq!(
+        exchange
+            .import_rates_str(
+                "date,from,to,rate,spread\n2024-01-01,EUR,USD,1.1,0.5\n2024-02-01,eur,usd,1.2,\n"
+            )
+            .ok(),
+        Some(2)
+    );
+
+    exchange
+}
+
+/// Returns the Time in Seconds since the Unix Epoch at the Start of the Day
+fn day_seconds(sdate: &str) -> u64 {
+    parse_date_days(sdate).unwrap() as u64 * 86400
+}
+
+#[cfg(test)]
+mod exchange_tests {
+    use super::*;
+
+    /// ### Test `rounding_modes()`
+    /// This test rounds converted Amounts down, up and to the nearest Value
+    #[test]
+    fn rounding_modes() {
+        //-------------------------------------
+        //Test Rounding of converted Amounts
+
+        assert_eq!(Rounding::parse("down:2").unwrap().apply(1.2399), 1.23);
+        assert_eq!(Rounding::parse("up:2").unwrap().apply(1.231), 1.24);
+        assert_eq!(Rounding::parse("up:2").unwrap().apply(1.23), 1.23);
+        assert_eq!(Rounding::parse("nearest").unwrap().apply(1.23456), 1.2346);
+        assert!(Rounding::parse("sideways").is_none());
+    }
+
+    /// ### Test `date_effective_rates()`
+    /// This test finds the Rate which is effective on a Day
+    /// Without direct Rate the inverse Rate is used
+    #[test]
+    fn date_effective_rates() {
+        //-------------------------------------
+        //Test date-effective Rates
+
+        let exchange = build_exchange_table();
+
+        assert_eq!(parse_date_days("1970-01-02"), Some(1));
+        assert_eq!(
+            exchange.find_rate("EUR", "USD", day_seconds("2024-01-15")),
+            Some((1.1, 0.5))
+        );
+        assert_eq!(
+            exchange.find_rate("EUR", "USD", day_seconds("2024-02-01") + 3600),
+            Some((1.2, 0.0))
+        );
+        assert_eq!(
+            exchange.find_rate("USD", "EUR", day_seconds("2024-01-15")),
+            Some((1.0 / 1.1, 0.5))
+        );
+        assert_eq!(
+            exchange.find_rate("EUR", "USD", day_seconds("2023-12-31")),
+            None
+        );
+    }
+
+    /// ### Test `exchange_between_currencies()`
+    /// This test converts `10.0` "_EUR_" into "_USD_" at the Rate `1.1` with a Spread of `0.5`%
+    /// The converted Amount is rounded down to 2 Decimals
+    /// The Rate and the Spread are recorded in the Transaction
+    #[test]
+    fn exchange_between_currencies() {
+        //-------------------------------------
+        //Test Exchange between Currencies
+
+        let mut exchange = build_exchange_table();
+        let mut imp = MovementImporter::new();
+
+        exchange.set_rounding(Rounding::parse("down:2").unwrap());
+
+        imp.set_debug(true);
+        imp.set_exchange_table(exchange);
+        imp.set_clock(day_seconds("2024-01-15"));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency,target\n\
+                deposit,1,1,100.0,,,EUR,\n\
+                exchange,1,2,10.0,,,EUR,USD\n",
+                true
+            ),
+            0
+        );
+
+        let scurrencies = imp.export_currencies_str();
+
+        println!("{}", scurrencies.as_str());
+
+        assert_eq!(
+            scurrencies.as_str(),
+            "client,currency,available,held,total,locked\n\
+            1,EUR,90.0,0.0,90.0,false\n\
+            1,USD,10.94,0.0,10.94,false\n"
+        );
+
+        let txexchange = imp.get_transaction(2).unwrap();
+
+        assert_eq!(txexchange.status, 1);
+        assert_eq!(txexchange.rate, Some(1.1));
+        assert_eq!(txexchange.spread, Some(0.5));
+        assert_eq!(txexchange.converted, Some(10.94));
+    }
+
+    /// ### Test `exchange_rate_missing()`
+    /// This test converts into a Currency without Rate
+    /// The "_exchange_" is refused and the Balances stay unchanged
+    #[test]
+    fn exchange_rate_missing() {
+        //-------------------------------------
+        //Test Exchange without Rate fails
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_exchange_table(build_exchange_table());
+        imp.set_clock(day_seconds("2024-01-15"));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency,target\n\
+                deposit,1,1,100.0,,,EUR,\n\
+                exchange,1,2,10.0,,,EUR,JPY\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(2).unwrap().status, -1);
+        assert_eq!(
+            imp.export_currencies_str().as_str(),
+            "client,currency,available,held,total,locked\n1,EUR,100.0,0.0,100.0,false\n"
+        );
+    }
+
+    /// ### Test `exchange_at_movement_time()`
+    /// This test processes an "_exchange_" with a `timestamp` in January
+    /// after the Rate of February became effective on the Wall Clock
+    /// The Rate `1.1` effective at the `timestamp` is used
+    #[test]
+    fn exchange_at_movement_time() {
+        //-------------------------------------
+        //Test Exchange Rate at the Time of the Movement
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_exchange_table(build_exchange_table());
+        imp.set_clock(day_seconds("2024-02-15"));
+
+        let smovements = format!(
+            "type,client,tx,amount,original,destination,currency,target,timestamp\n\
+            deposit,1,1,100.0,,,EUR,,\n\
+            exchange,1,2,10.0,,,EUR,USD,{}\n\
+            exchange,1,3,10.0,,,EUR,USD,\n",
+            day_seconds("2024-01-15")
+        );
+
+        assert_eq!(imp.import_movements_str(&smovements, true), 0);
+
+        assert_eq!(imp.get_transaction(2).unwrap().rate, Some(1.1));
+        assert_eq!(imp.get_transaction(3).unwrap().rate, Some(1.2));
+    }
+}
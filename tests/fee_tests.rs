@@ -0,0 +1,246 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::fee::FeeSchedule;
+use client_accounting::model::policy::ClearingPolicy;
+use client_accounting::model::transaction::INTERNAL_ID_START;
+
+#[cfg(test)]
+mod fee_tests {
+
+    /// Builds the Fee Schedule used by the Tests
+    fn build_fee_schedule() -> super::FeeSchedule {
+        let mut fees = super::FeeSchedule::new();
+
+        assert_eq!(
+            fees.import_rules_str(
+                "type,tier,flat,percent,min,max\nwithdrawal,,0.5,1.0,,5.0\nwithdrawal,premium,0.0,0.5,0.1,\nchargeback,,2.0,,,\n"
+            )
+            .ok(),
+            Some(3)
+        );
+        assert!(fees.import_tiers_str("client,tier\n2,premium\n").is_ok());
+
+        fees
+    }
+
+    /// ### Test `calculate_fees()`
+    /// This test calculates the Fees for Clients with and without Tier
+    /// The Fee is limited by `min` and `max`
+    #[test]
+    fn calculate_fees() {
+        //-------------------------------------
+        //Test calculate Fees
+
+        let fees = build_fee_schedule();
+
+        assert_eq!(fees.calculate("withdrawal", 1, 10.0), 0.6);
+        assert_eq!(fees.calculate("withdrawal", 1, 1000.0), 5.0);
+        assert_eq!(fees.calculate("withdrawal", 2, 10.0), 0.1);
+        assert_eq!(fees.calculate("withdrawal", 2, 100.0), 0.5);
+        assert_eq!(fees.calculate("deposit", 1, 10.0), 0.0);
+    }
+
+    /// ### Test `withdrawal_with_fee()`
+    /// This test charges the Fee of a "_withdrawal_" as a separate "_fee_" Transaction
+    /// The Fee Report shows the Fee Income
+    #[test]
+    fn withdrawal_with_fee() {
+        //-------------------------------------
+        //Test Withdrawal with Fee
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_fee_schedule(build_fee_schedule());
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,20.0\nwithdrawal,1,2,10.0\n",
+                true
+            ),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let sreport = imp.export_fee_report_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", sreport.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n1,9.4,0.0,9.4,false\n"
+        );
+        assert_eq!(sreport.as_str(), "type,count,income\nwithdrawal,1,0.6\n");
+
+        let txfee = imp.get_transaction(super::INTERNAL_ID_START).unwrap();
+
+        assert_eq!(txfee.tx_type, "fee");
+        assert_eq!(txfee.status, 1);
+        assert_eq!(txfee.amount, 0.6);
+        assert_eq!(txfee.original, Some(2));
+    }
+
+    /// ### Test `withdrawal_fee_not_covered()`
+    /// This test withdraws the whole `available` Funds
+    /// The "_withdrawal_" is refused because the Fee is not covered
+    #[test]
+    fn withdrawal_fee_not_covered() {
+        //-------------------------------------
+        //Test Withdrawal fails when the Fee is not covered
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_fee_schedule(build_fee_schedule());
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\nwithdrawal,1,2,10.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,10.0,0.0,10.0,false\n"
+        );
+        assert_eq!(imp.get_transaction(2).unwrap().status, -1);
+        assert!(imp.get_transaction(super::INTERNAL_ID_START).is_none());
+        assert_eq!(imp.export_fee_report_str().as_str(), "type,count,income\n");
+    }
+
+    /// ### Test `chargeback_fee_rejected()`
+    /// This test charges a Fee on a Chargeback which empties the Account
+    /// The Fee is recorded as invalid with `status` (`-1`)
+    #[test]
+    fn chargeback_fee_rejected() {
+        //-------------------------------------
+        //Test Chargeback Fee fails on insufficient Funds
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_fee_schedule(build_fee_schedule());
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\ndeposit,1,2,1.0\ndispute,1,1,\nchargeback,1,1,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,1.0,0.0,1.0,true\n"
+        );
+
+        let txfee = imp.get_transaction(super::INTERNAL_ID_START).unwrap();
+
+        assert_eq!(txfee.status, -1);
+        assert_eq!(txfee.amount, 2.0);
+        assert_eq!(txfee.reason.as_deref(), Some("chargeback"));
+    }
+
+    /// ### Test `deposit_fee_on_clearing()`
+    /// This test charges a flat Deposit Fee of `1.0` on 2 clearing "_deposit_"s
+    /// which are released after 1 subsequent Movement
+    /// Each Fee is charged when its Deposit is released
+    #[test]
+    fn deposit_fee_on_clearing() {
+        //-------------------------------------
+        //Test Deposit Fee is charged on Release
+
+        let mut imp = super::MovementImporter::new();
+        let mut fees = super::FeeSchedule::new();
+
+        assert_eq!(
+            fees.import_rules_str("type,tier,flat,percent,min,max\ndeposit,,1.0,,,\n")
+                .ok(),
+            Some(1)
+        );
+
+        imp.set_debug(true);
+        imp.set_fee_schedule(fees);
+        imp.set_clearing_policy(super::ClearingPolicy::Movements(1));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\ndeposit,1,2,5.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,9.0,5.0,14.0,false\n"
+        );
+        assert_eq!(
+            imp.get_transaction(super::INTERNAL_ID_START)
+                .unwrap()
+                .original,
+            Some(1)
+        );
+
+        assert_eq!(imp.import_movements_str("withdrawal,1,3,1.0\n", false), 0);
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,12.0,0.0,12.0,false\n"
+        );
+        assert_eq!(
+            imp.get_transaction(super::INTERNAL_ID_START + 1)
+                .unwrap()
+                .original,
+            Some(2)
+        );
+        assert_eq!(
+            imp.export_fee_report_str().as_str(),
+            "type,count,income\ndeposit,2,2.0\n"
+        );
+    }
+
+    /// ### Test `deposit_fee_on_settlement()`
+    /// This test settles a clearing "_deposit_" with a "_settle_" Movement
+    /// The Deposit Fee is charged with the Settlement
+    #[test]
+    fn deposit_fee_on_settlement() {
+        //-------------------------------------
+        //Test Deposit Fee is charged on Settlement
+
+        let mut imp = super::MovementImporter::new();
+        let mut fees = super::FeeSchedule::new();
+
+        assert_eq!(
+            fees.import_rules_str("type,tier,flat,percent,min,max\ndeposit,,1.0,,,\n")
+                .ok(),
+            Some(1)
+        );
+
+        imp.set_debug(true);
+        imp.set_fee_schedule(fees);
+        imp.set_clearing_policy(super::ClearingPolicy::Settlement);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,1,1,10.0\n", true),
+            0
+        );
+
+        assert!(imp.get_transaction(super::INTERNAL_ID_START).is_none());
+
+        assert_eq!(imp.import_movements_str("settle,1,1,\n", false), 0);
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,9.0,0.0,9.0,false\n"
+        );
+        assert_eq!(
+            imp.get_transaction(super::INTERNAL_ID_START)
+                .unwrap()
+                .status,
+            1
+        );
+    }
+}
@@ -17,7 +17,7 @@ mod account_tests {
         imp.set_debug(true);
 
         assert_eq!(
-            imp.import_movements_str(&"type, client, tx, amount\ndeposit,11,9,1.1\n", true),
+            imp.import_movements_str("type, client, tx, amount\ndeposit,11,9,1.1\n", true),
             0
         );
 
@@ -51,7 +51,7 @@ mod account_tests {
         imp.set_debug(true);
 
         assert_eq!(
-            imp.import_movements_str(&"type, client, tx, amount\nwithdrawal,5,3,0.5\n", true),
+            imp.import_movements_str("type, client, tx, amount\nwithdrawal,5,3,0.5\n", true),
             0
         );
 
@@ -92,7 +92,7 @@ mod dispute_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,9,1.1\ndispute,11,9,\n",
+                "type, client, tx, amount\ndeposit,11,9,1.1\ndispute,11,9,\n",
                 true
             ),
             0
@@ -131,7 +131,7 @@ mod dispute_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,7,0.5\ndispute,11,7,\n",
+                "type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,7,0.5\ndispute,11,7,\n",
                 true
             ),
             0
@@ -174,7 +174,7 @@ mod dispute_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nresolve,11,5,\n",
+                "type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nresolve,11,5,\n",
                 true
             ),
             0
@@ -215,7 +215,7 @@ mod dispute_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,7,0.5\ndispute,11,7,\nresolve,11,7,\n",
+                "type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,7,0.5\ndispute,11,7,\nresolve,11,7,\n",
                 true
             ),
             0
@@ -259,7 +259,7 @@ mod dispute_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nchargeback,11,5,\n",
+                "type, client, tx, amount\ndeposit,11,5,1.1\ndispute,11,5,\nchargeback,11,5,\n",
                 true
             ),
             0
@@ -282,6 +282,209 @@ mod dispute_tests {
         assert!(otxrec5.is_some());
         assert_eq!(otxrec5.unwrap().status, -1);
     }
+
+    /// ### Test `chargeback_on_withdrawal()`
+    /// This test runs a Chargeback of a "_withdrawal_" Transaction
+    /// So, 1 Account and 2 Transaction must have been created
+    /// Transaction (id: '5') must be marked as processed with `status` (`1`)
+    /// Transaction (id: '7') must be marked as invalid with `status` (`-1`)
+    /// The Funds are removed from `held` and returned to `available`
+    /// The Account is `locked`
+    #[test]
+    fn chargeback_on_withdrawal() {
+        //-------------------------------------
+        //Test Chargeback a Withdrawal
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,7,0.5\ndispute,11,7,\nchargeback,11,7,\n",
+                true
+            ),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let stransactions = imp.export_transactions_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", stransactions.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,1.1,0.0,1.1,true\n"
+        );
+
+        let txfact = TransactionFactory::from_str(stransactions.as_str(), true, true, false);
+        let otxrec5 = txfact.lsttransactions.get(&5);
+        let otxrec7 = txfact.lsttransactions.get(&7);
+
+        assert!(otxrec5.is_some());
+        assert!(otxrec7.is_some());
+        assert_eq!(otxrec5.unwrap().status, 1);
+        assert_eq!(otxrec7.unwrap().status, -1);
+    }
+
+    /// ### Test `chargeback_on_resolved_withdrawal()`
+    /// This test requests a Chargeback of a resolved "_withdrawal_" Transaction
+    /// The Chargeback fails because the Transaction is not disputed anymore
+    /// Transaction (id: '7') must be marked as processed with `status` (`1`)
+    /// The Account is not `locked`
+    #[test]
+    fn chargeback_on_resolved_withdrawal() {
+        //-------------------------------------
+        //Test Chargeback a resolved Withdrawal fails
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,1.1\nwithdrawal,11,7,0.5\ndispute,11,7,\nresolve,11,7,\nchargeback,11,7,\n",
+                true
+            ),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let stransactions = imp.export_transactions_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", stransactions.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,0.6,0.0,0.6,false\n"
+        );
+
+        let txfact = TransactionFactory::from_str(stransactions.as_str(), true, true, false);
+        let otxrec7 = txfact.lsttransactions.get(&7);
+
+        assert!(otxrec7.is_some());
+        assert_eq!(otxrec7.unwrap().status, 1);
+    }
+}
+
+#[cfg(test)]
+mod partial_dispute_tests {
+
+    /// ### Test `partial_disputes_on_deposit()`
+    /// This test opens 2 partial Disputes (`3.0` and `4.0`) on a "_deposit_" of `10.0`
+    /// A third Dispute of `4.0` exceeds the remaining Amount (`3.0`) and is refused
+    /// The first Portion is resolved and the remaining Portion is charged back
+    /// Transaction (id: '5') is processed again with `status` (`1`)
+    /// and has a charged back Portion of `4.0`
+    #[test]
+    fn partial_disputes_on_deposit() {
+        //-------------------------------------
+        //Test partial Disputes on a Deposit
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,10.0\ndispute,11,5,3.0\ndispute,11,5,4.0\ndispute,11,5,4.0\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,3.0,7.0,10.0,false\n"
+        );
+
+        let txrec = imp.get_transaction(5).unwrap();
+
+        assert_eq!(txrec.status, -2);
+        assert_eq!(txrec.disputed, 7.0);
+
+        assert_eq!(
+            imp.import_movements_str("resolve,11,5,3.0\nchargeback,11,5,\n", false),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,6.0,0.0,6.0,true\n"
+        );
+
+        let txrec = imp.get_transaction(5).unwrap();
+
+        assert_eq!(txrec.status, 1);
+        assert_eq!(txrec.disputed, 0.0);
+        assert_eq!(txrec.charged_back, 4.0);
+    }
+
+    /// ### Test `partial_chargeback_on_withdrawal()`
+    /// This test disputes and charges back `1.5` of a "_withdrawal_" of `4.0`
+    /// The charged back Portion is returned to the Client
+    #[test]
+    fn partial_chargeback_on_withdrawal() {
+        //-------------------------------------
+        //Test partial Chargeback on a Withdrawal
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,10.0\nwithdrawal,11,7,4.0\ndispute,11,7,1.5\nchargeback,11,7,1.5\n",
+                true
+            ),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,7.5,0.0,7.5,true\n"
+        );
+
+        let txrec = imp.get_transaction(7).unwrap();
+
+        assert_eq!(txrec.status, 1);
+        assert_eq!(txrec.charged_back, 1.5);
+    }
+
+    /// ### Test `resolve_exceeding_dispute()`
+    /// This test resolves more than the disputed Portion of a "_deposit_"
+    /// The Resolve is refused and the Transaction stays disputed
+    #[test]
+    fn resolve_exceeding_dispute() {
+        //-------------------------------------
+        //Test Resolve exceeding the Dispute fails
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,10.0\ndispute,11,5,2.0\nresolve,11,5,2.5\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,8.0,2.0,10.0,false\n"
+        );
+        assert_eq!(imp.get_transaction(5).unwrap().status, -2);
+    }
 }
 
 #[cfg(test)]
@@ -303,7 +506,7 @@ mod precision_tests {
         imp.set_debug(true);
 
         assert_eq!(
-            imp.import_movements_str(&"type, client, tx, amount\ndeposit,11,3,1.1\nwithdrawal,11,5,0.30004\nwithdrawal,11,7,0.50005\nwithdrawal,11,9,0.29994\n", true),
+            imp.import_movements_str("type, client, tx, amount\ndeposit,11,3,1.1\nwithdrawal,11,5,0.30004\nwithdrawal,11,7,0.50005\nwithdrawal,11,9,0.29994\n", true),
             0
         );
 
@@ -0,0 +1,130 @@
+use client_accounting::app::importer::MovementImporter;
+
+use std::fs;
+
+#[cfg(test)]
+mod journal_tests {
+    use super::*;
+
+    /// ### Test `trial_balance_sums_to_zero()`
+    /// This test deposits, withdraws, disputes and charges back Funds
+    /// The Trial Balance sums up to zero and the Chargeback is booked as Loss
+    #[test]
+    fn trial_balance_sums_to_zero() {
+        //-------------------------------------
+        //Test Trial Balance of the Journal
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                deposit,1,2,5.0\n\
+                withdrawal,1,3,3.0\n\
+                dispute,1,2,\n\
+                chargeback,1,2,\n",
+                true
+            ),
+            0
+        );
+
+        assert!(imp.get_journal().is_balanced());
+        assert_eq!(
+            imp.export_trial_balance_str().as_str(),
+            "currency,account,balance\n\
+            ,client_available:1,7.0\n\
+            ,client_held:1,0.0\n\
+            ,settlement,-12.0\n\
+            ,chargeback_loss,5.0\n\
+            ,total,0.0\n"
+        );
+    }
+
+    /// ### Test `derive_balances_from_postings()`
+    /// This test transfers Funds in "_EUR_" and leaves a Dispute open
+    /// The Balances derived from the Postings equal the Account Balances
+    #[test]
+    fn derive_balances_from_postings() {
+        //-------------------------------------
+        //Test Account Balances derived from the Journal
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,10.0,,,EUR\n\
+                deposit,1,2,2.5,,,\n\
+                transfer,1,3,4.0,,2,EUR\n\
+                dispute,1,2,,,,\n",
+                true
+            ),
+            0
+        );
+
+        let derived = imp.derive_account(1).unwrap();
+
+        assert_eq!(derived.available, 0.0);
+        assert_eq!(derived.held, 2.5);
+        assert_eq!(derived.currencies["EUR"].available, 6.0);
+        assert_eq!(imp.derive_account(2).unwrap().currencies["EUR"].total, 4.0);
+        assert_eq!(
+            imp.export_currencies_str().as_str(),
+            "client,currency,available,held,total,locked\n\
+            1,,0.0,2.5,2.5,false\n\
+            1,EUR,6.0,0.0,6.0,false\n\
+            2,EUR,4.0,0.0,4.0,false\n"
+        );
+    }
+
+    /// ### Test `opening_entries_from_state()`
+    /// This test loads a State File from before the Journal
+    /// The saved Balances are booked as Opening Entries and the Journal stays balanced
+    #[test]
+    fn opening_entries_from_state() {
+        //-------------------------------------
+        //Test Opening Entries for Balances without Postings
+
+        let pdirectory = std::env::temp_dir().join(format!(
+            "client-accounting-journal-opening-{}",
+            std::process::id()
+        ));
+        let sstatefile = pdirectory.join("state.json");
+
+        fs::create_dir_all(&pdirectory).unwrap();
+        fs::write(
+            &sstatefile,
+            "{\"offsets\":[],\"accounts\":[{\"client\":1,\"available\":7.5,\"held\":2.0,\
+            \"total\":9.5,\"locked\":false}],\"transactions\":[]}",
+        )
+        .unwrap();
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(imp.import_movements_str("deposit,1,1,0.5\n", false), 0);
+
+        assert!(imp.get_journal().is_balanced());
+        assert_eq!(imp.derive_account(1).unwrap().available, 8.0);
+        assert_eq!(imp.derive_account(1).unwrap().held, 2.0);
+        assert_eq!(
+            imp.export_journal_str().as_str(),
+            "entry,tx,type,account,currency,amount\n\
+            1,0,opening,client_available:1,,7.5\n\
+            1,0,opening,client_held:1,,2.0\n\
+            1,0,opening,settlement,,-9.5\n\
+            2,1,deposit,client_available:1,,0.5\n\
+            2,1,deposit,settlement,,-0.5\n"
+        );
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+}
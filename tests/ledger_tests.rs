@@ -0,0 +1,122 @@
+use client_accounting::app::importer::MovementImporter;
+
+#[cfg(test)]
+mod ledger_tests {
+    use super::*;
+
+    /// ### Test `entries_explain_balance()`
+    /// This test deposits, withdraws, disputes and charges back Funds
+    /// Each applied Movement appends an Entry with its Deltas and the resulting Balances
+    /// The refused Withdrawal does not append an Entry
+    #[test]
+    fn entries_explain_balance() {
+        //-------------------------------------
+        //Test Ledger Entries of applied Movements
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                deposit,1,2,5.0\n\
+                withdrawal,1,3,3.0\n\
+                withdrawal,1,4,20.0\n\
+                dispute,1,2,\n\
+                chargeback,1,2,\n",
+                true
+            ),
+            0
+        );
+
+        let ventries = imp.get_ledger_entries(1);
+
+        assert_eq!(ventries.len(), 5);
+        assert_eq!(
+            ventries
+                .iter()
+                .map(|entry| (entry.sequence, entry.tx, entry.tx_type.as_str()))
+                .collect::<Vec<(u64, u32, &str)>>(),
+            vec![
+                (1, 1, "deposit"),
+                (2, 2, "deposit"),
+                (3, 3, "withdrawal"),
+                (4, 2, "dispute"),
+                (5, 2, "chargeback")
+            ]
+        );
+        assert_eq!(ventries[3].available_delta, -5.0);
+        assert_eq!(ventries[3].held_delta, 5.0);
+        assert_eq!(ventries[3].total_delta, 0.0);
+        assert_eq!(ventries[4].held, 0.0);
+        assert_eq!(ventries[4].total, 7.0);
+
+        //The Deltas sum up to the Balances
+        let ftotal: f64 = ventries.iter().map(|entry| entry.total_delta).sum();
+
+        assert_eq!(ftotal, ventries[4].total);
+    }
+
+    /// ### Test `transfer_entries_per_account()`
+    /// This test transfers Funds between two Clients
+    /// Both Accounts get an Entry for the "_transfer_"
+    #[test]
+    fn transfer_entries_per_account() {
+        //-------------------------------------
+        //Test Ledger Entries of a Transfer
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,1,1,10.0,,\n\
+                transfer,1,2,4.0,,2\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_ledger_entries(1).len(), 2);
+        assert_eq!(imp.get_ledger_entries(1)[1].available_delta, -4.0);
+        assert_eq!(imp.get_ledger_entries(2).len(), 1);
+        assert_eq!(imp.get_ledger_entries(2)[0].tx, 2);
+        assert_eq!(imp.get_ledger_entries(2)[0].available, 4.0);
+    }
+
+    /// ### Test `export_ledger_per_client()`
+    /// This test exports the Ledger of one Client in "_USD_"
+    /// Only the Entries of this Client are exported with their Currency
+    #[test]
+    fn export_ledger_per_client() {
+        //-------------------------------------
+        //Test Ledger Export of one Client
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(500);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,10.0,,,\n\
+                deposit,2,2,5.0,,,USD\n\
+                withdrawal,2,3,1.5,,,USD\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_ledger_str(Some(2)).as_str(),
+            "client,sequence,tx,type,currency,available_delta,held_delta,total_delta,available,held,total,at\n\
+            2,1,2,deposit,USD,5.0,0.0,5.0,5.0,0.0,5.0,500\n\
+            2,2,3,withdrawal,USD,-1.5,0.0,-1.5,3.5,0.0,3.5,500\n"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::app::notifier::{EventFileSink, EventFormat};
+use client_accounting::model::limit::LimitSchedule;
+
+use std::fs;
+
+//==============================================================================
+// Auxiliary Functions
+
+fn create_events_filename(sname: &str) -> String {
+    let pfile = std::env::temp_dir().join(format!(
+        "client-accounting-{}-{}",
+        std::process::id(),
+        sname
+    ));
+
+    if pfile.exists() {
+        fs::remove_file(&pfile).unwrap();
+    }
+
+    pfile.to_string_lossy().into_owned()
+}
+
+/// Builds the Limits used by the Tests
+fn build_limit_schedule() -> LimitSchedule {
+    let mut limits = LimitSchedule::new();
+
+    assert_eq!(
+        limits
+            .import_str(
+                "client,overdraft,withdrawal_limit,max_transaction,max_balance\n1,5.0,,,\n2,,,50.0,100.0\n3,,10.0,,\n"
+            )
+            .ok(),
+        Some(3)
+    );
+
+    limits
+}
+
+#[cfg(test)]
+mod limit_tests {
+    use super::*;
+
+    /// ### Test `withdrawal_with_overdraft()`
+    /// This test withdraws more than the `available` Funds within the Overdraft
+    /// A second "_withdrawal_" beyond the Overdraft is refused
+    #[test]
+    fn withdrawal_with_overdraft() {
+        //-------------------------------------
+        //Test Withdrawal within the Overdraft
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_limit_schedule(build_limit_schedule());
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\nwithdrawal,1,2,14.0\nwithdrawal,1,3,2.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,-4.0,0.0,-4.0,false\n"
+        );
+        assert_eq!(imp.get_transaction(2).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+    }
+
+    /// ### Test `transaction_and_balance_limits()`
+    /// This test refuses a "_deposit_" above the Transaction Limit
+    /// and a "_deposit_" which exceeds the Balance Limit
+    /// The Events carry distinct Reasons
+    #[test]
+    fn transaction_and_balance_limits() {
+        //-------------------------------------
+        //Test Transaction and Balance Limits
+
+        let sfilename = create_events_filename("limit-events.csv");
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_limit_schedule(build_limit_schedule());
+        imp.add_observer(Box::new(EventFileSink::new(
+            &sfilename,
+            EventFormat::Csv,
+            false,
+        )));
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,2,1,60.0\ndeposit,2,2,50.0\ndeposit,2,3,40.0\ndeposit,2,4,20.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n2,90.0,0.0,90.0,false\n"
+        );
+
+        drop(imp);
+
+        let sevents = fs::read_to_string(&sfilename).unwrap();
+
+        println!("{}", sevents.as_str());
+
+        assert_eq!(
+            sevents.as_str(),
+            "event,client,tx,amount,reason\n\
+            DepositRejected,2,1,60.0,transaction_limit_exceeded\n\
+            DepositAccepted,2,2,50.0,\n\
+            DepositAccepted,2,3,40.0,\n\
+            DepositRejected,2,4,20.0,balance_limit_exceeded\n"
+        );
+
+        fs::remove_file(&sfilename).unwrap();
+    }
+
+    /// ### Test `rolling_withdrawal_limit()`
+    /// This test withdraws up to the Withdrawal Limit within one Day
+    /// A further "_withdrawal_" on the same Day is refused
+    /// After the Window has passed the "_withdrawal_" is accepted
+    #[test]
+    fn rolling_withdrawal_limit() {
+        //-------------------------------------
+        //Test rolling Withdrawal Limit
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_limit_schedule(build_limit_schedule());
+        imp.set_clock(1_000_000);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,3,1,50.0\nwithdrawal,3,2,6.0\nwithdrawal,3,3,4.0\nwithdrawal,3,4,1.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(3).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(4).unwrap().status, -1);
+
+        imp.set_clock(1_000_000 + 86400);
+
+        assert_eq!(imp.import_movements_str("withdrawal,3,5,1.0\n", false), 0);
+
+        assert_eq!(imp.get_transaction(5).unwrap().status, 1);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n3,39.0,0.0,39.0,false\n"
+        );
+    }
+
+    /// ### Test `withdrawal_limit_after_restart()`
+    /// This test counts a "_withdrawal_" and a "_transfer_" for the Withdrawal Limit
+    /// while "_deposits_" in the same Window are not counted
+    /// After the State is loaded again the Window still holds both Movements
+    #[test]
+    fn withdrawal_limit_after_restart() {
+        //-------------------------------------
+        //Test Withdrawal Window restored from the State
+
+        let pdirectory = std::env::temp_dir().join(format!(
+            "client-accounting-limit-restart-{}",
+            std::process::id()
+        ));
+        let sstatefile = pdirectory.join("state.json");
+
+        fs::create_dir_all(&pdirectory).unwrap();
+
+        if sstatefile.exists() {
+            fs::remove_file(&sstatefile).unwrap();
+        }
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_limit_schedule(build_limit_schedule());
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+        imp.set_clock(1_000_000);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,3,1,50.0,,\n\
+                withdrawal,3,2,6.0,,\n\
+                transfer,3,3,3.0,,4\n\
+                deposit,3,4,20.0,,\n",
+                true
+            ),
+            0
+        );
+        assert!(imp.save_state().is_ok());
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_limit_schedule(build_limit_schedule());
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+        imp.set_clock(1_000_100);
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(
+            imp.import_movements_str("withdrawal,3,5,2.0\nwithdrawal,3,6,1.0\n", false),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(5).unwrap().status, -1);
+        assert_eq!(imp.get_transaction(6).unwrap().status, 1);
+
+        imp.set_clock(1_000_000 + 86400);
+
+        assert_eq!(imp.import_movements_str("withdrawal,3,7,9.0\n", false), 0);
+
+        assert_eq!(imp.get_transaction(7).unwrap().status, 1);
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+}
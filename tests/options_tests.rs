@@ -0,0 +1,94 @@
+use client_accounting::app::options::RunOptions;
+use client_accounting::app::RunClientAccounting;
+use client_accounting::model::policy::ClearingPolicy;
+
+#[cfg(test)]
+mod options_tests {
+    use super::*;
+
+    /// ### Test `parse_known_options()`
+    /// This test parses a Command with Values and Flags
+    /// The Command Arguments must be kept in Order
+    /// The Values must be parsed into their Types
+    #[test]
+    fn parse_known_options() {
+        //-------------------------------------
+        //Test known Options are parsed
+
+        let options = RunOptions::parse([
+            "admin",
+            "credit",
+            "--state=state.json",
+            "--client=3",
+            "--amount=2.5",
+            "--max-age=600",
+            "--currency= eur ",
+            "--clearing=settle",
+            "--summary",
+            "--reorder-flush",
+            "-v",
+        ])
+        .unwrap();
+
+        assert_eq!(options.command, "admin");
+        assert_eq!(options.command_args, vec![String::from("credit")]);
+        assert_eq!(options.state, "state.json");
+        assert_eq!(options.client, Some(3));
+        assert_eq!(options.amount, Some(2.5));
+        assert_eq!(options.max_age, Some(600));
+        assert_eq!(options.currency, Some(String::from("EUR")));
+        assert_eq!(options.clearing, Some(ClearingPolicy::Settlement));
+        assert!(options.summary);
+        assert!(options.reorder_flush);
+        assert!(!options.quiet);
+        assert!(!options.debug);
+    }
+
+    /// ### Test `reject_unknown_options()`
+    /// This test parses misspelled Options and invalid Values
+    /// Each of them must be refused with the Error Code `3`
+    #[test]
+    fn reject_unknown_options() {
+        //-------------------------------------
+        //Test unknown Options are refused
+
+        for vargs in [
+            vec!["--stat=state.json", "transactions.csv"],
+            vec!["--verbos", "transactions.csv"],
+            vec!["-x", "transactions.csv"],
+            vec!["--client=abc", "transactions.csv"],
+            vec!["--limit-window=0", "transactions.csv"],
+        ]
+        .iter()
+        {
+            let rsoptions = RunOptions::parse(vargs);
+
+            println!("{:?}: {:?}", vargs, rsoptions.as_ref().err());
+
+            assert_eq!(rsoptions.unwrap_err().code, 3);
+        } //for vargs in [ ... ].iter()
+
+        let e = RunOptions::parse(["--stat=state.json"]).unwrap_err();
+
+        assert_eq!(e.message, "Option '--stat' is unknown.");
+    }
+
+    /// ### Test `setters_without_parser()`
+    /// This test configures the Application Object with its Setters
+    /// as it was done before the Options were parsed into `RunOptions`
+    /// A Transactions File that cannot be opened must end the Run with the Error Code `1`
+    #[test]
+    fn setters_without_parser() {
+        //-------------------------------------
+        //Test the Setters still configure the Run
+
+        let mut accounting = RunClientAccounting::new();
+
+        accounting.set_quiet(true);
+        accounting.set_import(true);
+        accounting.set_transaction_filename("no-such-transactions.csv");
+
+        assert!(accounting.is_import());
+        assert_eq!(accounting.do_run(), 1);
+    }
+}
@@ -0,0 +1,87 @@
+use client_accounting::app::importer::MovementImporter;
+
+#[cfg(test)]
+mod reconcile_tests {
+    use super::*;
+
+    /// ### Test `reconcile_within_tolerance()`
+    /// This test reconciles the Accounts with expected Balances which differ by less
+    /// than the Tolerance
+    /// The Reconciliation finds no Differences
+    #[test]
+    fn reconcile_within_tolerance() {
+        //-------------------------------------
+        //Test Reconciliation within the Tolerance
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                deposit,2,2,5.0\n\
+                dispute,2,2,\n",
+                true
+            ),
+            0
+        );
+
+        let reconciliation = imp.reconcile(
+            "client,available,held,total,locked\n\
+            1,10.0001,0.0,10.0001,false\n\
+            2,0.0,5.0,5.0,false\n",
+            0.001,
+        );
+
+        println!("{}", reconciliation.export_csv(true, false));
+
+        assert!(reconciliation.is_clean());
+    }
+
+    /// ### Test `reconcile_differences()`
+    /// This test reconciles the Accounts with expected Balances which have a missing Client,
+    /// an extra Account, a differing Balance and a differing Lock
+    /// Each Difference is reported with its Client and Field
+    #[test]
+    fn reconcile_differences() {
+        //-------------------------------------
+        //Test Reconciliation with Differences
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                deposit,2,2,5.0\n\
+                dispute,2,2,\n\
+                chargeback,2,2,\n\
+                deposit,4,3,1.0\n",
+                true
+            ),
+            0
+        );
+
+        let reconciliation = imp.reconcile(
+            "client,available,held,total,locked\n\
+            1,9.5,0.0,10.0,false\n\
+            2,0.0,0.0,0.0,false\n\
+            3,1.0,0.0,1.0,false\n",
+            0.0,
+        );
+
+        assert!(!reconciliation.is_clean());
+        assert_eq!(
+            reconciliation.export_csv(true, false).as_str(),
+            "client,kind,field,expected,actual\n\
+            1,mismatch,available,9.5,10.0\n\
+            2,mismatch,locked,false,true\n\
+            3,missing,,,\n\
+            4,extra,,,\n"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use client_accounting::app::importer::MovementImporter;
+
+#[cfg(test)]
+mod refund_tests {
+    use client_accounting::model::transaction::TransactionFactory;
+
+    /// ### Test `refund_withdrawal()`
+    /// This test refunds a "_withdrawal_" of `4.0` in 2 partial Refunds
+    /// A third Refund exceeding the remaining Amount is refused
+    /// The Refunds are linked to the original Transaction
+    #[test]
+    fn refund_withdrawal() {
+        //-------------------------------------
+        //Test Refund a Withdrawal
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original\ndeposit,11,5,10.0\nwithdrawal,11,7,4.0\nrefund,11,8,1.5,7\nrefund,11,9,2.0,7\nrefund,11,10,1.0,7\n",
+                true
+            ),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+        let shistory = imp.export_history_str();
+
+        println!("{}", saccounts.as_str());
+        println!("{}", shistory.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,9.5,0.0,9.5,false\n"
+        );
+        assert_eq!(imp.get_transaction(7).unwrap().refunded, 3.5);
+        assert_eq!(imp.get_transaction(8).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(9).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(10).unwrap().status, -1);
+    }
+
+    /// ### Test `find_refunds()`
+    /// This test finds the Refunds linked to an original Transaction
+    #[test]
+    fn find_refunds() {
+        //-------------------------------------
+        //Test find Refunds of a Transaction
+
+        let txfact = TransactionFactory::from_csv_str(
+            "type,client,tx,amount,original\nrefund,11,9,2.0,7\nrefund,11,8,1.5,7\nrefund,11,10,1.0,6\n",
+            true,
+            true,
+            false,
+        );
+        let vrefunds: Vec<u32> = txfact.get_refunds(7).iter().map(|txrec| txrec.tx).collect();
+
+        assert_eq!(vrefunds, vec![8, 9]);
+    }
+
+    /// ### Test `reverse_deposit()`
+    /// This test reverses a "_deposit_" with a Refund without Amount
+    /// The whole remaining Amount is taken back from the Client
+    /// The reversed Deposit cannot be disputed anymore
+    #[test]
+    fn reverse_deposit() {
+        //-------------------------------------
+        //Test Reversal of a Deposit
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,5,10.0\ndeposit,11,6,2.0\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.import_movements_str("refund,11,8,,6\ndispute,11,6,\n", false),
+            0
+        );
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,10.0,0.0,10.0,false\n"
+        );
+
+        let txrefund = imp.get_transaction(8).unwrap();
+
+        assert_eq!(txrefund.status, 1);
+        assert_eq!(txrefund.amount, 2.0);
+        assert_eq!(txrefund.original, Some(6));
+        assert_eq!(imp.get_transaction(6).unwrap().status, 1);
+    }
+
+    /// ### Test `refund_foreign_transaction()`
+    /// This test refunds a "_withdrawal_" of another Client
+    /// The Refund is refused and marked as invalid with `status` (`-1`)
+    #[test]
+    fn refund_foreign_transaction() {
+        //-------------------------------------
+        //Test Refund of a foreign Transaction fails
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original\ndeposit,11,5,10.0\nwithdrawal,11,7,4.0\nrefund,12,8,4.0,7\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(imp.get_transaction(8).unwrap().status, -1);
+        assert_eq!(imp.get_transaction(7).unwrap().refunded, 0.0);
+    }
+}
@@ -0,0 +1,225 @@
+use client_accounting::app::importer::MovementImporter;
+
+#[cfg(test)]
+mod reorder_tests {
+
+    /// ### Test `reorder_within_window()`
+    /// This test delivers a "_dispute_" before the "_withdrawal_" it preceded
+    /// Within the Reorder Window the "_dispute_" is applied first
+    /// and the "_withdrawal_" fails on the `held` Funds
+    #[test]
+    fn reorder_within_window() {
+        //-------------------------------------
+        //Test Movements are reordered within the Window
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_reorder_window(10);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,timestamp\n\
+                deposit,11,9,1.1,100\n\
+                withdrawal,11,3,0.5,105\n\
+                dispute,11,9,,103\n",
+                true
+            ),
+            0
+        );
+
+        //All Movements are still buffered
+        assert_eq!(imp.get_transaction(9).unwrap().status, 0);
+
+        imp.flush_movements();
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,0.0,1.1,1.1,false\n"
+        );
+        assert_eq!(imp.get_transaction(9).unwrap().status, -2);
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+        assert!(imp.get_late_arrivals().is_empty());
+    }
+
+    /// ### Test `release_after_window()`
+    /// This test applies buffered Movements when a Movement beyond the Window arrives
+    #[test]
+    fn release_after_window() {
+        //-------------------------------------
+        //Test Movements are released after the Window
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_reorder_window(10);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,timestamp\ndeposit,11,2,2.0,102\ndeposit,11,1,1.0,100\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.import_movements_str("deposit,11,3,3.0,,,,,111\n", false),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(1).unwrap().status, 1);
+        assert_eq!(imp.get_transaction(2).unwrap().status, 0);
+        assert_eq!(imp.get_transaction(3).unwrap().status, 0);
+
+        imp.flush_movements();
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,6.0,0.0,6.0,false\n"
+        );
+    }
+
+    /// ### Test `report_late_arrival()`
+    /// This test delivers a Movement which is older than the Reorder Window
+    /// The Movement is applied and reported as late Arrival
+    #[test]
+    fn report_late_arrival() {
+        //-------------------------------------
+        //Test late Arrivals are reported
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_reorder_window(10);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,timestamp\n\
+                deposit,11,1,1.0,200\n\
+                deposit,11,2,2.0,150\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(2).unwrap().status, 1);
+        assert_eq!(
+            imp.export_late_arrivals_str().as_str(),
+            "type,client,tx,timestamp,newest\ndeposit,11,2,150,200\n"
+        );
+
+        imp.flush_movements();
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,3.0,0.0,3.0,false\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod split_run_tests {
+    use client_accounting::app::importer::MovementImporter;
+    use client_accounting::model::account::AccountFactory;
+    use client_accounting::model::rule::RuleSet;
+
+    use std::fs;
+
+    const MOVEMENTS_FIRST_CSV: &str = "type,client,tx,amount,timestamp\n\
+        deposit,1,1,10.0,100\n\
+        deposit,2,2,5.0,101\n\
+        withdrawal,1,3,2.0,108\n";
+    const MOVEMENTS_SECOND_CSV: &str = "type,client,tx,amount,timestamp\n\
+        dispute,2,2,,104\n\
+        deposit,1,4,1.0,115\n\
+        withdrawal,1,5,9.0,125\n\
+        deposit,2,6,1.0,102\n";
+
+    fn create_importer() -> MovementImporter {
+        let mut imp = MovementImporter::new();
+        let mut rules = RuleSet::new();
+
+        rules
+            .import_str("rule,kind,threshold,window,action\nlarge,withdrawal_ratio,1.5,,flag\n")
+            .unwrap();
+
+        imp.set_debug(true);
+        imp.set_reorder_window(10);
+        imp.set_rule_set(rules);
+
+        imp
+    }
+
+    /// ### Test `split_matches_single_run()`
+    /// This test imports the Movements in two Runs on a State File
+    /// while Movements are still waiting in the Reorder Buffer
+    /// The Accounts, Late Arrivals, Rule Hits and the Summary
+    /// must be the same as after a single Run over all Movements
+    #[test]
+    fn split_matches_single_run() {
+        //-------------------------------------
+        //Test a split Import matches a single Run
+
+        let sstatefile = std::env::temp_dir()
+            .join(format!(
+                "client-accounting-split-matches-single-run-{}.json",
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned();
+        let mut single = create_importer();
+
+        single.import_movements_str(MOVEMENTS_FIRST_CSV, true);
+        single.import_movements_str(MOVEMENTS_SECOND_CSV, true);
+        single.flush_movements();
+
+        let mut first = create_importer();
+
+        first.set_state_filename(&sstatefile);
+        first.import_movements_str(MOVEMENTS_FIRST_CSV, true);
+        first.save_state().unwrap();
+
+        //The Withdrawal is still buffered
+        assert_eq!(first.get_transaction(3).unwrap().status, 0);
+
+        let mut second = create_importer();
+
+        second.set_state_filename(&sstatefile);
+        second.load_state().unwrap();
+        second.import_movements_str(MOVEMENTS_SECOND_CSV, true);
+        second.flush_movements();
+
+        let saccounts = second.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        let accsingle =
+            AccountFactory::from_str(single.export_accounts_str().as_str(), true, true, false);
+        let accsplit = AccountFactory::from_str(saccounts.as_str(), true, true, false);
+
+        for iclient in [1, 2].iter() {
+            let accsgl = accsingle.lstaccounts.get(iclient).unwrap();
+            let accspl = accsplit.lstaccounts.get(iclient).unwrap();
+
+            assert_eq!(accspl.available, accsgl.available);
+            assert_eq!(accspl.held, accsgl.held);
+            assert_eq!(accspl.total, accsgl.total);
+        }
+
+        assert_eq!(accsplit.lstaccounts.get(&1).unwrap().available, 0.0);
+        assert_eq!(accsplit.lstaccounts.get(&2).unwrap().held, 5.0);
+        assert_eq!(second.get_late_arrivals(), single.get_late_arrivals());
+        assert_eq!(second.get_late_arrivals().len(), 1);
+        assert_eq!(
+            second
+                .get_rule_hits()
+                .iter()
+                .map(|hit| (hit.rule.clone(), hit.tx))
+                .collect::<Vec<_>>(),
+            vec![(String::from("large"), 5)]
+        );
+        assert_eq!(second.get_rule_hits().len(), single.get_rule_hits().len());
+        assert_eq!(second.get_summary(), single.get_summary());
+
+        fs::remove_file(&sstatefile).unwrap();
+    }
+}
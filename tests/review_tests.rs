@@ -0,0 +1,276 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::limit::LimitSchedule;
+use client_accounting::model::rule::RuleSet;
+
+use std::fs;
+
+#[cfg(test)]
+mod review_tests {
+    use super::*;
+
+    /// ### Test `review_approve_in_order()`
+    /// This test holds a Withdrawal of a fresh Deposit for a Review
+    /// The later Movements of the Client wait behind it while other Clients go on
+    /// Only the first held Movement can be approved and the waiting Movements follow it
+    /// A released Movement which matches the Rule again is held in front of the Queue
+    #[test]
+    fn review_approve_in_order() {
+        //-------------------------------------
+        //Test Approval in the Order of the Client
+
+        let mut rules = RuleSet::new();
+        let mut imp = MovementImporter::new();
+
+        rules
+            .import_str("rule,kind,threshold,window,action\ncashout,rapid_cycle,0.9,600,hold\n")
+            .unwrap();
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+        imp.set_rule_set(rules);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                withdrawal,1,2,9.5\n\
+                deposit,1,3,1.0\n\
+                withdrawal,1,4,1.5\n\
+                deposit,2,5,3.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_review_str().as_str(),
+            "client,tx,type,amount,reason,held_at\n\
+            1,2,withdrawal,9.5,cashout,100\n\
+            1,3,deposit,1.0,,100\n\
+            1,4,withdrawal,1.5,,100\n"
+        );
+        assert!(imp.export_accounts_str().contains("2,3.0,0.0,3.0,false\n"));
+
+        let error = imp.approve_movement(4, "ops").unwrap_err();
+
+        assert_eq!(error.code, 3);
+        assert!(imp.approve_movement(2, "").is_err());
+        assert_eq!(imp.approve_movement(2, "ops").ok(), Some(2));
+
+        //The released Withdrawal matches the Rule again
+        assert_eq!(
+            imp.export_review_str().as_str(),
+            "client,tx,type,amount,reason,held_at\n\
+            1,4,withdrawal,1.5,cashout,100\n"
+        );
+        assert_eq!(imp.approve_movement(4, "ops").ok(), Some(4));
+
+        assert!(imp.get_review_queue().is_empty());
+        assert_eq!(
+            imp.get_transaction(2).unwrap().operator.as_deref(),
+            Some("ops")
+        );
+        assert!(imp.export_accounts_str().contains("1,0.0,0.0,0.0,false\n"));
+    }
+
+    /// ### Test `review_reject_limit_and_persist()`
+    /// This test holds Withdrawals beyond the Withdrawal Limit for a Review
+    /// The Queue is saved with the State and loaded again
+    /// The rejected Withdrawal is marked as invalid and the next held Withdrawal is decided next
+    #[test]
+    fn review_reject_limit_and_persist() {
+        //-------------------------------------
+        //Test Rejection of held Limit Violations
+
+        let pdirectory =
+            std::env::temp_dir().join(format!("client-accounting-review-{}", std::process::id()));
+        let sstatefile = pdirectory.join("state.json");
+        let mut limits = LimitSchedule::new();
+        let mut imp = MovementImporter::new();
+
+        fs::create_dir_all(&pdirectory).unwrap();
+
+        if sstatefile.exists() {
+            fs::remove_file(&sstatefile).unwrap();
+        }
+
+        limits
+            .import_str("client,overdraft,withdrawal_limit,max_transaction,max_balance\n1,,5.0,,\n")
+            .unwrap();
+        limits.set_review(true);
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+        imp.set_limit_schedule(limits);
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,20.0\n\
+                withdrawal,1,2,8.0\n\
+                withdrawal,1,3,2.0\n",
+                true
+            ),
+            0
+        );
+
+        assert!(imp.save_state().is_ok());
+
+        let mut imprestored = MovementImporter::new();
+
+        imprestored.set_debug(true);
+        imprestored.set_clock(200);
+        imprestored.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imprestored.load_state().is_ok());
+        assert_eq!(
+            imprestored.export_review_str().as_str(),
+            "client,tx,type,amount,reason,held_at\n\
+            1,2,withdrawal,8.0,withdrawal_limit_exceeded,100\n\
+            1,3,withdrawal,2.0,,100\n"
+        );
+
+        assert_eq!(
+            imprestored.reject_held_movement(2, "ops", "limit").ok(),
+            Some(2)
+        );
+
+        let txrec = imprestored.get_transaction(2).unwrap();
+
+        assert_eq!(txrec.status, -1);
+        assert_eq!(txrec.reason.as_deref(), Some("limit"));
+        assert!(imprestored.get_review_queue().is_empty());
+        assert_eq!(
+            imprestored.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,18.0,0.0,18.0,false\n"
+        );
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+
+    /// ### Test `review_approve_after_restart()`
+    /// This test holds a Withdrawal beyond the Withdrawal Limit and saves the Queue
+    /// After the Restart the aged Report shows the blocked Deposit behind it
+    /// A Decision without Operator leaves the Queue and the Transaction unchanged
+    /// The approved Withdrawal is applied and the blocked Deposit follows
+    #[test]
+    fn review_approve_after_restart() {
+        //-------------------------------------
+        //Test Approval from the persisted Review Queue
+
+        let pdirectory = std::env::temp_dir().join(format!(
+            "client-accounting-review-restart-{}",
+            std::process::id()
+        ));
+        let sstatefile = pdirectory.join("state.json");
+        let mut limits = LimitSchedule::new();
+        let mut imp = MovementImporter::new();
+
+        fs::create_dir_all(&pdirectory).unwrap();
+
+        if sstatefile.exists() {
+            fs::remove_file(&sstatefile).unwrap();
+        }
+
+        limits
+            .import_str("client,overdraft,withdrawal_limit,max_transaction,max_balance\n1,,5.0,,\n")
+            .unwrap();
+        limits.set_review(true);
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+        imp.set_limit_schedule(limits.clone());
+        imp.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,20.0\n\
+                withdrawal,1,2,8.0\n\
+                deposit,1,3,1.0\n",
+                true
+            ),
+            0
+        );
+
+        assert!(imp.save_state().is_ok());
+
+        let mut imprestored = MovementImporter::new();
+
+        imprestored.set_debug(true);
+        imprestored.set_clock(400);
+        imprestored.set_limit_schedule(limits);
+        imprestored.set_state_filename(sstatefile.to_str().unwrap());
+
+        assert!(imprestored.load_state().is_ok());
+        assert_eq!(
+            imprestored.export_aged_review_str(200).as_str(),
+            "client,tx,type,reason,held_at,age,waiting\n\
+            1,2,withdrawal,withdrawal_limit_exceeded,100,300,1\n"
+        );
+        assert_eq!(
+            imprestored.export_aged_review_str(500).as_str(),
+            "client,tx,type,reason,held_at,age,waiting\n"
+        );
+
+        assert!(imprestored.reject_held_movement(2, "", "limit").is_err());
+
+        let txrec = imprestored.get_transaction(2).unwrap();
+
+        assert_eq!(txrec.status, 0);
+        assert_eq!(txrec.reason, None);
+        assert_eq!(imprestored.get_review_queue().vitems.len(), 2);
+
+        assert_eq!(imprestored.approve_movement(2, "ops").ok(), Some(2));
+
+        let txrec = imprestored.get_transaction(2).unwrap();
+
+        assert_eq!(txrec.status, 1);
+        assert_eq!(txrec.operator.as_deref(), Some("ops"));
+        assert!(imprestored.get_review_queue().is_empty());
+        assert_eq!(
+            imprestored.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,13.0,0.0,13.0,false\n"
+        );
+
+        fs::remove_dir_all(&pdirectory).unwrap();
+    }
+
+    /// ### Test `review_approve_transaction_limit()`
+    /// This test holds a Deposit above the Transaction Limit for a Review
+    /// The approved Deposit is applied without checking the Limit again
+    #[test]
+    fn review_approve_transaction_limit() {
+        //-------------------------------------
+        //Test Approval of a held Transaction Limit Violation
+
+        let mut limits = LimitSchedule::new();
+        let mut imp = MovementImporter::new();
+
+        limits
+            .import_str("client,overdraft,withdrawal_limit,max_transaction,max_balance\n1,,,5.0,\n")
+            .unwrap();
+        limits.set_review(true);
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+        imp.set_limit_schedule(limits);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,3.0\ndeposit,1,2,7.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_review_queue().vitems.len(), 1);
+        assert_eq!(imp.approve_movement(2, "ops").ok(), Some(2));
+        assert_eq!(imp.get_transaction(2).unwrap().status, 1);
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,10.0,0.0,10.0,false\n"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::rule::{RuleAction, RuleSet};
+use client_accounting::model::transaction::TransactionFactory;
+
+#[cfg(test)]
+mod rule_tests {
+    use super::*;
+
+    /// ### Test `rule_withdrawal_ratio()`
+    /// This test configures a flagging Rule for Withdrawals above twice the average Deposit
+    /// and a rejecting Rule for Withdrawals above four Times the average Deposit
+    /// The flagged Withdrawal is applied and the rejected Withdrawal is refused
+    #[test]
+    fn rule_withdrawal_ratio() {
+        //-------------------------------------
+        //Test Withdrawal Ratio Rules
+
+        let mut rules = RuleSet::new();
+        let mut imp = MovementImporter::new();
+
+        assert_eq!(
+            rules
+                .import_str(
+                    "rule,kind,threshold,window,action\n\
+                    large,withdrawal_ratio,2.0,,flag\n\
+                    huge,withdrawal_ratio,4.0,,reject\n"
+                )
+                .unwrap(),
+            2
+        );
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+        imp.set_rule_set(rules);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,100.0\n\
+                deposit,1,2,20.0\n\
+                deposit,1,3,30.0\n\
+                withdrawal,1,4,30.0\n\
+                withdrawal,1,5,110.0\n\
+                withdrawal,1,6,250.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n1,10.0,0.0,10.0,false\n"
+        );
+        assert_eq!(imp.get_transaction(6).unwrap().status, -1);
+        assert_eq!(
+            imp.export_rule_hits_str().as_str(),
+            "rule,action,client,tx,type,amount,at,message\n\
+            large,flag,1,5,withdrawal,110.0,100,Amount '110' exceeds 2 Times the average Deposit '50'\n\
+            large,flag,1,6,withdrawal,250.0,100,Amount '250' exceeds 2 Times the average Deposit '50'\n\
+            huge,reject,1,6,withdrawal,250.0,100,Amount '250' exceeds 4 Times the average Deposit '50'\n"
+        );
+    }
+
+    /// ### Test `rule_dispute_count()`
+    /// This test rejects the third Dispute of a Client within one Hour
+    /// The Dispute after the Window is accepted again
+    #[test]
+    fn rule_dispute_count() {
+        //-------------------------------------
+        //Test Dispute Count Rule
+
+        let mut rules = RuleSet::new();
+        let mut imp = MovementImporter::new();
+
+        rules
+            .import_str("rule,kind,threshold,window,action\nstorm,dispute_count,2,3600,reject\n")
+            .unwrap();
+
+        imp.set_debug(true);
+        imp.set_clock(1000);
+        imp.set_rule_set(rules);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,1.0\n\
+                deposit,1,2,1.0\n\
+                deposit,1,3,1.0\n\
+                deposit,1,4,1.0\n\
+                dispute,1,1,\n\
+                dispute,1,2,\n\
+                dispute,1,3,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(3).unwrap().status, 1);
+        assert_eq!(imp.get_rule_hits().len(), 1);
+        assert_eq!(imp.get_rule_hits()[0].action, RuleAction::Reject);
+
+        imp.set_clock(1000 + 3601);
+
+        assert_eq!(imp.import_movements_str("dispute,1,4,\n", false), 0);
+
+        assert_eq!(imp.get_transaction(4).unwrap().status, -2);
+        assert_eq!(imp.get_rule_hits().len(), 1);
+    }
+
+    /// ### Test `rule_hold_rapid_cycle_and_unlock()`
+    /// This test holds a Withdrawal of a fresh Deposit and flags a Movement after an Unlock
+    /// The held Withdrawal is not applied
+    #[test]
+    fn rule_hold_rapid_cycle_and_unlock() {
+        //-------------------------------------
+        //Test Rapid Cycle and After Unlock Rules
+
+        let mut rules = RuleSet::new();
+        let mut imp = MovementImporter::new();
+
+        rules
+            .import_str(
+                "rule,kind,threshold,window,action\n\
+                cashout,rapid_cycle,0.9,600,hold\n\
+                unlocked,after_unlock,,3600,flag\n",
+            )
+            .unwrap();
+
+        imp.set_debug(true);
+        imp.set_clock(1000);
+        imp.set_rule_set(rules);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                withdrawal,1,2,9.5\n\
+                deposit,2,3,4.0\n\
+                dispute,2,3,\n\
+                chargeback,2,3,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(imp.get_transaction(2).unwrap().status, 0);
+        assert_eq!(imp.get_held_movements().len(), 1);
+        assert_eq!(imp.get_held_movements()[0].tx, 2);
+
+        assert!(imp.unlock_account(2, "ops", "verified").is_ok());
+        assert_eq!(imp.import_movements_str("deposit,2,4,1.0\n", false), 0);
+
+        let saccounts = imp.export_accounts_str();
+
+        assert!(saccounts.contains("1,10.0,0.0,10.0,false\n"));
+        assert!(saccounts.contains("2,1.0,0.0,1.0,false\n"));
+
+        let vhits = imp.get_rule_hits();
+
+        assert_eq!(vhits.len(), 2);
+        assert_eq!(vhits[0].rule, "cashout");
+        assert_eq!(vhits[0].action, RuleAction::Hold);
+        assert_eq!(vhits[1].rule, "unlocked");
+        assert_eq!(vhits[1].tx, 4);
+    }
+
+    /// ### Test `rule_client_index_after_restart()`
+    /// This test indexes the Transactions per Client and skips repeated Transaction IDs
+    /// After a Restart from the State File the Withdrawal Ratio still sees
+    /// only the Deposits of the withdrawing Client
+    #[test]
+    fn rule_client_index_after_restart() {
+        //-------------------------------------
+        //Test Client Index of the Rule Evaluation
+
+        let txfact = TransactionFactory::from_csv_str(
+            "type, client, tx, amount\ndeposit,1,1,1.0\ndeposit,2,2,2.0\ndeposit,1,3,3.0\ndeposit,1,1,1.0\n",
+            true,
+            true,
+            false,
+        );
+        let vclient: Vec<u32> = txfact
+            .get_client_transactions(1)
+            .map(|txrec| txrec.tx)
+            .collect();
+
+        assert_eq!(vclient, vec![1, 3]);
+        assert_eq!(txfact.get_client_transactions(3).count(), 0);
+
+        let sstatefile = std::env::temp_dir()
+            .join(format!(
+                "client-accounting-rule-client-index-{}.json",
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned();
+        let mut rules = RuleSet::new();
+
+        rules
+            .import_str("rule,kind,threshold,window,action\nlarge,withdrawal_ratio,0.5,,flag\n")
+            .unwrap();
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+        imp.set_rule_set(rules.clone());
+        imp.set_state_filename(&sstatefile);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,50.0\ndeposit,2,2,1000.0\n",
+                true
+            ),
+            0
+        );
+        assert!(imp.save_state().is_ok());
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(200);
+        imp.set_rule_set(rules);
+        imp.set_state_filename(&sstatefile);
+
+        assert!(imp.load_state().is_ok());
+        assert_eq!(imp.import_movements_str("withdrawal,1,3,30.0\n", false), 0);
+
+        assert_eq!(
+            imp.export_rule_hits_str().as_str(),
+            "rule,action,client,tx,type,amount,at,message\n\
+            large,flag,1,3,withdrawal,30.0,200,Amount '30' exceeds 0.5 Times the average Deposit '50'\n"
+        );
+
+        std::fs::remove_file(&sstatefile).unwrap();
+    }
+}
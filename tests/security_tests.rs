@@ -1,4 +1,5 @@
 use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::policy::NegativeBalancePolicy;
 
 #[cfg(test)]
 mod dispute_fails_tests {
@@ -25,7 +26,7 @@ mod dispute_fails_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,5,3,0.5\ndispute,5,9,\n",
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,5,3,0.5\ndispute,5,9,\n",
                 true
             ),
             0
@@ -76,7 +77,7 @@ mod dispute_fails_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,2.2\ndispute,11,3,\n",
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,2.2\ndispute,11,3,\n",
                 true
             ),
             0
@@ -123,7 +124,7 @@ mod dispute_fails_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nchargeback,11,3,\n",
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nchargeback,11,3,\n",
                 true
             ),
             0
@@ -170,7 +171,7 @@ mod dispute_fails_tests {
 
         assert_eq!(
             imp.import_movements_str(
-                &"type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nchargeback,11,9,\n",
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nchargeback,11,9,\n",
                 true
             ),
             0
@@ -198,3 +199,140 @@ mod dispute_fails_tests {
         assert_eq!(otxrec9.unwrap().status, -2);
     }
 }
+
+#[cfg(test)]
+mod negative_balance_tests {
+    const MOVEMENTS: &str = "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nchargeback,11,9,\n";
+
+    /// ### Test `dispute_rejected()`
+    /// This test disputes a "_deposit_" whose Funds were partially withdrawn
+    /// with the Policy `Reject`
+    /// The Dispute is refused and the Chargeback fails because the Transaction is not disputed
+    /// Transaction (id: '9') must be marked as processed with `status` (`1`)
+    #[test]
+    fn dispute_rejected() {
+        //-------------------------------------
+        //Test Dispute on withdrawn Funds is refused
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_negative_balance_policy(super::NegativeBalancePolicy::Reject);
+
+        assert_eq!(imp.import_movements_str(MOVEMENTS, true), 0);
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,0.6,0.0,0.6,false\n"
+        );
+        assert_eq!(imp.get_transaction(9).unwrap().status, 1);
+    }
+
+    /// ### Test `dispute_with_debt()`
+    /// This test disputes a "_deposit_" whose Funds were partially withdrawn
+    /// with the Policy `Debt`
+    /// The Shortfall (`0.5`) is recorded as Debt and the Chargeback is applied
+    /// Transaction (id: '9') must be marked as invalid with `status` (`-1`)
+    #[test]
+    fn dispute_with_debt() {
+        //-------------------------------------
+        //Test Dispute on withdrawn Funds records a Debt
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_negative_balance_policy(super::NegativeBalancePolicy::Debt);
+
+        assert_eq!(imp.import_movements_str(MOVEMENTS, true), 0);
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,-0.5,0.0,-0.5,true\n"
+        );
+
+        let txrec = imp.get_transaction(9).unwrap();
+
+        assert_eq!(txrec.status, -1);
+        assert_eq!(txrec.debt, Some(0.5));
+    }
+
+    /// ### Test `dispute_capped()`
+    /// This test disputes a "_deposit_" whose Funds were partially withdrawn
+    /// with the Policy `Cap`
+    /// Only the `available` Funds (`0.6`) are placed on `held` and charged back
+    /// Transaction (id: '9') must be marked as invalid with `status` (`-1`)
+    #[test]
+    fn dispute_capped() {
+        //-------------------------------------
+        //Test Dispute on withdrawn Funds holds the available Funds
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_negative_balance_policy(super::NegativeBalancePolicy::Cap);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,0.0,0.6,0.6,false\n"
+        );
+        assert_eq!(imp.get_transaction(9).unwrap().held, Some(0.6));
+
+        assert_eq!(imp.import_movements_str("chargeback,11,9,\n", false), 0);
+
+        let saccounts = imp.export_accounts_str();
+
+        println!("{}", saccounts.as_str());
+
+        assert_eq!(
+            saccounts.as_str(),
+            "client,available,held,total,locked\n11,0.0,0.0,0.0,true\n"
+        );
+        assert_eq!(imp.get_transaction(9).unwrap().status, -1);
+    }
+
+    /// ### Test `resolve_capped_dispute()`
+    /// This test resolves a capped Dispute
+    /// Only the held Funds (`0.6`) are returned to `available`
+    #[test]
+    fn resolve_capped_dispute() {
+        //-------------------------------------
+        //Test Resolve a capped Dispute
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_negative_balance_policy(super::NegativeBalancePolicy::Cap);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,11,9,1.1\nwithdrawal,11,3,0.5\ndispute,11,9,\nresolve,11,9,\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(
+            imp.export_accounts_str().as_str(),
+            "client,available,held,total,locked\n11,0.6,0.0,0.6,false\n"
+        );
+
+        let txrec = imp.get_transaction(9).unwrap();
+
+        assert_eq!(txrec.status, 1);
+        assert_eq!(txrec.held, None);
+    }
+}
@@ -0,0 +1,144 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::statement::{StatementFormat, StatementRange};
+
+#[cfg(test)]
+mod statement_tests {
+    use super::*;
+
+    /// ### Test `statement_sequence_range()`
+    /// This test builds a Statement for the Sequence Numbers `2` to `4`
+    /// The opening Balance is the Balance after the first Entry
+    /// The Dispute and the Chargeback appear as their own Lines
+    #[test]
+    fn statement_sequence_range() {
+        //-------------------------------------
+        //Test Statement for a Sequence Range
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(100);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\n\
+                deposit,1,1,10.0\n\
+                deposit,1,2,5.0\n\
+                dispute,1,2,\n\
+                chargeback,1,2,\n\
+                withdrawal,1,3,1.0\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_statement_str(
+                1,
+                None,
+                &StatementRange::parse("sequence:2-4").unwrap(),
+                StatementFormat::Csv
+            )
+            .as_str(),
+            "sequence,at,tx,type,status,amount,available,held,balance\n\
+            ,,,opening,,,10.0,0.0,10.0\n\
+            2,100,2,deposit,-1,5.0,15.0,0.0,15.0\n\
+            3,100,2,dispute,-1,5.0,10.0,5.0,15.0\n\
+            4,100,2,chargeback,-1,5.0,10.0,0.0,10.0\n\
+            ,,,closing,,,10.0,0.0,10.0\n"
+        );
+
+        let statement = imp.build_statement(1, None, &StatementRange::All);
+
+        assert_eq!(statement.disputes, 1);
+        assert_eq!(statement.chargebacks, 1);
+        assert_eq!(statement.lines.len(), 4);
+        assert_eq!(statement.opening.total, 0.0);
+        assert_eq!(statement.closing.total, 10.0);
+    }
+
+    /// ### Test `statement_date_range()`
+    /// This test books Movements on 3 Days and builds the Statement of the second Day
+    /// The Movements of the first Day make up the opening Balance
+    #[test]
+    fn statement_date_range() {
+        //-------------------------------------
+        //Test Statement for a Date Range
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        //2024-01-01
+        imp.set_clock(1704067200);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,1,1,10.0\n", true),
+            0
+        );
+
+        imp.set_clock(1704067200 + 86400);
+
+        assert_eq!(imp.import_movements_str("withdrawal,1,2,4.0\n", false), 0);
+
+        imp.set_clock(1704067200 + 2 * 86400);
+
+        assert_eq!(imp.import_movements_str("deposit,1,3,1.0\n", false), 0);
+
+        let statement = imp.build_statement(
+            1,
+            None,
+            &StatementRange::parse("date:2024-01-02..2024-01-02").unwrap(),
+        );
+
+        assert_eq!(statement.opening.total, 10.0);
+        assert_eq!(statement.lines.len(), 1);
+        assert_eq!(statement.lines[0].tx, 2);
+        assert_eq!(statement.lines[0].balance, 6.0);
+        assert_eq!(statement.closing.total, 6.0);
+    }
+
+    /// ### Test `statement_layouts()`
+    /// This test exports a Statement in "_EUR_" as JSON and Text
+    /// Both Layouts show the Balances in "_EUR_" only
+    #[test]
+    fn statement_layouts() {
+        //-------------------------------------
+        //Test Statement JSON and Text Layouts
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+        imp.set_clock(7);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,3.0,,,EUR\n\
+                deposit,1,2,9.0,,,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            imp.export_statement_str(1, Some("EUR"), &StatementRange::All, StatementFormat::Json)
+                .as_str(),
+            "{\"client\":1,\"currency\":\"EUR\",\
+            \"opening\":{\"available\":0.0,\"held\":0.0,\"total\":0.0},\
+            \"lines\":[{\"sequence\":1,\"at\":7,\"tx\":1,\"type\":\"deposit\",\"status\":1,\
+            \"amount\":3.0,\"available\":3.0,\"held\":0.0,\"balance\":3.0}],\
+            \"closing\":{\"available\":3.0,\"held\":0.0,\"total\":3.0},\
+            \"disputes\":0,\"chargebacks\":0}"
+        );
+
+        let stext =
+            imp.export_statement_str(1, Some("EUR"), &StatementRange::All, StatementFormat::Text);
+
+        println!("{}", stext.as_str());
+
+        assert!(stext.starts_with("Statement of Client 1 in EUR\n"));
+        assert!(stext.contains("Closing Balance: available 3.0 held 0.0 total 3.0\n"));
+        assert!(!stext.contains("9.0"));
+    }
+}
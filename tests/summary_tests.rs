@@ -0,0 +1,144 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::summary::RunSummary;
+
+#[cfg(test)]
+mod summary_tests {
+    use super::*;
+
+    /// ### Test `summary_counts()`
+    /// This test processes Deposits, Withdrawals, a Dispute, a Chargeback
+    /// and a Line which cannot be parsed
+    /// The Summary counts the Movements by Type and the Rejections by Reason
+    /// and sums the Amounts
+    #[test]
+    fn summary_counts() {
+        //-------------------------------------
+        //Test Summary Counts and Amounts
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        imp.import_movements_str(
+            "type, client, tx, amount\n\
+            deposit,1,1,2.0\n\
+            withdrawal,1,2,5.0\n\
+            withdrawal,1,3,1.0\n\
+            bad,line\n\
+            deposit,2,4,3.0\n\
+            deposit,2,5,1.5\n\
+            dispute,2,5,\n\
+            dispute,2,4,\n\
+            chargeback,2,4,\n",
+            true,
+        );
+
+        let summary = imp.get_summary();
+
+        println!("{}", summary.export_text());
+
+        assert_eq!(summary.read, 9);
+        assert_eq!(summary.parsed, 8);
+        assert_eq!(summary.get_parse_errors(), 1);
+        assert_eq!(summary.types.get("deposit"), Some(&3));
+        assert_eq!(summary.types.get("withdrawal"), Some(&2));
+        assert_eq!(summary.types.get("dispute"), Some(&2));
+        assert_eq!(summary.rejected.get("insufficient_funds"), Some(&1));
+        assert_eq!(summary.get_rejected_count(), 1);
+        assert_eq!(summary.accounts_created, 2);
+        assert_eq!(summary.accounts_locked, 1);
+        assert_eq!(summary.deposited.get(""), Some(&6.5));
+        assert_eq!(summary.withdrawn.get(""), Some(&1.0));
+        assert_eq!(summary.held.get(""), Some(&1.5));
+        assert_eq!(summary.charged_back.get(""), Some(&3.0));
+    }
+
+    /// ### Test `summary_per_currency()`
+    /// This test processes Deposits, Withdrawals and a Dispute in the primary Currency
+    /// and in "_EUR_"
+    /// The Amounts are summed per Currency
+    #[test]
+    fn summary_per_currency() {
+        //-------------------------------------
+        //Test Summary Amounts per Currency
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination,currency\n\
+                deposit,1,1,2.0,,,\n\
+                deposit,1,2,10.0,,,EUR\n\
+                withdrawal,1,3,4.0,,,EUR\n\
+                deposit,2,4,1.5,,,EUR\n\
+                dispute,2,4,,,,EUR\n",
+                true
+            ),
+            0
+        );
+
+        let summary = imp.get_summary();
+
+        println!("{}", summary.export_text());
+
+        assert_eq!(summary.deposited.get(""), Some(&2.0));
+        assert_eq!(summary.deposited.get("EUR"), Some(&11.5));
+        assert_eq!(summary.withdrawn.get(""), None);
+        assert_eq!(summary.withdrawn.get("EUR"), Some(&4.0));
+        assert_eq!(summary.held.get("EUR"), Some(&1.5));
+        assert!(summary
+            .export_text()
+            .contains("  Deposited: 2.0\n  Deposited 'EUR': 11.5\n"));
+    }
+
+    /// ### Test `summary_single_sums()`
+    /// This test reads a Summary saved with one Sum over all Currencies
+    /// The Sums are read as Amounts of the primary Currency
+    #[test]
+    fn summary_single_sums() {
+        //-------------------------------------
+        //Test Summary with single Sums is read
+
+        let summary: RunSummary = serde_json::from_str(
+            "{\"read\":1,\"parsed\":1,\"rejected\":{},\"types\":{\"deposit\":1},\
+            \"accounts_created\":1,\"accounts_locked\":0,\"deposited\":2.0,\"withdrawn\":0.0,\
+            \"held\":0.0,\"charged_back\":0.0,\"elapsed_ms\":500,\"throughput\":2.0}",
+        )
+        .unwrap();
+
+        assert_eq!(summary.deposited.get(""), Some(&2.0));
+        assert!(summary.withdrawn.is_empty());
+        assert!(summary.held.is_empty());
+    }
+
+    /// ### Test `summary_json()`
+    /// This test exports the Summary of one Deposit as JSON
+    /// with the Throughput calculated from the Duration
+    #[test]
+    fn summary_json() {
+        //-------------------------------------
+        //Test Summary JSON Export
+
+        let mut imp = MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str("type, client, tx, amount\ndeposit,1,1,2.0\n", true),
+            0
+        );
+
+        let mut summary = imp.get_summary();
+
+        summary.set_elapsed(500);
+
+        assert_eq!(
+            summary.export_json(false).as_str(),
+            "{\"read\":1,\"parsed\":1,\"rejected\":{},\"types\":{\"deposit\":1},\
+            \"accounts_created\":1,\"accounts_locked\":0,\"deposited\":{\"\":2.0},\"withdrawn\":{},\
+            \"held\":{},\"charged_back\":{},\"elapsed_ms\":500,\"throughput\":2.0}"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use client_accounting::app::importer::MovementImporter;
+use client_accounting::model::fee::FeeSchedule;
+use client_accounting::model::policy::NegativeBalancePolicy;
+
+#[cfg(test)]
+mod transfer_tests {
+
+    /// ### Test `transfer_between_accounts()`
+    /// This test transfers `4.0` from Account (id: '1') to Account (id: '2')
+    /// The Destination Account is created by the Transfer
+    /// Transaction (id: '3') must be marked as processed with `status` (`1`)
+    #[test]
+    fn transfer_between_accounts() {
+        //-------------------------------------
+        //Test Transfer between Accounts
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\ndeposit,1,1,10.0,,\ntransfer,1,3,4.0,,2\n",
+                true
+            ),
+            0
+        );
+
+        let mut vaccounts: Vec<String> = imp
+            .export_accounts_str()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        vaccounts.sort();
+
+        println!("{:?}", vaccounts);
+
+        assert_eq!(
+            vaccounts,
+            vec![
+                "1,6.0,0.0,6.0,false",
+                "2,4.0,0.0,4.0,false",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, 1);
+    }
+
+    /// ### Test `transfer_insufficient_funds()`
+    /// This test transfers more than the `available` Funds
+    /// Both Accounts stay unchanged
+    /// Transaction (id: '3') must be marked as invalid with `status` (`-1`)
+    #[test]
+    fn transfer_insufficient_funds() {
+        //-------------------------------------
+        //Test Transfer on insufficient Funds fails
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\ndeposit,2,2,1.0\n",
+                true
+            ),
+            0
+        );
+        assert_eq!(imp.import_movements_str("transfer,1,3,12.0,,2\n", false), 0);
+
+        let mut vaccounts: Vec<String> = imp
+            .export_accounts_str()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        vaccounts.sort();
+
+        assert_eq!(
+            vaccounts,
+            vec![
+                "1,10.0,0.0,10.0,false",
+                "2,1.0,0.0,1.0,false",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+    }
+
+    /// ### Test `transfer_to_locked_account()`
+    /// This test transfers Funds to a frozen Account
+    /// The Transfer is refused and both Accounts stay unchanged
+    #[test]
+    fn transfer_to_locked_account() {
+        //-------------------------------------
+        //Test Transfer to a locked Account fails
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type, client, tx, amount\ndeposit,1,1,10.0\ndeposit,2,2,1.0\n",
+                true
+            ),
+            0
+        );
+        assert!(imp.freeze_account(2, "operator-7", "fraud check").is_ok());
+        assert_eq!(imp.import_movements_str("transfer,1,3,4.0,,2\n", false), 0);
+
+        let mut vaccounts: Vec<String> = imp
+            .export_accounts_str()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        vaccounts.sort();
+
+        assert_eq!(
+            vaccounts,
+            vec![
+                "1,10.0,0.0,10.0,false",
+                "2,1.0,0.0,1.0,true",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+    }
+
+    /// ### Test `chargeback_on_transfer()`
+    /// This test disputes a Transfer as a Unit
+    /// The transferred Funds are held on the Destination Account
+    /// The Chargeback returns them to the Source Account and locks the Destination Account
+    #[test]
+    fn chargeback_on_transfer() {
+        //-------------------------------------
+        //Test Dispute and Chargeback a Transfer
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\ndeposit,1,1,10.0,,\ntransfer,1,3,4.0,,2\ndispute,1,3,,,\n",
+                true
+            ),
+            0
+        );
+
+        let mut vaccounts: Vec<String> = imp
+            .export_accounts_str()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        vaccounts.sort();
+
+        assert_eq!(
+            vaccounts,
+            vec![
+                "1,6.0,0.0,6.0,false",
+                "2,0.0,4.0,4.0,false",
+                "client,available,held,total,locked"
+            ]
+        );
+
+        assert_eq!(imp.import_movements_str("chargeback,1,3,,,\n", false), 0);
+
+        let mut vaccounts: Vec<String> = imp
+            .export_accounts_str()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        vaccounts.sort();
+
+        println!("{:?}", vaccounts);
+
+        assert_eq!(
+            vaccounts,
+            vec![
+                "1,10.0,0.0,10.0,false",
+                "2,0.0,0.0,0.0,true",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+    }
+
+    /// Imports a Transfer of `4.0` from Account (id: '1') to Account (id: '2')
+    /// which the Destination Account has withdrawn before it is disputed
+    fn dispute_spent_transfer(imp: &mut super::MovementImporter) {
+        imp.set_debug(true);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,1,1,10.0,,\n\
+                transfer,1,3,4.0,,2\n\
+                withdrawal,2,4,4.0,,\n\
+                dispute,1,3,,,\n",
+                true
+            ),
+            0
+        );
+    }
+
+    fn sorted_accounts(imp: &super::MovementImporter) -> Vec<String> {
+        let mut vaccounts: Vec<String> = imp
+            .export_accounts_str()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        vaccounts.sort();
+
+        println!("{:?}", vaccounts);
+
+        vaccounts
+    }
+
+    /// ### Test `resolve_spent_transfer()`
+    /// This test resolves the Dispute of a Transfer after the Destination spent the Funds
+    /// The Dispute holds the Funds on the Destination Account with negative `available` Funds
+    /// The Resolve releases them there and the Source Account is not changed
+    #[test]
+    fn resolve_spent_transfer() {
+        //-------------------------------------
+        //Test Resolve a Transfer spent by the Destination
+
+        let mut imp = super::MovementImporter::new();
+
+        dispute_spent_transfer(&mut imp);
+
+        assert_eq!(
+            sorted_accounts(&imp),
+            vec![
+                "1,6.0,0.0,6.0,false",
+                "2,-4.0,4.0,0.0,false",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().get_holder(), 2);
+
+        assert_eq!(imp.import_movements_str("resolve,1,3,,,\n", false), 0);
+
+        assert_eq!(
+            sorted_accounts(&imp),
+            vec![
+                "1,6.0,0.0,6.0,false",
+                "2,0.0,0.0,0.0,false",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, 1);
+        assert!(imp.audit().is_clean());
+    }
+
+    /// ### Test `chargeback_spent_transfer()`
+    /// This test charges back a Transfer after the Destination spent the Funds
+    /// Without Debt Policy the Chargeback exceeds the `total` Funds of the Destination
+    /// and is refused. With the Debt Policy the Destination Account goes negative and is locked
+    /// and the Source Account gets the Funds back
+    #[test]
+    fn chargeback_spent_transfer() {
+        //-------------------------------------
+        //Test Chargeback a Transfer spent by the Destination
+
+        let mut imp = super::MovementImporter::new();
+
+        dispute_spent_transfer(&mut imp);
+
+        assert_eq!(imp.import_movements_str("chargeback,1,3,,,\n", false), 0);
+
+        assert_eq!(
+            sorted_accounts(&imp),
+            vec![
+                "1,6.0,0.0,6.0,false",
+                "2,-4.0,4.0,0.0,false",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, -2);
+
+        let mut imp = super::MovementImporter::new();
+
+        imp.set_negative_balance_policy(super::NegativeBalancePolicy::Debt);
+
+        dispute_spent_transfer(&mut imp);
+
+        assert_eq!(imp.import_movements_str("chargeback,1,3,,,\n", false), 0);
+
+        assert_eq!(
+            sorted_accounts(&imp),
+            vec![
+                "1,10.0,0.0,10.0,false",
+                "2,-4.0,0.0,-4.0,true",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(imp.get_transaction(3).unwrap().status, -1);
+        assert_eq!(imp.get_transaction(3).unwrap().charged_back, 4.0);
+        assert_eq!(imp.get_transaction(3).unwrap().debt, Some(4.0));
+        assert!(imp.audit().is_clean());
+    }
+
+    /// ### Test `chargeback_fee_on_transfer()`
+    /// This test charges back a Transfer with a Chargeback Fee
+    /// The Fee is charged to the Destination Account which holds the Dispute
+    #[test]
+    fn chargeback_fee_on_transfer() {
+        //-------------------------------------
+        //Test Chargeback Fee of a Transfer
+
+        let mut imp = super::MovementImporter::new();
+        let mut fees = super::FeeSchedule::new();
+
+        assert!(fees
+            .import_rules_str("type,tier,flat,percent,min,max\nchargeback,,1.0,,,\n")
+            .is_ok());
+
+        imp.set_debug(true);
+        imp.set_fee_schedule(fees);
+
+        assert_eq!(
+            imp.import_movements_str(
+                "type,client,tx,amount,original,destination\n\
+                deposit,1,1,10.0,,\n\
+                transfer,1,3,4.0,,2\n\
+                deposit,2,5,2.0,,\n\
+                dispute,1,3,,,\n\
+                chargeback,1,3,,,\n",
+                true
+            ),
+            0
+        );
+
+        assert_eq!(
+            sorted_accounts(&imp),
+            vec![
+                "1,10.0,0.0,10.0,false",
+                "2,1.0,0.0,1.0,true",
+                "client,available,held,total,locked"
+            ]
+        );
+        assert_eq!(
+            imp.export_fee_report_str(),
+            "type,count,income\nchargeback,1,1.0\n"
+        );
+    }
+}